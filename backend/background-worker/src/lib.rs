@@ -1,3 +1,4 @@
+mod metainfo;
 pub mod tasks;
 
 use diesel::{r2d2, PgConnection};
@@ -0,0 +1,150 @@
+use std::path::{Path, PathBuf};
+
+use appstream::Component;
+
+/// Suffixes of the files describing an app. Meson projects usually keep them as templates with
+/// an extra `.in` suffix, which are still valid metainfo.
+const METAINFO_SUFFIXES: &[&str] = &[
+    ".metainfo.xml",
+    ".appdata.xml",
+    ".metainfo.xml.in",
+    ".appdata.xml.in",
+];
+
+/// Checks the repository checked out at `dir` describes the app being submitted, and has a
+/// release entry for the submitted version. The error explains what to fix to the developer.
+pub fn check_metainfo(dir: &Path, app_id: &str, version_tag: &str) -> Result<(), String> {
+    let files = find_metainfo_files(dir).map_err(|e| {
+        tracing::error!("Error searching for metainfo files: {}", e);
+        "Unable to search the repository for metainfo files".to_string()
+    })?;
+
+    if files.is_empty() {
+        return Err(format!(
+            "No metainfo file (*.metainfo.xml or *.appdata.xml) was found at {}",
+            version_tag
+        ));
+    }
+
+    let mut problems = vec![];
+    for file in files {
+        let name = file
+            .strip_prefix(dir)
+            .unwrap_or(&file)
+            .display()
+            .to_string();
+
+        let component = match Component::from_path(file) {
+            Ok(c) => c,
+            Err(e) => {
+                problems.push(format!("{} couldn't be parsed: {}", name, e));
+                continue;
+            }
+        };
+
+        if component.id.0 != app_id {
+            problems.push(format!(
+                "{} is for {} rather than {}",
+                name, component.id.0, app_id
+            ));
+            continue;
+        }
+
+        let version = version_tag.strip_prefix('v').unwrap_or(version_tag);
+        if component
+            .releases
+            .iter()
+            .any(|r| r.version == version_tag || r.version == version)
+        {
+            return Ok(());
+        }
+
+        problems.push(format!(
+            "{} has no <release> entry for version {}",
+            name, version
+        ));
+    }
+
+    Err(problems.join(", "))
+}
+
+fn find_metainfo_files(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+
+        if file_type.is_dir() && !name.starts_with('.') {
+            files.append(&mut find_metainfo_files(&path)?);
+        } else if file_type.is_file() && METAINFO_SUFFIXES.iter().any(|s| name.ends_with(s)) {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METAINFO: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<component type="desktop-application">
+  <id>com.example.app</id>
+  <name>Example</name>
+  <summary>An example app</summary>
+  <metadata_license>CC0-1.0</metadata_license>
+  <releases>
+    <release version="1.1.0" date="2024-01-02"/>
+    <release version="1.0.0" date="2024-01-01"/>
+  </releases>
+</component>
+"#;
+
+    fn repo_with_metainfo(path: &str) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().expect("Couldn't create temporary dir");
+        let file = dir.path().join(path);
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(file, METAINFO).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_check_metainfo() {
+        let repo = repo_with_metainfo("data/com.example.app.metainfo.xml.in");
+
+        assert_eq!(
+            check_metainfo(repo.path(), "com.example.app", "1.1.0"),
+            Ok(())
+        );
+        assert_eq!(
+            check_metainfo(repo.path(), "com.example.app", "v1.0.0"),
+            Ok(())
+        );
+
+        assert_eq!(
+            check_metainfo(repo.path(), "com.example.app", "1.2.0"),
+            Err(
+                "data/com.example.app.metainfo.xml.in has no <release> entry for version 1.2.0"
+                    .into()
+            )
+        );
+        assert_eq!(
+            check_metainfo(repo.path(), "com.example.other", "1.1.0"),
+            Err("data/com.example.app.metainfo.xml.in is for com.example.app rather than com.example.other".into())
+        );
+    }
+
+    #[test]
+    fn test_missing_metainfo() {
+        let repo = repo_with_metainfo(".git/com.example.app.metainfo.xml");
+
+        assert!(check_metainfo(repo.path(), "com.example.app", "1.1.0")
+            .unwrap_err()
+            .starts_with("No metainfo file"));
+    }
+}
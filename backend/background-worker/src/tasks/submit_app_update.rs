@@ -1,3 +1,4 @@
+use common::models::SubmissionState;
 use diesel::{Connection, ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl};
 use fang::{
     serde::{Deserialize, Serialize},
@@ -5,21 +6,26 @@ use fang::{
 };
 use uuid::Uuid;
 
-use crate::GIT_WORKER;
+use crate::{metainfo, GIT_WORKER};
 
 #[derive(Serialize, Deserialize)]
 pub struct SubmitAppUpdate {
     app_id: String,
     version_tag: String,
     user_uuid: Uuid,
+    /// Row in the `submissions` table to record progress in, tasks queued before
+    /// submissions were tracked don't have one
+    #[serde(default)]
+    submission_id: Option<Uuid>,
 }
 
 impl SubmitAppUpdate {
-    pub fn new(app_id: String, version_tag: String, user_uuid: Uuid) -> Self {
+    pub fn new(app_id: String, version_tag: String, user_uuid: Uuid, submission_id: Uuid) -> Self {
         Self {
             app_id,
             version_tag,
             user_uuid,
+            submission_id: Some(submission_id),
         }
     }
 }
@@ -33,7 +39,39 @@ impl Runnable for SubmitAppUpdate {
         let mut con = PgConnection::establish(&settings.database.url)
             .expect("Unable to connect to database to insert apps");
 
-        let repo_url = match get_repo_url_from_db(&mut con, &self.app_id, &self.user_uuid) {
+        let branch_name = format!("appcenter-website/{}-{}", self.app_id, self.version_tag);
+
+        if let Some(submission) = self.submission_id {
+            if let Err(e) = set_submission_pushing(&mut con, &submission, &branch_name) {
+                tracing::error!("Error updating submission state: {}", e);
+            }
+        }
+
+        let result = self.push_update(&mut con, &settings, &branch_name);
+
+        if let Some(submission) = self.submission_id {
+            let recorded = match &result {
+                Ok(pr) => set_submission_pr_open(&mut con, &submission, pr),
+                Err(e) => set_submission_failed(&mut con, &submission, &e.description),
+            };
+
+            if let Err(e) = recorded {
+                tracing::error!("Error updating submission state: {}", e);
+            }
+        }
+
+        result.map(|_| ())
+    }
+}
+
+impl SubmitAppUpdate {
+    fn push_update(
+        &self,
+        con: &mut PgConnection,
+        settings: &common::settings::Settings,
+        branch_name: &str,
+    ) -> Result<github_utils::CreatedPullRequest, FangError> {
+        let repo_url = match get_repo_url_from_db(con, &self.app_id, &self.user_uuid) {
             Ok(r) => r,
             Err(_) => {
                 return Err(FangError {
@@ -42,7 +80,14 @@ impl Runnable for SubmitAppUpdate {
             }
         };
 
-        let branch_name = format!("appcenter-website/{}-{}", self.app_id, self.version_tag);
+        // The repository is stored by the app's owner, so it mustn't reach our internal network
+        if let Err(e) = common::network::check_public_https_url(&repo_url) {
+            tracing::warn!("Refusing to fetch app repository: {}", e);
+            return Err(FangError {
+                description: "The app's repository must be a public https URL".into(),
+            });
+        }
+
         let commit_message = format!("{} version {}", self.app_id, self.version_tag);
 
         let commit_id =
@@ -55,6 +100,26 @@ impl Runnable for SubmitAppUpdate {
                 }
             };
 
+        let checkout =
+            match git_worker::checkout_remote_tag(&repo_url, &self.version_tag, &commit_id) {
+                Ok(c) => c,
+                Err(e) => {
+                    tracing::error!("Error checking out submitted tag: {}", e);
+                    return Err(FangError {
+                        description: "Unable to check out the app's repository at the tag".into(),
+                    });
+                }
+            };
+
+        // Catch the most common reasons for reviewers to bounce a PR before opening it
+        if let Err(reason) =
+            metainfo::check_metainfo(checkout.path(), &self.app_id, &self.version_tag)
+        {
+            return Err(FangError {
+                description: reason,
+            });
+        }
+
         let info = common::models::RepoAppFile {
             source: repo_url,
             commit: commit_id,
@@ -75,10 +140,10 @@ impl Runnable for SubmitAppUpdate {
             });
         }
 
-        if let Err(e) = GIT_WORKER.create_branch(&branch_name) {
+        if let Err(e) = GIT_WORKER.create_branch(branch_name) {
             tracing::error!("Error creating branch: {}", e);
 
-            if let Err(e) = GIT_WORKER.delete_local_branch(&branch_name) {
+            if let Err(e) = GIT_WORKER.delete_local_branch(branch_name) {
                 tracing::error!("Error deleting local branch: {}", e);
             }
 
@@ -99,7 +164,7 @@ impl Runnable for SubmitAppUpdate {
                 tracing::error!("Error changing local branch: {}", e);
             }
 
-            if let Err(e) = GIT_WORKER.delete_local_branch(&branch_name) {
+            if let Err(e) = GIT_WORKER.delete_local_branch(branch_name) {
                 tracing::error!("Error deleting local branch: {}", e);
             }
 
@@ -119,7 +184,7 @@ impl Runnable for SubmitAppUpdate {
                 tracing::error!("Error changing local branch: {}", e);
             }
 
-            if let Err(e) = GIT_WORKER.delete_local_branch(&branch_name) {
+            if let Err(e) = GIT_WORKER.delete_local_branch(branch_name) {
                 tracing::error!("Error deleting local branch: {}", e);
             }
 
@@ -128,7 +193,7 @@ impl Runnable for SubmitAppUpdate {
             });
         }
 
-        if let Err(e) = GIT_WORKER.push(&branch_name) {
+        if let Err(e) = GIT_WORKER.push(branch_name) {
             tracing::error!("Error pushing app: {}", e);
             return Err(FangError {
                 description: "Error pushing to git repo".into(),
@@ -142,22 +207,65 @@ impl Runnable for SubmitAppUpdate {
                 description: "Unable to start tokio runtime for async methods".into(),
             })?;
 
-        if let Err(e) = rt.block_on(github_utils::create_pull_request(
+        rt.block_on(github_utils::create_pull_request(
             &commit_message,
-            &branch_name,
+            branch_name,
             "main",
             "This pull request was automatically generated by the AppCenter website.",
-        )) {
+        ))
+        .map_err(|e| {
             tracing::error!("Error opening pull request: {}", e);
-            return Err(FangError {
+            FangError {
                 description: "Error opening pull request".into(),
-            });
-        }
-
-        Ok(())
+            }
+        })
     }
 }
 
+fn set_submission_pushing(
+    con: &mut PgConnection,
+    submission: &Uuid,
+    branch: &str,
+) -> Result<usize, diesel::result::Error> {
+    use common::schema::submissions::dsl::*;
+
+    diesel::update(submissions.filter(id.eq(submission)))
+        .set((
+            state.eq(SubmissionState::Pushing),
+            branch_name.eq(branch),
+            error.eq(None::<String>),
+        ))
+        .execute(con)
+}
+
+fn set_submission_pr_open(
+    con: &mut PgConnection,
+    submission: &Uuid,
+    pull_request: &github_utils::CreatedPullRequest,
+) -> Result<usize, diesel::result::Error> {
+    use common::schema::submissions::dsl::*;
+
+    diesel::update(submissions.filter(id.eq(submission)))
+        .set((
+            state.eq(SubmissionState::PrOpen),
+            pr_number.eq(i32::try_from(pull_request.number).ok()),
+            pr_url.eq(pull_request.url.as_deref()),
+        ))
+        .execute(con)
+}
+
+fn set_submission_failed(
+    con: &mut PgConnection,
+    submission: &Uuid,
+    message: &str,
+) -> Result<usize, diesel::result::Error> {
+    use common::schema::submissions::dsl::*;
+
+    diesel::update(submissions.filter(id.eq(submission)))
+        .set((state.eq(SubmissionState::Failed), error.eq(message)))
+        .execute(con)
+}
+
 pub fn get_repo_url_from_db(
     con: &mut PgConnection,
     app_id: &str,
@@ -230,4 +338,74 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_submission_states() -> Result<(), diesel::result::Error> {
+        use common::models::{NewSubmission, Submission};
+        use common::schema::{apps, submissions};
+
+        std::env::set_current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/../"))
+            .expect("Couldn't set working directory for test");
+
+        let settings: common::settings::Settings =
+            common::settings::get_settings().expect("Failed to read settings.");
+
+        let mut con = PgConnection::establish(&settings.database.url)
+            .expect("Unable to connect to database to insert apps");
+
+        con.begin_test_transaction()
+            .expect("Unable to begin test transaction");
+
+        diesel::insert_into(apps::table)
+            .values((
+                apps::id.eq("com.github.fakeorg.submittedapp"),
+                apps::repository.eq("https://github.com/fakeorg/submittedapp"),
+            ))
+            .execute(&mut con)?;
+
+        let submission = diesel::insert_into(submissions::table)
+            .values(NewSubmission {
+                app_id: "com.github.fakeorg.submittedapp",
+                user_id: None,
+                version_tag: "1.0.0",
+                state: SubmissionState::Queued,
+            })
+            .returning(submissions::id)
+            .get_result::<Uuid>(&mut con)?;
+
+        let get_submission = |con: &mut PgConnection| {
+            submissions::table
+                .filter(submissions::id.eq(submission))
+                .first::<Submission>(con)
+        };
+
+        set_submission_pushing(&mut con, &submission, "appcenter-website/test-1.0.0")?;
+        let pushing = get_submission(&mut con)?;
+        assert_eq!(pushing.state, SubmissionState::Pushing);
+        assert_eq!(
+            pushing.branch_name.as_deref(),
+            Some("appcenter-website/test-1.0.0")
+        );
+
+        set_submission_failed(&mut con, &submission, "Error pushing to git repo")?;
+        let failed = get_submission(&mut con)?;
+        assert_eq!(failed.state, SubmissionState::Failed);
+        assert_eq!(failed.error.as_deref(), Some("Error pushing to git repo"));
+
+        set_submission_pushing(&mut con, &submission, "appcenter-website/test-1.0.0")?;
+        set_submission_pr_open(
+            &mut con,
+            &submission,
+            &github_utils::CreatedPullRequest {
+                number: 42,
+                url: Some("https://github.com/elementary/appcenter-reviews/pull/42".into()),
+            },
+        )?;
+        let opened = get_submission(&mut con)?;
+        assert_eq!(opened.state, SubmissionState::PrOpen);
+        assert_eq!(opened.error, None);
+        assert_eq!(opened.pr_number, Some(42));
+
+        Ok(())
+    }
 }
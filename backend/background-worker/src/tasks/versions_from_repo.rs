@@ -51,6 +51,10 @@ impl Runnable for VersionsFromRepo {
                 first_seen: Some(f.1.first),
                 last_update: Some(f.1.last),
                 stripe_connect_id: None,
+                suggested_price: None,
+                minimum_price: 0,
+                price_currency: "usd".into(),
+                free_allowed: true,
             })
             .collect::<Vec<_>>();
 
@@ -1,6 +1,11 @@
 pub mod models;
+pub mod network;
 pub mod schema;
+pub mod search;
 pub mod settings;
 pub mod telemetry;
 
 pub const APP_SUMMARIES_REDIS_KEY: &str = "appstream_worker/app_summary";
+pub const APP_DETAILS_REDIS_KEY: &str = "appstream_worker/app_details";
+pub const APP_SEARCH_INDEX_REDIS_KEY: &str = "appstream_worker/search_index";
+pub const APP_CATEGORIES_REDIS_KEY: &str = "appstream_worker/categories";
@@ -0,0 +1,75 @@
+use diesel::{
+    deserialize::{self, FromSql, FromSqlRow},
+    expression::AsExpression,
+    pg::{Pg, PgValue},
+    prelude::*,
+    serialize::{self, Output, ToSql},
+    sql_types::Text,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+use crate::schema::access_tokens;
+
+/// What a personal access token can be used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, AsExpression, FromSqlRow)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[diesel(sql_type = Text)]
+#[serde(rename_all = "kebab-case")]
+pub enum TokenScope {
+    /// Submitting updates of apps
+    Submit,
+    /// Listing apps and their submissions
+    ReadApps,
+}
+
+impl TokenScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Submit => "submit",
+            Self::ReadApps => "read-apps",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for TokenScope {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        <str as ToSql<Text, Pg>>::to_sql(self.as_str(), out)
+    }
+}
+
+impl FromSql<Text, Pg> for TokenScope {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        match <String as FromSql<Text, Pg>>::from_sql(bytes)?.as_str() {
+            "submit" => Ok(Self::Submit),
+            "read-apps" => Ok(Self::ReadApps),
+            other => Err(format!("Unknown token scope: {}", other).into()),
+        }
+    }
+}
+
+/// A personal access token, without its secret
+#[derive(Queryable, Serialize, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AccessToken {
+    pub id: Uuid,
+    #[cfg_attr(feature = "openapi", schema(example = "Release pipeline"))]
+    pub name: String,
+    pub scopes: Vec<TokenScope>,
+    #[cfg_attr(feature = "openapi", schema(format = DateTime, value_type = String))]
+    pub created_at: time::OffsetDateTime,
+    #[cfg_attr(feature = "openapi", schema(format = DateTime, value_type = Option<String>))]
+    pub last_used_at: Option<time::OffsetDateTime>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = access_tokens)]
+pub struct NewAccessToken<'a> {
+    pub user_id: &'a Uuid,
+    pub name: &'a str,
+    pub token_hash: &'a str,
+    pub scopes: &'a [TokenScope],
+}
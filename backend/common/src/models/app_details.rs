@@ -0,0 +1,303 @@
+use std::collections::BTreeMap;
+
+use appstream::{
+    enums::{ContentAttribute, ContentState, ImageKind, ProjectUrl},
+    Component,
+};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+use super::{component_summary::cached_icons, Icon, Localize, TranslatableString};
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ScreenshotImage {
+    #[cfg_attr(
+        feature = "openapi",
+        schema(example = "https://example.com/screenshots/torrential.png")
+    )]
+    url: String,
+    #[cfg_attr(feature = "openapi", schema(example = 1280))]
+    width: Option<u32>,
+    #[cfg_attr(feature = "openapi", schema(example = 720))]
+    height: Option<u32>,
+    /// Whether this is a scaled down version of the source image
+    thumbnail: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct Screenshot {
+    is_default: bool,
+    caption: Option<TranslatableString>,
+    images: Vec<ScreenshotImage>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct Release {
+    #[cfg_attr(feature = "openapi", schema(example = "3.0.0"))]
+    version: String,
+    /// RFC 3339 formatted release date
+    #[cfg_attr(feature = "openapi", schema(example = "2023-03-27T00:00:00+00:00"))]
+    date: Option<String>,
+    /// HTML formatted release notes
+    description: Option<TranslatableString>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum UrlKind {
+    Homepage,
+    BugTracker,
+    Donation,
+    Help,
+    Translate,
+    Faq,
+    Contact,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AppUrl {
+    kind: UrlKind,
+    #[cfg_attr(feature = "openapi", schema(example = "https://example.com"))]
+    url: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ContentRating {
+    /// OARS content attributes and their intensity, e.g. `violence-cartoon: mild`
+    #[cfg_attr(
+        feature = "openapi",
+        schema(example = json!({"violence-cartoon": "mild", "social-chat": "none"}))
+    )]
+    attributes: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AppDetails {
+    #[cfg_attr(
+        feature = "openapi",
+        schema(example = "com.github.davidmhewitt.torrential")
+    )]
+    id: String,
+    name: TranslatableString,
+    summary: Option<TranslatableString>,
+    /// HTML formatted description
+    description: Option<TranslatableString>,
+    icons: Vec<Icon>,
+    screenshots: Vec<Screenshot>,
+    /// Releases in the order they are listed in the AppStream metadata, usually newest first
+    releases: Vec<Release>,
+    urls: Vec<AppUrl>,
+    developer_name: Option<TranslatableString>,
+    content_rating: Option<ContentRating>,
+    /// SPDX license expression of the project
+    #[cfg_attr(feature = "openapi", schema(example = "GPL-2.0-or-later"))]
+    license: Option<String>,
+}
+
+/// Splits an OARS attribute into its ID and intensity, using their names in the metainfo
+fn content_attribute(attribute: &ContentAttribute) -> Option<(String, String)> {
+    use ContentAttribute::*;
+
+    let (id, state) = match *attribute {
+        ViolenceCartoon(s) => ("violence-cartoon", s),
+        ViolenceFantasy(s) => ("violence-fantasy", s),
+        ViolenceRealistic(s) => ("violence-realistic", s),
+        ViolenceBloodshed(s) => ("violence-bloodshed", s),
+        ViolenceSexual(s) => ("violence-sexual", s),
+        ViolenceDesecration(s) => ("violence-desecration", s),
+        ViolenceSlavery(s) => ("violence-slavery", s),
+        ViolenceWorship(s) => ("violence-worship", s),
+        DrugsAlcohol(s) => ("drugs-alcohol", s),
+        DrugsNarcotics(s) => ("drugs-narcotics", s),
+        DrugsTobacco(s) => ("drugs-tobacco", s),
+        SexNudity(s) => ("sex-nudity", s),
+        SexThemes(s) => ("sex-themes", s),
+        SexHomosexuality(s) => ("sex-homosexuality", s),
+        SexProstitution(s) => ("sex-prostitution", s),
+        SexAdultery(s) => ("sex-adultery", s),
+        SexAppearance(s) => ("sex-appearance", s),
+        LanguageProfanity(s) => ("language-profanity", s),
+        LanguageHumor(s) => ("language-humor", s),
+        LanguageDiscrimination(s) => ("language-discrimination", s),
+        SocialChat(s) => ("social-chat", s),
+        SocialInfo(s) => ("social-info", s),
+        SocialAudio(s) => ("social-audio", s),
+        SocialLocation(s) => ("social-location", s),
+        SocialContacts(s) => ("social-contacts", s),
+        MoneyAdvertising(s) => ("money-advertising", s),
+        MoneyPurchasing(s) => ("money-purchasing", s),
+        MoneyGambling(s) => ("money-gambling", s),
+        // Attributes added in newer versions of OARS that we don't know the ID of
+        _ => return None,
+    };
+
+    let value = match state {
+        ContentState::None => "none",
+        ContentState::Mild => "mild",
+        ContentState::Moderate => "moderate",
+        ContentState::Intense => "intense",
+    };
+
+    Some((id.into(), value.into()))
+}
+
+impl Localize for Screenshot {
+    fn localize(&mut self, locales: &[String]) {
+        self.caption.localize(locales);
+    }
+}
+
+impl Localize for Release {
+    fn localize(&mut self, locales: &[String]) {
+        self.description.localize(locales);
+    }
+}
+
+impl Localize for AppDetails {
+    fn localize(&mut self, locales: &[String]) {
+        self.name.localize(locales);
+        self.summary.localize(locales);
+        self.description.localize(locales);
+        self.screenshots.localize(locales);
+        self.releases.localize(locales);
+        self.developer_name.localize(locales);
+    }
+}
+
+impl From<&Component> for AppDetails {
+    fn from(value: &Component) -> Self {
+        Self {
+            id: value.id.0.to_owned(),
+            name: TranslatableString::from(value.name.to_owned()),
+            summary: value.summary.to_owned().map(TranslatableString::from),
+            description: value
+                .description
+                .as_ref()
+                .map(|d| TranslatableString::Translations(d.0.to_owned())),
+            icons: cached_icons(value),
+            screenshots: value
+                .screenshots
+                .iter()
+                .map(|s| Screenshot {
+                    is_default: s.is_default,
+                    caption: s.caption.to_owned().map(TranslatableString::from),
+                    images: s
+                        .images
+                        .iter()
+                        .map(|i| ScreenshotImage {
+                            url: i.url.to_string(),
+                            width: i.width,
+                            height: i.height,
+                            thumbnail: matches!(i.kind, ImageKind::Thumbnail),
+                        })
+                        .collect(),
+                })
+                .collect(),
+            releases: value
+                .releases
+                .iter()
+                .map(|r| Release {
+                    version: r.version.to_owned(),
+                    date: r.date.map(|d| d.to_rfc3339()),
+                    description: r
+                        .description
+                        .as_ref()
+                        .map(|d| TranslatableString::Translations(d.0.to_owned())),
+                })
+                .collect(),
+            urls: value
+                .urls
+                .iter()
+                .filter_map(|u| {
+                    let (kind, url) = match u {
+                        ProjectUrl::Homepage(url) => (UrlKind::Homepage, url),
+                        ProjectUrl::BugTracker(url) => (UrlKind::BugTracker, url),
+                        ProjectUrl::Donation(url) => (UrlKind::Donation, url),
+                        ProjectUrl::Help(url) => (UrlKind::Help, url),
+                        ProjectUrl::Translate(url) => (UrlKind::Translate, url),
+                        ProjectUrl::Faq(url) => (UrlKind::Faq, url),
+                        ProjectUrl::Contact(url) => (UrlKind::Contact, url),
+                        _ => return None,
+                    };
+
+                    Some(AppUrl {
+                        kind,
+                        url: url.to_string(),
+                    })
+                })
+                .collect(),
+            developer_name: value
+                .developer_name
+                .to_owned()
+                .map(TranslatableString::from),
+            content_rating: value.content_rating.as_ref().map(|c| ContentRating {
+                attributes: c.attributes.iter().filter_map(content_attribute).collect(),
+            }),
+            license: value.project_license.as_ref().map(|l| l.0.to_owned()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use appstream::{
+        builders::ComponentBuilder, enums::ProjectUrl, ContentRating, TranslatableString,
+    };
+
+    use super::*;
+
+    #[test]
+    fn from_component() {
+        let component = ComponentBuilder::default()
+            .id("com.example.foo".into())
+            .name(TranslatableString::with_default("Foo"))
+            .url(ProjectUrl::Homepage(
+                "https://example.com".parse().expect("Invalid URL"),
+            ))
+            .content_rating(ContentRating {
+                version: Default::default(),
+                attributes: vec![
+                    ContentAttribute::ViolenceCartoon(ContentState::Mild),
+                    ContentAttribute::SocialChat(ContentState::None),
+                ],
+            })
+            .build();
+
+        let details = AppDetails::from(&component);
+
+        assert_eq!(details.urls.len(), 1);
+        assert_eq!(
+            details.content_rating.map(|c| c.attributes),
+            Some(BTreeMap::from([
+                ("social-chat".to_string(), "none".to_string()),
+                ("violence-cartoon".to_string(), "mild".to_string()),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_content_attribute() {
+        assert_eq!(
+            content_attribute(&ContentAttribute::LanguageDiscrimination(
+                ContentState::Moderate
+            )),
+            Some(("language-discrimination".into(), "moderate".into()))
+        );
+        assert_eq!(
+            content_attribute(&ContentAttribute::MoneyGambling(ContentState::Intense)),
+            Some(("money-gambling".into(), "intense".into()))
+        );
+        assert_eq!(
+            content_attribute(&ContentAttribute::SexHomosexuality(ContentState::None)),
+            Some(("sex-homosexuality".into(), "none".into()))
+        );
+    }
+}
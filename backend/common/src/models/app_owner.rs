@@ -0,0 +1,64 @@
+use diesel::{
+    deserialize::{self, FromSql, FromSqlRow},
+    expression::AsExpression,
+    pg::{Pg, PgValue},
+    serialize::{self, Output, ToSql},
+    sql_types::Text,
+};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+/// What an owner of an app is allowed to do with it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, AsExpression, FromSqlRow)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[diesel(sql_type = Text)]
+#[serde(rename_all = "snake_case")]
+pub enum AppOwnerRole {
+    /// The primary owner, who can do everything including managing the other owners
+    Owner,
+    /// Can submit updates of the app
+    Maintainer,
+    /// Can manage the app's payments and pricing
+    Finance,
+}
+
+impl AppOwnerRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Owner => "owner",
+            Self::Maintainer => "maintainer",
+            Self::Finance => "finance",
+        }
+    }
+
+    pub fn can_submit_updates(&self) -> bool {
+        matches!(self, Self::Owner | Self::Maintainer)
+    }
+
+    pub fn can_manage_payments(&self) -> bool {
+        matches!(self, Self::Owner | Self::Finance)
+    }
+
+    pub fn can_manage_owners(&self) -> bool {
+        matches!(self, Self::Owner)
+    }
+}
+
+impl ToSql<Text, Pg> for AppOwnerRole {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        <str as ToSql<Text, Pg>>::to_sql(self.as_str(), out)
+    }
+}
+
+impl FromSql<Text, Pg> for AppOwnerRole {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        match <String as FromSql<Text, Pg>>::from_sql(bytes)?.as_str() {
+            "owner" => Ok(Self::Owner),
+            "maintainer" => Ok(Self::Maintainer),
+            "finance" => Ok(Self::Finance),
+            other => Err(format!("Unknown app owner role: {}", other).into()),
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use diesel::{
+    deserialize::{self, FromSql, FromSqlRow},
+    expression::AsExpression,
+    pg::{Pg, PgValue},
+    prelude::*,
+    serialize::{self, Output, ToSql},
+    sql_types::Text,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+use crate::schema::audit_events;
+
+/// Something a user did that is kept in the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, AsExpression, FromSqlRow)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[diesel(sql_type = Text)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Login,
+    AppAdded,
+    PaymentsEnabled,
+    StripeAccountCreated,
+    StripeAccountLinked,
+    UpdateSubmitted,
+    AdminUserDeactivated,
+    AdminUserReactivated,
+    AdminAppPublished,
+    AdminAppUnpublished,
+    AdminAppVerificationSet,
+    AdminOwnerVerificationSet,
+    AdminAppFeeSet,
+    AccessTokenCreated,
+    AccessTokenRevoked,
+    AppOwnerInvited,
+    AppInvitationAccepted,
+    AppOwnerRemoved,
+    AppOwnershipTransferred,
+    AppPricingSet,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Login => "login",
+            Self::AppAdded => "app_added",
+            Self::PaymentsEnabled => "payments_enabled",
+            Self::StripeAccountCreated => "stripe_account_created",
+            Self::StripeAccountLinked => "stripe_account_linked",
+            Self::UpdateSubmitted => "update_submitted",
+            Self::AdminUserDeactivated => "admin_user_deactivated",
+            Self::AdminUserReactivated => "admin_user_reactivated",
+            Self::AdminAppPublished => "admin_app_published",
+            Self::AdminAppUnpublished => "admin_app_unpublished",
+            Self::AdminAppVerificationSet => "admin_app_verification_set",
+            Self::AdminOwnerVerificationSet => "admin_owner_verification_set",
+            Self::AdminAppFeeSet => "admin_app_fee_set",
+            Self::AccessTokenCreated => "access_token_created",
+            Self::AccessTokenRevoked => "access_token_revoked",
+            Self::AppOwnerInvited => "app_owner_invited",
+            Self::AppInvitationAccepted => "app_invitation_accepted",
+            Self::AppOwnerRemoved => "app_owner_removed",
+            Self::AppOwnershipTransferred => "app_ownership_transferred",
+            Self::AppPricingSet => "app_pricing_set",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for AuditAction {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        <str as ToSql<Text, Pg>>::to_sql(self.as_str(), out)
+    }
+}
+
+impl FromSql<Text, Pg> for AuditAction {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        match <String as FromSql<Text, Pg>>::from_sql(bytes)?.as_str() {
+            "login" => Ok(Self::Login),
+            "app_added" => Ok(Self::AppAdded),
+            "payments_enabled" => Ok(Self::PaymentsEnabled),
+            "stripe_account_created" => Ok(Self::StripeAccountCreated),
+            "stripe_account_linked" => Ok(Self::StripeAccountLinked),
+            "update_submitted" => Ok(Self::UpdateSubmitted),
+            "admin_user_deactivated" => Ok(Self::AdminUserDeactivated),
+            "admin_user_reactivated" => Ok(Self::AdminUserReactivated),
+            "admin_app_published" => Ok(Self::AdminAppPublished),
+            "admin_app_unpublished" => Ok(Self::AdminAppUnpublished),
+            "admin_app_verification_set" => Ok(Self::AdminAppVerificationSet),
+            "admin_owner_verification_set" => Ok(Self::AdminOwnerVerificationSet),
+            "admin_app_fee_set" => Ok(Self::AdminAppFeeSet),
+            "access_token_created" => Ok(Self::AccessTokenCreated),
+            "access_token_revoked" => Ok(Self::AccessTokenRevoked),
+            "app_owner_invited" => Ok(Self::AppOwnerInvited),
+            "app_invitation_accepted" => Ok(Self::AppInvitationAccepted),
+            "app_owner_removed" => Ok(Self::AppOwnerRemoved),
+            "app_ownership_transferred" => Ok(Self::AppOwnershipTransferred),
+            "app_pricing_set" => Ok(Self::AppPricingSet),
+            other => Err(format!("Unknown audit action: {}", other).into()),
+        }
+    }
+}
+
+#[derive(Queryable, Serialize, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AuditEvent {
+    pub id: Uuid,
+    /// The user who did it, unset if their account was deleted
+    pub actor_id: Option<Uuid>,
+    pub action: AuditAction,
+    /// The app acted on, if any
+    #[cfg_attr(
+        feature = "openapi",
+        schema(example = "com.github.davidmhewitt.torrential")
+    )]
+    pub app_id: Option<String>,
+    /// The user acted on, if any
+    pub target_user_id: Option<Uuid>,
+    /// Action specific details, like the login method or the new value of a setting
+    #[cfg_attr(feature = "openapi", schema(value_type = Object))]
+    pub details: serde_json::Value,
+    #[cfg_attr(feature = "openapi", schema(format = DateTime, value_type = String))]
+    pub created_at: time::OffsetDateTime,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = audit_events)]
+pub struct NewAuditEvent<'a> {
+    pub actor_id: Option<&'a Uuid>,
+    pub action: AuditAction,
+    pub app_id: Option<&'a str>,
+    pub target_user_id: Option<&'a Uuid>,
+    pub details: serde_json::Value,
+}
+
+impl<'a> NewAuditEvent<'a> {
+    pub fn new(actor_id: &'a Uuid, action: AuditAction) -> Self {
+        Self {
+            actor_id: Some(actor_id),
+            action,
+            app_id: None,
+            target_user_id: None,
+            details: serde_json::Value::Object(Default::default()),
+        }
+    }
+
+    pub fn app(self, app_id: &'a str) -> Self {
+        Self {
+            app_id: Some(app_id),
+            ..self
+        }
+    }
+
+    pub fn target_user(self, target_user_id: &'a Uuid) -> Self {
+        Self {
+            target_user_id: Some(target_user_id),
+            ..self
+        }
+    }
+
+    pub fn details(self, details: serde_json::Value) -> Self {
+        Self { details, ..self }
+    }
+}
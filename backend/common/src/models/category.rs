@@ -0,0 +1,50 @@
+use appstream::Component;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+use super::{ComponentSummary, Localize};
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct CategorySummary {
+    #[cfg_attr(feature = "openapi", schema(example = "Graphics"))]
+    pub name: String,
+    /// The number of published apps in this category
+    #[cfg_attr(feature = "openapi", schema(example = 12))]
+    pub count: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct CategoryApps {
+    #[cfg_attr(feature = "openapi", schema(example = "Graphics"))]
+    pub name: String,
+    /// The number of published apps in this category
+    #[cfg_attr(feature = "openapi", schema(example = 12))]
+    pub count: usize,
+    pub apps: Vec<ComponentSummary>,
+}
+
+impl Localize for CategoryApps {
+    fn localize(&mut self, locales: &[String]) {
+        self.apps.localize(locales);
+    }
+}
+
+/// Returns the names of the AppStream categories a component belongs to, e.g. `Graphics`
+pub fn component_categories(component: &Component) -> Vec<String> {
+    component
+        .categories
+        .iter()
+        .filter_map(|c| match serde_json::to_value(c) {
+            Ok(serde_json::Value::String(name)) => Some(name),
+            // Custom categories are serialized as a single-field map, e.g. `{"Unknown": "X-Foo"}`
+            Ok(serde_json::Value::Object(map)) => map
+                .into_iter()
+                .next()
+                .and_then(|(_, v)| v.as_str().map(str::to_owned)),
+            _ => None,
+        })
+        .collect()
+}
@@ -5,14 +5,116 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "openapi")]
 use utoipa::ToSchema;
 
-#[derive(Serialize, Deserialize)]
+/// A string with a translation for each locale, keyed by locale name with `C` being the
+/// untranslated original. Once localized, only the best matching translation is kept.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[cfg_attr(feature = "openapi", derive(ToSchema))]
 #[cfg_attr(feature = "openapi", schema(example = json!({"C": "Welcome", "ja": "いらっしゃいませ"})))]
-pub struct TranslatableString(pub BTreeMap<String, String>);
+#[serde(untagged)]
+pub enum TranslatableString {
+    Translations(BTreeMap<String, String>),
+    Localized(String),
+}
 
 impl TranslatableString {
     pub fn from(original: appstream::TranslatableString) -> Self {
-        Self(original.0)
+        Self::Translations(original.0)
+    }
+
+    /// Collapses the translations down to the best match for the given locales, in order of
+    /// preference, falling back to the untranslated `C` string.
+    pub fn localize(&mut self, locales: &[String]) {
+        if let Self::Translations(translations) = self {
+            let best = best_locale_match(translations, locales)
+                .or_else(|| translations.get("C"))
+                .or_else(|| translations.values().next())
+                .cloned()
+                .unwrap_or_default();
+
+            *self = Self::Localized(best);
+        }
+    }
+}
+
+fn best_locale_match<'a>(
+    translations: &'a BTreeMap<String, String>,
+    locales: &[String],
+) -> Option<&'a String> {
+    let language = |locale: &str| -> String {
+        locale
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase()
+    };
+
+    for locale in locales {
+        if let Some((_, t)) = translations
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(locale))
+        {
+            return Some(t);
+        }
+
+        let wanted_language = language(locale);
+        if let Some(t) = translations.get(&wanted_language) {
+            return Some(t);
+        }
+
+        // The untranslated original is US English, so it's closer than other regional variants
+        if is_us_english(locale) {
+            if let Some(t) = translations.get("C") {
+                return Some(t);
+            }
+        }
+
+        if let Some((_, t)) = translations
+            .iter()
+            .find(|(key, _)| key.as_str() != "C" && language(key) == wanted_language)
+        {
+            return Some(t);
+        }
+    }
+
+    None
+}
+
+/// Whether the locale is English without a region, or as used in the US, e.g. `en_US.UTF-8`
+fn is_us_english(locale: &str) -> bool {
+    let mut parts = locale
+        .split(['.', '@'])
+        .next()
+        .unwrap_or_default()
+        .split(['_', '-']);
+
+    if !parts.next().is_some_and(|l| l.eq_ignore_ascii_case("en")) {
+        return false;
+    }
+
+    match parts.next() {
+        Some(region) => region.eq_ignore_ascii_case("us"),
+        None => true,
+    }
+}
+
+/// A response type containing [`TranslatableString`]s that can be collapsed to a single locale
+pub trait Localize {
+    fn localize(&mut self, locales: &[String]);
+}
+
+impl<T: Localize> Localize for Vec<T> {
+    fn localize(&mut self, locales: &[String]) {
+        for item in self.iter_mut() {
+            item.localize(locales);
+        }
+    }
+}
+
+impl Localize for Option<TranslatableString> {
+    fn localize(&mut self, locales: &[String]) {
+        if let Some(s) = self {
+            s.localize(locales);
+        }
     }
 }
 
@@ -37,28 +139,100 @@ pub struct ComponentSummary {
     icons: Vec<Icon>,
 }
 
+impl Localize for ComponentSummary {
+    fn localize(&mut self, locales: &[String]) {
+        self.name.localize(locales);
+        self.summary.localize(locales);
+    }
+}
+
 impl From<&Component> for ComponentSummary {
     fn from(value: &Component) -> Self {
         Self {
             id: value.id.0.to_owned(),
             name: TranslatableString::from(value.name.to_owned()),
             summary: value.summary.to_owned().map(TranslatableString::from),
-            icons: value
-                .icons
-                .iter()
-                .filter_map(|i| match i {
-                    appstream::enums::Icon::Cached {
-                        path,
-                        width,
-                        height,
-                    } => Some(Icon {
-                        path: path.to_string_lossy().into_owned(),
-                        width: *width,
-                        height: *height,
-                    }),
-                    _ => None,
-                })
-                .collect(),
+            icons: cached_icons(value),
         }
     }
 }
+
+/// Returns the icons of a component that are cached on the Flatpak remote
+pub(crate) fn cached_icons(component: &Component) -> Vec<Icon> {
+    component
+        .icons
+        .iter()
+        .filter_map(|i| match i {
+            appstream::enums::Icon::Cached {
+                path,
+                width,
+                height,
+            } => Some(Icon {
+                path: path.to_string_lossy().into_owned(),
+                width: *width,
+                height: *height,
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translations() -> TranslatableString {
+        TranslatableString::Translations(BTreeMap::from([
+            ("C".to_string(), "Color".to_string()),
+            ("en_GB".to_string(), "Colour".to_string()),
+            ("pt_BR".to_string(), "Cor".to_string()),
+        ]))
+    }
+
+    fn localized(locales: &[&str]) -> TranslatableString {
+        let mut s = translations();
+        s.localize(&locales.iter().map(|l| l.to_string()).collect::<Vec<_>>());
+        s
+    }
+
+    #[test]
+    fn localizing() {
+        let cases = [
+            (vec!["en_GB"], "Colour"),
+            (vec!["en_gb"], "Colour"),
+            (vec!["en"], "Color"),
+            (vec!["en_US"], "Color"),
+            (vec!["en-us.UTF-8"], "Color"),
+            (vec!["en_AU"], "Colour"),
+            (vec!["pt"], "Cor"),
+            (vec!["pt_PT"], "Cor"),
+            (vec!["ja", "en_GB"], "Colour"),
+            (vec!["ja"], "Color"),
+            (vec![], "Color"),
+        ];
+
+        for (locales, expected) in cases {
+            assert_eq!(
+                localized(&locales),
+                TranslatableString::Localized(expected.to_string()),
+                "localizing to {:?}",
+                locales
+            );
+        }
+    }
+
+    #[test]
+    fn serialization() {
+        assert_eq!(
+            serde_json::to_string(&localized(&["en_GB"])).unwrap(),
+            r#""Colour""#
+        );
+        assert_eq!(
+            serde_json::from_str::<TranslatableString>(r#"{"C":"Color"}"#).unwrap(),
+            TranslatableString::Translations(BTreeMap::from([(
+                "C".to_string(),
+                "Color".to_string()
+            )]))
+        );
+    }
+}
@@ -14,6 +14,9 @@ pub struct User {
     pub is_active: bool,
     pub is_admin: bool,
     pub date_joined: time::OffsetDateTime,
+    pub password_changed_at: Option<time::OffsetDateTime>,
+    /// When an administrator deactivated the account, if they did
+    pub deactivated_at: Option<time::OffsetDateTime>,
 }
 
 #[derive(Insertable)]
@@ -46,6 +49,14 @@ pub struct App {
     pub last_update: Option<time::OffsetDateTime>,
     pub is_published: bool,
     pub stripe_connect_id: Option<String>,
+    /// Price the developer suggests paying, in the smallest unit of `price_currency`
+    pub suggested_price: Option<i32>,
+    /// Smallest payment accepted for the app, in the smallest unit of `price_currency`
+    pub minimum_price: i32,
+    /// Lowercase ISO code of the currency the app's prices are set in
+    pub price_currency: String,
+    /// Whether the app can be downloaded without paying
+    pub free_allowed: bool,
 }
 
 #[derive(Insertable, Queryable, PartialEq, Debug, Clone)]
@@ -63,6 +74,46 @@ pub struct NewGithubAuth {
     pub github_refresh_token: Option<String>,
 }
 
+/// A user's account on a forge other than GitHub, such as GitLab or Codeberg
+#[derive(Insertable)]
+#[diesel(table_name = forge_auth)]
+pub struct NewForgeAuth<'a> {
+    pub user_id: &'a Uuid,
+    /// Identifier of the forge, e.g. `gitlab`
+    pub forge: &'a str,
+    pub forge_user_id: &'a str,
+    pub access_token: Option<&'a str>,
+    pub refresh_token: Option<&'a str>,
+}
+
+#[derive(Queryable, PartialEq, Debug, Clone)]
+pub struct Payment {
+    pub id: Uuid,
+    pub checkout_session_id: String,
+    pub payment_intent_id: Option<String>,
+    pub app_id: Option<String>,
+    /// Amount paid in the smallest unit of `currency`
+    pub amount: i64,
+    pub currency: String,
+    pub platform_fee: i64,
+    pub amount_refunded: i64,
+    pub status: String,
+    pub created_at: time::OffsetDateTime,
+    pub updated_at: time::OffsetDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = payments)]
+pub struct NewPayment<'a> {
+    pub checkout_session_id: &'a str,
+    pub payment_intent_id: Option<&'a str>,
+    pub app_id: Option<&'a str>,
+    pub amount: i64,
+    pub currency: &'a str,
+    pub platform_fee: i64,
+    pub status: &'a str,
+}
+
 #[derive(Deserialize, Serialize)]
 pub struct RepoAppFile {
     pub source: String,
@@ -1,7 +1,19 @@
+mod access_token;
+mod app_details;
+mod app_owner;
+mod audit_event;
+mod category;
 mod component_summary;
 mod db;
 mod stripe;
+mod submission;
 
+pub use access_token::*;
+pub use app_details::*;
+pub use app_owner::*;
+pub use audit_event::*;
+pub use category::*;
 pub use component_summary::*;
 pub use db::*;
 pub use stripe::*;
+pub use submission::*;
@@ -0,0 +1,116 @@
+use diesel::{
+    deserialize::{self, FromSql, FromSqlRow},
+    expression::AsExpression,
+    pg::{Pg, PgValue},
+    prelude::*,
+    serialize::{self, Output, ToSql},
+    sql_types::Text,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+use crate::schema::submissions;
+
+/// Progress of an app update submission through the background worker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, AsExpression, FromSqlRow)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[diesel(sql_type = Text)]
+#[serde(rename_all = "snake_case")]
+pub enum SubmissionState {
+    /// Waiting for the background worker to pick it up
+    Queued,
+    /// The update is being committed to the reviews repository
+    Pushing,
+    /// A pull request was opened on the reviews repository
+    PrOpen,
+    /// A reviewer asked for changes on the pull request
+    ChangesRequested,
+    /// The pull request was merged and the update published
+    Merged,
+    /// The pull request was closed without being merged
+    Closed,
+    /// The update couldn't be submitted, see `error`
+    Failed,
+}
+
+impl SubmissionState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::Pushing => "pushing",
+            Self::PrOpen => "pr_open",
+            Self::ChangesRequested => "changes_requested",
+            Self::Merged => "merged",
+            Self::Closed => "closed",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for SubmissionState {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        <str as ToSql<Text, Pg>>::to_sql(self.as_str(), out)
+    }
+}
+
+impl FromSql<Text, Pg> for SubmissionState {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        match <String as FromSql<Text, Pg>>::from_sql(bytes)?.as_str() {
+            "queued" => Ok(Self::Queued),
+            "pushing" => Ok(Self::Pushing),
+            "pr_open" => Ok(Self::PrOpen),
+            "changes_requested" => Ok(Self::ChangesRequested),
+            "merged" => Ok(Self::Merged),
+            "closed" => Ok(Self::Closed),
+            "failed" => Ok(Self::Failed),
+            other => Err(format!("Unknown submission state: {}", other).into()),
+        }
+    }
+}
+
+#[derive(Queryable, Serialize, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct Submission {
+    pub id: Uuid,
+    #[cfg_attr(
+        feature = "openapi",
+        schema(example = "com.github.davidmhewitt.torrential")
+    )]
+    pub app_id: String,
+    /// The user who submitted the update, unset if their account was deleted
+    pub user_id: Option<Uuid>,
+    #[cfg_attr(feature = "openapi", schema(example = "3.0.1"))]
+    pub version_tag: String,
+    pub state: SubmissionState,
+    /// Why the submission failed, if it did
+    pub error: Option<String>,
+    /// Branch of the reviews repository the update was pushed to
+    #[cfg_attr(
+        feature = "openapi",
+        schema(example = "appcenter-website/com.github.davidmhewitt.torrential-3.0.1")
+    )]
+    pub branch_name: Option<String>,
+    #[cfg_attr(feature = "openapi", schema(example = 1234))]
+    pub pr_number: Option<i32>,
+    #[cfg_attr(
+        feature = "openapi",
+        schema(example = "https://github.com/elementary/appcenter-reviews/pull/1234")
+    )]
+    pub pr_url: Option<String>,
+    #[cfg_attr(feature = "openapi", schema(format = DateTime, value_type = String))]
+    pub created_at: time::OffsetDateTime,
+    #[cfg_attr(feature = "openapi", schema(format = DateTime, value_type = String))]
+    pub updated_at: time::OffsetDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = submissions)]
+pub struct NewSubmission<'a> {
+    pub app_id: &'a str,
+    pub user_id: Option<&'a Uuid>,
+    pub version_tag: &'a str,
+    pub state: SubmissionState,
+}
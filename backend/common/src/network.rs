@@ -0,0 +1,173 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs};
+
+use url::{Host, Url};
+
+#[derive(Debug, thiserror::Error)]
+pub enum PublicUrlError {
+    #[error("{0} isn't a valid URL")]
+    Invalid(String),
+    #[error("{0} doesn't use https")]
+    NotHttps(String),
+    #[error("{0} names its host by IP address rather than by domain")]
+    NoDomain(String),
+    #[error("{0} couldn't be resolved: {1}")]
+    Unresolvable(String, std::io::Error),
+    #[error("{0} resolves to non-public address {1}")]
+    NotPublic(String, IpAddr),
+}
+
+/// Whether `ip` can be reached on the public internet, as opposed to loopback, private,
+/// link-local and other special purpose ranges
+pub fn is_public_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public_ipv4(ip),
+            None => is_public_ipv6(ip),
+        },
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        || a == 0
+        || a >= 240
+        // Shared address space used for carrier-grade NAT
+        || (a == 100 && (64..128).contains(&b))
+        // IETF protocol assignments
+        || (a == 192 && b == 0 && c == 0)
+        // Benchmarking
+        || (a == 198 && (b == 18 || b == 19)))
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // Unique local
+        || (first & 0xfe00) == 0xfc00
+        // Link-local
+        || (first & 0xffc0) == 0xfe80
+        // Documentation
+        || (first == 0x2001 && ip.segments()[1] == 0x0db8))
+}
+
+/// Checks `domain` resolved to at least one address and only to public ones
+pub fn check_public_addresses(
+    domain: &str,
+    addrs: impl IntoIterator<Item = SocketAddr>,
+) -> Result<Vec<SocketAddr>, PublicUrlError> {
+    let addrs = addrs.into_iter().collect::<Vec<_>>();
+
+    if addrs.is_empty() {
+        return Err(PublicUrlError::Unresolvable(
+            domain.into(),
+            std::io::Error::new(std::io::ErrorKind::NotFound, "no addresses"),
+        ));
+    }
+
+    if let Some(addr) = addrs.iter().find(|a| !is_public_address(a.ip())) {
+        return Err(PublicUrlError::NotPublic(domain.into(), addr.ip()));
+    }
+
+    Ok(addrs)
+}
+
+/// The domain and port of `url`, which has to be an https URL naming its host by domain
+pub fn https_domain(url: &str) -> Result<(String, u16), PublicUrlError> {
+    let parsed = Url::parse(url).map_err(|_| PublicUrlError::Invalid(url.into()))?;
+
+    if parsed.scheme() != "https" {
+        return Err(PublicUrlError::NotHttps(url.into()));
+    }
+
+    match parsed.host() {
+        Some(Host::Domain(domain)) => Ok((domain.into(), parsed.port().unwrap_or(443))),
+        _ => Err(PublicUrlError::NoDomain(url.into())),
+    }
+}
+
+/// Checks `url` is an https URL whose host only resolves to public addresses, so URLs stored
+/// by users, like app repositories, can't be used to reach the servers' internal network.
+/// This resolves the host, so it blocks.
+pub fn check_public_https_url(url: &str) -> Result<(), PublicUrlError> {
+    let (domain, port) = https_domain(url)?;
+
+    let addrs = (domain.as_str(), port)
+        .to_socket_addrs()
+        .map_err(|e| PublicUrlError::Unresolvable(domain.clone(), e))?;
+
+    check_public_addresses(&domain, addrs).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_public_addresses() {
+        for ip in ["93.184.216.34", "2606:2800:220:1:248:1893:25c8:1946"] {
+            assert!(is_public_address(ip.parse().unwrap()), "{}", ip);
+        }
+
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!is_public_address(ip.parse().unwrap()), "{}", ip);
+        }
+    }
+
+    #[test]
+    fn test_https_domain() {
+        assert_eq!(
+            https_domain("https://github.com/elementary/appcenter.git").unwrap(),
+            ("github.com".into(), 443)
+        );
+        assert_eq!(
+            https_domain("https://git.example.com:8443/app.git").unwrap(),
+            ("git.example.com".into(), 8443)
+        );
+
+        for url in [
+            "not a url",
+            "/srv/git/app.git",
+            "file:///srv/git/app.git",
+            "http://github.com/elementary/appcenter.git",
+            "ssh://git@github.com/elementary/appcenter.git",
+            "https://127.0.0.1/app.git",
+            "https://169.254.169.254/latest/meta-data",
+            "https://[::1]/app.git",
+            "https://2130706433/app.git",
+        ] {
+            assert!(https_domain(url).is_err(), "{}", url);
+        }
+    }
+
+    #[test]
+    fn test_check_public_https_url_refuses_internal_hosts() {
+        assert!(matches!(
+            check_public_https_url("https://localhost/app.git"),
+            Err(PublicUrlError::NotPublic(_, _))
+        ));
+    }
+}
@@ -6,11 +6,35 @@ pub mod sql_types {
     pub struct FangTaskState;
 }
 
+diesel::table! {
+    access_tokens (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        name -> Text,
+        token_hash -> Text,
+        scopes -> Array<Text>,
+        created_at -> Timestamptz,
+        last_used_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    app_invitations (id) {
+        id -> Uuid,
+        app_id -> Text,
+        email -> Text,
+        role -> Text,
+        invited_by -> Uuid,
+        created_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     app_owners (user_id, app_id) {
         user_id -> Uuid,
         app_id -> Text,
         verified_owner -> Bool,
+        role -> Text,
     }
 }
 
@@ -24,6 +48,36 @@ diesel::table! {
         last_update -> Nullable<Timestamptz>,
         is_published -> Bool,
         stripe_connect_id -> Nullable<Text>,
+        fee_percentage -> Nullable<Float8>,
+        fee_minimum -> Nullable<Float8>,
+        fee_maximum -> Nullable<Float8>,
+        suggested_price -> Nullable<Int4>,
+        minimum_price -> Int4,
+        price_currency -> Text,
+        free_allowed -> Bool,
+    }
+}
+
+diesel::table! {
+    audit_events (id) {
+        id -> Uuid,
+        actor_id -> Nullable<Uuid>,
+        action -> Text,
+        app_id -> Nullable<Text>,
+        target_user_id -> Nullable<Uuid>,
+        details -> Jsonb,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    domain_challenges (app_id, user_id) {
+        app_id -> Text,
+        user_id -> Uuid,
+        domain -> Text,
+        token -> Text,
+        created_at -> Timestamptz,
+        verified_at -> Nullable<Timestamptz>,
     }
 }
 
@@ -46,6 +100,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    forge_auth (user_id, forge) {
+        user_id -> Uuid,
+        forge -> Text,
+        forge_user_id -> Text,
+        access_token -> Nullable<Text>,
+        refresh_token -> Nullable<Text>,
+    }
+}
+
 diesel::table! {
     github_auth (user_id) {
         user_id -> Uuid,
@@ -55,6 +119,22 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    payments (id) {
+        id -> Uuid,
+        checkout_session_id -> Text,
+        payment_intent_id -> Nullable<Text>,
+        app_id -> Nullable<Text>,
+        amount -> Int8,
+        currency -> Text,
+        platform_fee -> Int8,
+        amount_refunded -> Int8,
+        status -> Text,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     stripe_accounts (user_id) {
         user_id -> Uuid,
@@ -62,6 +142,22 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    submissions (id) {
+        id -> Uuid,
+        app_id -> Text,
+        user_id -> Nullable<Uuid>,
+        version_tag -> Text,
+        state -> Text,
+        error -> Nullable<Text>,
+        branch_name -> Nullable<Text>,
+        pr_number -> Nullable<Int4>,
+        pr_url -> Nullable<Text>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     user_profile (id) {
         id -> Uuid,
@@ -79,21 +175,40 @@ diesel::table! {
         is_active -> Bool,
         is_admin -> Bool,
         date_joined -> Timestamptz,
+        password_changed_at -> Nullable<Timestamptz>,
+        deactivated_at -> Nullable<Timestamptz>,
     }
 }
 
+diesel::joinable!(access_tokens -> users (user_id));
+diesel::joinable!(app_invitations -> apps (app_id));
+diesel::joinable!(app_invitations -> users (invited_by));
 diesel::joinable!(app_owners -> apps (app_id));
 diesel::joinable!(app_owners -> users (user_id));
+diesel::joinable!(audit_events -> users (actor_id));
+diesel::joinable!(domain_challenges -> apps (app_id));
+diesel::joinable!(domain_challenges -> users (user_id));
+diesel::joinable!(forge_auth -> users (user_id));
 diesel::joinable!(github_auth -> users (user_id));
+diesel::joinable!(payments -> apps (app_id));
 diesel::joinable!(stripe_accounts -> users (user_id));
+diesel::joinable!(submissions -> apps (app_id));
+diesel::joinable!(submissions -> users (user_id));
 diesel::joinable!(user_profile -> users (user_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    access_tokens,
+    app_invitations,
     app_owners,
     apps,
+    audit_events,
+    domain_challenges,
     fang_tasks,
+    forge_auth,
     github_auth,
+    payments,
     stripe_accounts,
+    submissions,
     user_profile,
     users,
 );
@@ -0,0 +1,150 @@
+use std::{collections::BTreeMap, ops::Bound};
+
+use appstream::Component;
+use serde::{Deserialize, Serialize};
+
+const NAME_WEIGHT: u32 = 10;
+const KEYWORD_WEIGHT: u32 = 5;
+const SUMMARY_WEIGHT: u32 = 3;
+const DESCRIPTION_WEIGHT: u32 = 1;
+
+/// Pre-computed, weighted search terms for a single component.
+///
+/// Terms from every locale are indexed so that users can search in their own language.
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq)]
+pub struct SearchDocument {
+    pub terms: BTreeMap<String, u32>,
+}
+
+impl SearchDocument {
+    fn add_text(&mut self, text: &str, weight: u32) {
+        for term in tokenize(text) {
+            let entry = self.terms.entry(term).or_insert(0);
+            *entry = (*entry).max(weight);
+        }
+    }
+
+    /// Scores this document against a set of (already tokenized) query terms.
+    ///
+    /// Every query term must prefix-match at least one indexed term, otherwise the
+    /// document scores 0. Exact matches are worth double a prefix match.
+    pub fn score(&self, query_terms: &[String]) -> u32 {
+        let mut total = 0;
+
+        for query_term in query_terms {
+            let best = self
+                .terms
+                .range::<str, _>((Bound::Included(query_term.as_str()), Bound::Unbounded))
+                .take_while(|(term, _)| term.starts_with(query_term.as_str()))
+                .map(|(term, weight)| {
+                    if term == query_term {
+                        weight * 2
+                    } else {
+                        *weight
+                    }
+                })
+                .max();
+
+            match best {
+                Some(b) => total += b,
+                None => return 0,
+            }
+        }
+
+        total
+    }
+}
+
+impl From<&Component> for SearchDocument {
+    fn from(value: &Component) -> Self {
+        let mut document = Self::default();
+
+        for name in value.name.0.values() {
+            document.add_text(name, NAME_WEIGHT);
+        }
+
+        if let Some(keywords) = &value.keywords {
+            for keyword in keywords.0.values().flatten() {
+                document.add_text(keyword, KEYWORD_WEIGHT);
+            }
+        }
+
+        if let Some(summary) = &value.summary {
+            for summary in summary.0.values() {
+                document.add_text(summary, SUMMARY_WEIGHT);
+            }
+        }
+
+        if let Some(description) = &value.description {
+            for description in description.0.values() {
+                document.add_text(&strip_markup(description), DESCRIPTION_WEIGHT);
+            }
+        }
+
+        document
+    }
+}
+
+/// Splits text into lowercase alphanumeric terms.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+        .collect()
+}
+
+fn strip_markup(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut in_tag = false;
+
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                stripped.push(' ');
+            }
+            _ if !in_tag => stripped.push(c),
+            _ => {}
+        }
+    }
+
+    stripped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenizing() {
+        assert_eq!(
+            tokenize("Torrential: a simple BitTorrent client"),
+            vec!["torrential", "a", "simple", "bittorrent", "client"]
+        );
+        assert!(tokenize("  -- ").is_empty());
+    }
+
+    #[test]
+    fn markup_is_stripped() {
+        assert_eq!(
+            tokenize(&strip_markup("<p>Download <em>torrents</em></p>")),
+            vec!["download", "torrents"]
+        );
+    }
+
+    #[test]
+    fn scoring() {
+        let mut document = SearchDocument::default();
+        document.add_text("Torrential", NAME_WEIGHT);
+        document.add_text("Download torrents", SUMMARY_WEIGHT);
+
+        assert_eq!(document.score(&tokenize("torrential")), NAME_WEIGHT * 2);
+        assert_eq!(document.score(&tokenize("torr")), NAME_WEIGHT);
+        assert_eq!(
+            document.score(&tokenize("torrential download")),
+            NAME_WEIGHT * 2 + SUMMARY_WEIGHT * 2
+        );
+        assert_eq!(document.score(&tokenize("torrential photos")), 0);
+    }
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf};
 
 use secrecy::SecretString;
 
@@ -12,6 +12,8 @@ pub struct Settings {
     pub secret: Secret,
     pub email: EmailSettings,
     pub github: GithubSettings,
+    /// OAuth applications on other forges, keyed by the forge's identifier, e.g. `gitlab`
+    pub forges: HashMap<String, ForgeSettings>,
     pub stripe: StripeSettings,
     pub frontend_url: String,
 }
@@ -24,6 +26,9 @@ pub struct ApplicationSettings {
     pub port: u16,
     pub host: String,
     pub base_url: String,
+    /// Reverse proxies whose `X-Forwarded-For` header is trusted to name the client's address
+    #[serde(default)]
+    pub trusted_proxies: Vec<std::net::IpAddr>,
 }
 
 /// Redis settings for the entire app
@@ -62,11 +67,36 @@ pub struct GithubSettings {
     pub username: String,
     pub access_token: SecretString,
     pub local_repo_path: PathBuf,
+    /// Secret of the webhook on the reviews repository, used to verify events came from GitHub
+    pub webhook_secret: SecretString,
+}
+
+#[derive(serde::Deserialize, Clone)]
+pub struct ForgeSettings {
+    pub client_id: String,
+    pub client_secret: SecretString,
 }
 
 #[derive(serde::Deserialize, Clone)]
 pub struct StripeSettings {
     pub secret_key: SecretString,
+    /// Signing secret of the webhook endpoint, used to verify events came from Stripe
+    pub webhook_secret: SecretString,
+    /// Currencies accepted at checkout, mapped to the smallest payment allowed in whole units
+    /// of that currency
+    pub minimum_amounts: HashMap<String, f64>,
+    pub fee: FeeSettings,
+}
+
+/// How much of each payment the platform keeps, individual apps can override any of these
+#[derive(serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct FeeSettings {
+    /// Share of the payment, from 0 to 100
+    pub percentage: f64,
+    /// Smallest fee charged, in whole units of the payment's currency
+    pub minimum: f64,
+    /// Largest fee charged, in whole units of the payment's currency
+    pub maximum: Option<f64>,
 }
 
 /// The possible runtime environment for our application.
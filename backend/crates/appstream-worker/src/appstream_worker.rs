@@ -1,11 +1,17 @@
 use crate::redis_utils;
-use common::APP_SUMMARIES_REDIS_KEY;
+use common::{
+    models::{component_categories, AppDetails},
+    search::SearchDocument,
+    APP_CATEGORIES_REDIS_KEY, APP_DETAILS_REDIS_KEY, APP_SEARCH_INDEX_REDIS_KEY,
+    APP_SUMMARIES_REDIS_KEY,
+};
 
 use appstream::{enums::Bundle, Collection, Component};
 use http_cache_reqwest::{CACacheManager, Cache, CacheMode, HttpCache, HttpCacheOptions};
 use reqwest::Client;
 use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
 use std::{
+    collections::BTreeMap,
     io::{Error, ErrorKind},
     path::Path,
 };
@@ -58,6 +64,9 @@ impl AppstreamWorker {
         };
 
         self.summarise_apps(&mut collection);
+        self.store_app_details(&collection);
+        self.index_apps(&collection);
+        self.categorise_apps(&collection);
         self.download_icons("https://flatpak.elementary.io", &collection);
     }
 
@@ -94,6 +103,87 @@ impl AppstreamWorker {
         }
     }
 
+    fn store_app_details(&self, collection: &[Component]) {
+        let mut redis_con = self
+            .redis_client
+            .get_connection()
+            .map_err(|e| {
+                tracing::error!("Error getting redis connection: {}", e);
+            })
+            .expect("Redis connection cannot be gotten.");
+
+        let details = collection
+            .iter()
+            .filter_map(|c| match serde_json::ser::to_string(&AppDetails::from(c)) {
+                Ok(d) => Some((c.id.0.to_owned(), d)),
+                Err(e) => {
+                    tracing::warn!("Error serializing app details: {}", e);
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        redis_utils::replace_hash(&mut redis_con, APP_DETAILS_REDIS_KEY, &details);
+    }
+
+    fn index_apps(&self, collection: &[Component]) {
+        let mut redis_con = self
+            .redis_client
+            .get_connection()
+            .map_err(|e| {
+                tracing::error!("Error getting redis connection: {}", e);
+            })
+            .expect("Redis connection cannot be gotten.");
+
+        let documents = collection
+            .iter()
+            .filter_map(
+                |c| match serde_json::ser::to_string(&SearchDocument::from(c)) {
+                    Ok(d) => Some((c.id.0.to_owned(), d)),
+                    Err(e) => {
+                        tracing::warn!("Error serializing search document: {}", e);
+                        None
+                    }
+                },
+            )
+            .collect::<Vec<_>>();
+
+        redis_utils::replace_hash(&mut redis_con, APP_SEARCH_INDEX_REDIS_KEY, &documents);
+    }
+
+    fn categorise_apps(&self, collection: &[Component]) {
+        let mut redis_con = self
+            .redis_client
+            .get_connection()
+            .map_err(|e| {
+                tracing::error!("Error getting redis connection: {}", e);
+            })
+            .expect("Redis connection cannot be gotten.");
+
+        let mut categories: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for c in collection {
+            for category in component_categories(c) {
+                categories
+                    .entry(category)
+                    .or_default()
+                    .push(c.id.0.to_owned());
+            }
+        }
+
+        let members = categories
+            .into_iter()
+            .filter_map(|(category, ids)| match serde_json::ser::to_string(&ids) {
+                Ok(i) => Some((category, i)),
+                Err(e) => {
+                    tracing::warn!("Error serializing category members: {}", e);
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        redis_utils::replace_hash(&mut redis_con, APP_CATEGORIES_REDIS_KEY, &members);
+    }
+
     fn download_icons(&self, base_url: &str, collection: &Vec<Component>) {
         for c in collection {
             for icon in &c.icons {
@@ -5,3 +5,25 @@ pub(crate) async fn hset(redis_con: &mut Connection, key: &str, field: &str, val
         tracing::warn!("Error with redis hset command: {}", e);
     }
 }
+
+/// Atomically replaces the entire contents of a hash, so readers never observe a
+/// partially rebuilt index.
+pub(crate) fn replace_hash(redis_con: &mut Connection, key: &str, entries: &[(String, String)]) {
+    let temp_key = format!("{}_rebuild", key);
+
+    let mut pipe = redis::pipe();
+    pipe.atomic().del(&temp_key).ignore();
+
+    if entries.is_empty() {
+        pipe.del(key).ignore();
+    } else {
+        pipe.hset_multiple(&temp_key, entries)
+            .ignore()
+            .rename(&temp_key, key)
+            .ignore();
+    }
+
+    if let Err(e) = pipe.query::<()>(redis_con) {
+        tracing::warn!("Error replacing redis hash {}: {}", key, e);
+    }
+}
@@ -1,9 +1,9 @@
 use anyhow::Result;
-use std::path::Path;
+use std::{os::raw::c_int, path::Path, sync::Once};
 
 use git2::{build::RepoBuilder, Cred, FetchOptions, RemoteCallbacks, Repository};
 use secrecy::{ExposeSecret, SecretString};
-use tempfile::tempdir;
+use tempfile::{tempdir, TempDir};
 
 use crate::Error;
 
@@ -62,20 +62,92 @@ pub(crate) fn clone_repo(
         .map_err(Error::Git)
 }
 
-pub fn get_remote_commit_id_from_tag(repo_url: &str, tag_name: &str) -> Result<String> {
+/// How long to wait for a git host to accept a connection, and then for each read or write
+const SERVER_CONNECT_TIMEOUT_MS: c_int = 10_000;
+const SERVER_TIMEOUT_MS: c_int = 30_000;
+
+// Options from `git_libgit2_opt_t` in libgit2's `common.h`, which `git2::opts` doesn't wrap yet
+const GIT_OPT_SET_SERVER_CONNECT_TIMEOUT: c_int = 39;
+const GIT_OPT_SET_SERVER_TIMEOUT: c_int = 41;
+
+static SERVER_TIMEOUTS: Once = Once::new();
+
+/// Stops unresponsive git hosts from tying up the calling thread indefinitely
+fn set_server_timeouts() {
+    SERVER_TIMEOUTS.call_once(|| {
+        libgit2_sys::init();
+
+        // SAFETY: both options take a single int and only change libgit2's global settings
+        let results = unsafe {
+            [
+                libgit2_sys::git_libgit2_opts(
+                    GIT_OPT_SET_SERVER_CONNECT_TIMEOUT,
+                    SERVER_CONNECT_TIMEOUT_MS,
+                ),
+                libgit2_sys::git_libgit2_opts(GIT_OPT_SET_SERVER_TIMEOUT, SERVER_TIMEOUT_MS),
+            ]
+        };
+
+        if results.iter().any(|r| *r < 0) {
+            tracing::warn!("Unable to set timeouts for git hosts");
+        }
+    });
+}
+
+/// Looks up the commit a tag of a remote repository points at, or `None` if the repository
+/// doesn't have the tag. Errors mean the repository couldn't be read at all.
+pub fn find_remote_tag(repo_url: &str, tag_name: &str) -> Result<Option<String>> {
+    set_server_timeouts();
+
     let temp_repo_dir = tempdir()?;
     let temp_repo = git2::Repository::init(temp_repo_dir.path())?;
     let mut remote = temp_repo.remote("origin", repo_url)?;
     remote.connect(git2::Direction::Fetch)?;
 
-    let refs = remote.list()?;
-    for r in refs {
-        if r.name() == format!("refs/tags/{}", tag_name) {
-            return Ok(r.oid().to_string());
-        }
-    }
+    let tag_ref = format!("refs/tags/{}", tag_name);
+    let commit_id = remote
+        .list()?
+        .iter()
+        .find(|r| r.name() == tag_ref)
+        .map(|r| r.oid().to_string());
+
+    Ok(commit_id)
+}
+
+pub fn get_remote_commit_id_from_tag(repo_url: &str, tag_name: &str) -> Result<String> {
+    find_remote_tag(repo_url, tag_name)?
+        .ok_or_else(|| anyhow::format_err!("Couldn't find commit id"))
+}
+
+/// Checks out the files of a tagged commit of a remote repository into a temporary directory,
+/// which is removed when the returned handle is dropped. Only the tag is fetched, without its
+/// history.
+pub fn checkout_remote_tag(repo_url: &str, tag_name: &str, commit_id: &str) -> Result<TempDir> {
+    set_server_timeouts();
 
-    Err(anyhow::format_err!("Couldn't find commit id"))
+    let temp_repo_dir = tempdir()?;
+    let temp_repo = git2::Repository::init(temp_repo_dir.path())?;
+    let mut remote = temp_repo.remote("origin", repo_url)?;
+
+    let mut options = FetchOptions::new();
+    options.depth(1);
+    remote.fetch(
+        &[format!("refs/tags/{0}:refs/tags/{0}", tag_name)],
+        Some(&mut options),
+        None,
+    )?;
+
+    // Annotated tags point at a tag object rather than the commit itself
+    let commit = temp_repo
+        .find_object(git2::Oid::from_str(commit_id)?, None)?
+        .peel_to_commit()?;
+
+    temp_repo.checkout_tree(
+        commit.as_object(),
+        Some(git2::build::CheckoutBuilder::new().force()),
+    )?;
+
+    Ok(temp_repo_dir)
 }
 
 #[cfg(test)]
@@ -90,4 +162,36 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn local_remote_tag() -> Result<()> {
+        let repo_dir = tempdir()?;
+        let repo = Repository::init_bare(repo_dir.path())?;
+        let signature = git2::Signature::now("Test", "test@example.com")?;
+        let tree = repo.find_tree(repo.treebuilder(None)?.write()?)?;
+        let commit = repo.commit(Some("HEAD"), &signature, &signature, "Initial", &tree, &[])?;
+        repo.tag_lightweight("1.0.0", &repo.find_object(commit, None)?, false)?;
+
+        let repo_url = repo_dir.path().to_str().unwrap();
+        assert_eq!(
+            find_remote_tag(repo_url, "1.0.0")?,
+            Some(commit.to_string())
+        );
+        assert_eq!(find_remote_tag(repo_url, "2.0.0")?, None);
+        assert!(find_remote_tag("http://127.0.0.1:9/missing.git", "1.0.0").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn checkout_tag() -> Result<()> {
+        let checkout = checkout_remote_tag(
+            "https://github.com/elementary/appcenter.git",
+            "7.2.1",
+            "1e210fe79afe6a0a59e253ca54de92105dbd3efa",
+        )?;
+        assert!(checkout.path().join("meson.build").is_file());
+
+        Ok(())
+    }
 }
@@ -1,7 +1,7 @@
 mod git_utils;
 mod git_worker;
 
-pub use self::git_utils::get_remote_commit_id_from_tag;
+pub use self::git_utils::{checkout_remote_tag, find_remote_tag, get_remote_commit_id_from_tag};
 pub use self::git_worker::GitWorker;
 
 use thiserror::Error;
@@ -1,6 +1,7 @@
 use anyhow::{anyhow, Result};
 use once_cell::sync::Lazy;
 use secrecy::{ExposeSecret, SecretString};
+use serde::Deserialize;
 use url::Url;
 
 #[derive(Debug)]
@@ -23,21 +24,86 @@ pub async fn get_github_repo_owner_id(org: &str, repo: &str) -> Result<GithubOwn
     Err(anyhow!("Unable to find repository owner"))
 }
 
+/// Checks whether the user the access token belongs to is an active admin of the organisation
+/// with the given ID. The token needs the `read:org` scope to see the user's memberships.
 pub async fn is_user_admin_member_of_github_org(
-    _access_token: &SecretString,
-    _org_id: &str,
+    access_token: &SecretString,
+    org_id: &str,
 ) -> Result<bool> {
-    // TODO: Awaiting https://github.com/XAMPPRocky/octocrab/pull/357
+    is_user_admin_member_of_org_at(GITHUB_API_URL, access_token, org_id).await
+}
+
+#[derive(Deserialize)]
+struct OrgMembership {
+    state: String,
+    role: String,
+    organization: MembershipOrganization,
+}
+
+#[derive(Deserialize)]
+struct MembershipOrganization {
+    id: u64,
+}
+
+const MEMBERSHIPS_PER_PAGE: usize = 100;
+
+async fn is_user_admin_member_of_org_at(
+    api_url: &str,
+    access_token: &SecretString,
+    org_id: &str,
+) -> Result<bool> {
+    let client = reqwest::Client::new();
+
+    for page in 1.. {
+        let response = client
+            .get(format!("{}/user/memberships/orgs", api_url))
+            .query(&[
+                ("state", "active".to_string()),
+                ("per_page", MEMBERSHIPS_PER_PAGE.to_string()),
+                ("page", page.to_string()),
+            ])
+            .bearer_auth(access_token.expose_secret())
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+            .header(reqwest::header::USER_AGENT, "elementary AppCenter Website")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "GitHub returned {} when listing organisation memberships",
+                response.status()
+            ));
+        }
+
+        let memberships = response.json::<Vec<OrgMembership>>().await?;
+
+        if memberships.iter().any(|m| {
+            m.organization.id.to_string() == org_id && m.state == "active" && m.role == "admin"
+        }) {
+            return Ok(true);
+        }
+
+        if memberships.len() < MEMBERSHIPS_PER_PAGE {
+            break;
+        }
+    }
 
     Ok(false)
 }
 
+#[derive(Debug)]
+pub struct CreatedPullRequest {
+    pub number: u64,
+    pub url: Option<String>,
+}
+
 pub async fn create_pull_request(
     title: &str,
     src_branch: &str,
     dst_branch: &str,
     body: &str,
-) -> Result<()> {
+) -> Result<CreatedPullRequest> {
     let settings = common::settings::get_settings().expect("Unable to get settings");
 
     let url = Url::parse(&settings.github.reviews_url)?;
@@ -59,15 +125,49 @@ pub async fn create_pull_request(
         path_repo_name
     };
 
-    OCTO.pulls(*path_org_name, path_repo_name)
+    let pull_request = OCTO
+        .pulls(*path_org_name, path_repo_name)
         .create(title, src_branch, dst_branch)
         .body(body)
         .send()
         .await?;
 
-    Ok(())
+    Ok(CreatedPullRequest {
+        number: pull_request.number,
+        url: pull_request.html_url.map(|u| u.to_string()),
+    })
 }
 
+/// Revokes the OAuth grant (and so every token) the user gave this application, so that it no
+/// longer shows as an authorized app in their GitHub settings
+pub async fn revoke_oauth_grant(access_token: &SecretString) -> Result<()> {
+    let settings = common::settings::get_settings().expect("Unable to get settings");
+
+    let response = reqwest::Client::new()
+        .delete(format!(
+            "{}/applications/{}/grant",
+            GITHUB_API_URL, settings.github.client_id
+        ))
+        .basic_auth(
+            &settings.github.client_id,
+            Some(settings.github.client_secret.expose_secret()),
+        )
+        .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+        .header(reqwest::header::USER_AGENT, "elementary AppCenter Website")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&serde_json::json!({ "access_token": access_token.expose_secret() }))
+        .send()
+        .await?;
+
+    // GitHub responds with a 404 if the grant has already been revoked
+    match response.status() {
+        reqwest::StatusCode::NO_CONTENT | reqwest::StatusCode::NOT_FOUND => Ok(()),
+        status => Err(anyhow!("GitHub returned {} when revoking grant", status)),
+    }
+}
+
+const GITHUB_API_URL: &str = "https://api.github.com";
+
 static OCTO: Lazy<octocrab::Octocrab> = Lazy::new(|| {
     let settings = common::settings::get_settings().expect("Unable to get settings");
     octocrab::OctocrabBuilder::new()
@@ -75,3 +175,99 @@ static OCTO: Lazy<octocrab::Octocrab> = Lazy::new(|| {
         .build()
         .expect("Unable to build GitHub client")
 });
+
+#[cfg(test)]
+mod tests {
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+
+    fn membership(org_id: u64, state: &str, role: &str) -> serde_json::Value {
+        serde_json::json!({
+            "url": format!("https://api.github.com/orgs/org{}/memberships/octocat", org_id),
+            "state": state,
+            "role": role,
+            "organization": {
+                "login": format!("org{}", org_id),
+                "id": org_id,
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn test_org_admin_membership() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        let token = SecretString::new("gho_test".into());
+
+        Mock::given(method("GET"))
+            .and(path("/user/memberships/orgs"))
+            .and(header("Authorization", "Bearer gho_test"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vec![
+                membership(1, "active", "member"),
+                membership(2, "active", "admin"),
+                membership(3, "pending", "admin"),
+            ]))
+            .mount(&mock_server)
+            .await;
+
+        assert!(!is_user_admin_member_of_org_at(&mock_server.uri(), &token, "1").await?);
+        assert!(is_user_admin_member_of_org_at(&mock_server.uri(), &token, "2").await?);
+        assert!(!is_user_admin_member_of_org_at(&mock_server.uri(), &token, "3").await?);
+        assert!(!is_user_admin_member_of_org_at(&mock_server.uri(), &token, "4").await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_org_admin_membership_pages() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        let token = SecretString::new("gho_test".into());
+
+        Mock::given(method("GET"))
+            .and(path("/user/memberships/orgs"))
+            .and(query_param("page", "1"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(
+                    (100..100 + MEMBERSHIPS_PER_PAGE as u64)
+                        .map(|id| membership(id, "active", "member"))
+                        .collect::<Vec<_>>(),
+                ),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/user/memberships/orgs"))
+            .and(query_param("page", "2"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(vec![membership(5, "active", "admin")]),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        assert!(is_user_admin_member_of_org_at(&mock_server.uri(), &token, "5").await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_org_admin_membership_without_scope() {
+        let mock_server = MockServer::start().await;
+        let token = SecretString::new("gho_test".into());
+
+        Mock::given(method("GET"))
+            .and(path("/user/memberships/orgs"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&mock_server)
+            .await;
+
+        assert!(
+            is_user_admin_member_of_org_at(&mock_server.uri(), &token, "2")
+                .await
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS payments(
+    id UUID NOT NULL PRIMARY KEY DEFAULT gen_random_uuid(),
+    checkout_session_id TEXT NOT NULL UNIQUE,
+    payment_intent_id TEXT NULL,
+    app_id TEXT NULL REFERENCES apps(id) ON DELETE SET NULL,
+    amount BIGINT NOT NULL,
+    currency TEXT NOT NULL,
+    platform_fee BIGINT NOT NULL DEFAULT 0,
+    amount_refunded BIGINT NOT NULL DEFAULT 0,
+    status TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS payments_app_id ON payments (app_id);
+CREATE INDEX IF NOT EXISTS payments_payment_intent_id ON payments (payment_intent_id);
+
+SELECT diesel_manage_updated_at('payments');
@@ -0,0 +1,4 @@
+ALTER TABLE "apps"
+DROP COLUMN "fee_percentage",
+DROP COLUMN "fee_minimum",
+DROP COLUMN "fee_maximum";
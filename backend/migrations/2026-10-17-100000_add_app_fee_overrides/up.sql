@@ -0,0 +1,4 @@
+ALTER TABLE "apps"
+ADD COLUMN "fee_percentage" DOUBLE PRECISION NULL,
+ADD COLUMN "fee_minimum" DOUBLE PRECISION NULL,
+ADD COLUMN "fee_maximum" DOUBLE PRECISION NULL;
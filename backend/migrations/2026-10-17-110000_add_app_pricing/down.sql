@@ -0,0 +1,5 @@
+ALTER TABLE "apps"
+DROP COLUMN "suggested_price",
+DROP COLUMN "minimum_price",
+DROP COLUMN "price_currency",
+DROP COLUMN "free_allowed";
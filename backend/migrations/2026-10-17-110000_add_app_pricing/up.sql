@@ -0,0 +1,5 @@
+ALTER TABLE "apps"
+ADD COLUMN "suggested_price" INTEGER NULL CHECK ("suggested_price" >= 0),
+ADD COLUMN "minimum_price" INTEGER NOT NULL DEFAULT 0 CHECK ("minimum_price" >= 0),
+ADD COLUMN "price_currency" TEXT NOT NULL DEFAULT 'usd',
+ADD COLUMN "free_allowed" BOOLEAN NOT NULL DEFAULT TRUE;
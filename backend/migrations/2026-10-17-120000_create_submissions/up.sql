@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS submissions(
+    id UUID NOT NULL PRIMARY KEY DEFAULT gen_random_uuid(),
+    app_id TEXT NOT NULL REFERENCES apps(id) ON DELETE CASCADE,
+    user_id UUID NULL REFERENCES users(id) ON DELETE SET NULL,
+    version_tag TEXT NOT NULL,
+    state TEXT NOT NULL DEFAULT 'queued' CHECK (state IN ('queued', 'pushing', 'pr_open', 'failed')),
+    error TEXT NULL,
+    branch_name TEXT NULL,
+    pr_number INTEGER NULL,
+    pr_url TEXT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS submissions_app_id ON submissions (app_id);
+
+SELECT diesel_manage_updated_at('submissions');
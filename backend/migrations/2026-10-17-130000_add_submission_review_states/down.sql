@@ -0,0 +1,5 @@
+UPDATE submissions SET state = 'pr_open' WHERE state IN ('changes_requested', 'merged', 'closed');
+
+ALTER TABLE submissions DROP CONSTRAINT IF EXISTS submissions_state_check;
+ALTER TABLE submissions ADD CONSTRAINT submissions_state_check
+    CHECK (state IN ('queued', 'pushing', 'pr_open', 'failed'));
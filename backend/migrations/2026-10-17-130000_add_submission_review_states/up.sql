@@ -0,0 +1,3 @@
+ALTER TABLE submissions DROP CONSTRAINT IF EXISTS submissions_state_check;
+ALTER TABLE submissions ADD CONSTRAINT submissions_state_check
+    CHECK (state IN ('queued', 'pushing', 'pr_open', 'changes_requested', 'merged', 'closed', 'failed'));
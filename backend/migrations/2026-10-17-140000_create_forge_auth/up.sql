@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS forge_auth(
+    user_id UUID NOT NULL REFERENCES users ON DELETE CASCADE,
+    forge TEXT NOT NULL,
+    forge_user_id TEXT NOT NULL,
+    access_token TEXT NULL,
+    refresh_token TEXT NULL,
+    PRIMARY KEY (user_id, forge),
+    UNIQUE (forge, forge_user_id)
+);
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS domain_challenges(
+    app_id TEXT NOT NULL REFERENCES apps ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users ON DELETE CASCADE,
+    domain TEXT NOT NULL,
+    token TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    verified_at TIMESTAMPTZ NULL,
+    PRIMARY KEY (app_id, user_id)
+);
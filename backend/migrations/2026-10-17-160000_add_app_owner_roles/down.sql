@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS app_invitations;
+ALTER TABLE app_owners DROP COLUMN IF EXISTS role;
@@ -0,0 +1,12 @@
+-- Existing owners keep full control of their apps
+ALTER TABLE app_owners ADD COLUMN role TEXT NOT NULL DEFAULT 'owner' CHECK (role IN ('owner', 'maintainer', 'finance'));
+
+CREATE TABLE IF NOT EXISTS app_invitations(
+    id UUID NOT NULL PRIMARY KEY DEFAULT gen_random_uuid(),
+    app_id TEXT NOT NULL REFERENCES apps(id) ON DELETE CASCADE,
+    email TEXT NOT NULL,
+    role TEXT NOT NULL CHECK (role IN ('owner', 'maintainer', 'finance')),
+    invited_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (app_id, email)
+);
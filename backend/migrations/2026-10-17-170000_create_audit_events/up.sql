@@ -0,0 +1,14 @@
+-- Apps and target users aren't foreign keys so that events outlive what they refer to
+CREATE TABLE IF NOT EXISTS audit_events(
+    id UUID NOT NULL PRIMARY KEY DEFAULT gen_random_uuid(),
+    actor_id UUID NULL REFERENCES users(id) ON DELETE SET NULL,
+    action TEXT NOT NULL,
+    app_id TEXT NULL,
+    target_user_id UUID NULL,
+    details JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS audit_events_created_at ON audit_events (created_at DESC, id);
+CREATE INDEX IF NOT EXISTS audit_events_actor_id ON audit_events (actor_id);
+CREATE INDEX IF NOT EXISTS audit_events_app_id ON audit_events (app_id);
@@ -0,0 +1,2 @@
+-- Sessions started before this time are no longer accepted, e.g. after a password reset
+ALTER TABLE users ADD COLUMN IF NOT EXISTS password_changed_at TIMESTAMPTZ NULL;
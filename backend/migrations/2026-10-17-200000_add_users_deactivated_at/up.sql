@@ -0,0 +1,2 @@
+-- Set by administrators, separately from `is_active` which tracks whether the email address was confirmed
+ALTER TABLE users ADD COLUMN IF NOT EXISTS deactivated_at TIMESTAMPTZ NULL;
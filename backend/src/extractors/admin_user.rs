@@ -0,0 +1,41 @@
+use std::{future::Future, pin::Pin};
+
+use actix_web::{error::ErrorForbidden, Error, FromRequest};
+use serde_json::json;
+
+use super::AuthedUser;
+use crate::types::{ErrorResponse, ErrorTranslationKey};
+
+/// An authenticated user with the `is_admin` flag set
+pub struct AdminUser {
+    pub uuid: uuid::Uuid,
+    pub email: String,
+}
+
+impl FromRequest for AdminUser {
+    type Error = Error;
+
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    #[inline]
+    fn from_request(
+        req: &actix_web::HttpRequest,
+        payload: &mut actix_web::dev::Payload,
+    ) -> Self::Future {
+        let user = AuthedUser::from_request(req, payload);
+        Box::pin(async move {
+            let user = user.await?;
+            if user.is_admin {
+                Ok(AdminUser {
+                    uuid: user.uuid,
+                    email: user.email,
+                })
+            } else {
+                Err(ErrorForbidden(json!(ErrorResponse {
+                    error: "This request requires an administrator".into(),
+                    translation_key: ErrorTranslationKey::AdminOnly,
+                })))
+            }
+        })
+    }
+}
@@ -2,16 +2,23 @@ use std::{future::Future, pin::Pin};
 
 use actix_session::SessionExt;
 use actix_web::{
-    error::{Error, ErrorInternalServerError, ErrorUnauthorized},
+    error::{Error, ErrorForbidden, ErrorInternalServerError, ErrorUnauthorized},
+    http::header,
     web::Data,
-    FromRequest,
+    FromRequest, HttpRequest,
 };
 use diesel_async::{pooled_connection::bb8::Pool, AsyncPgConnection};
 use serde_json::json;
 
+use crate::{
+    types::{ErrorResponse, ErrorTranslationKey},
+    utils::auth::access_tokens::{check_access_token, required_scope},
+};
+
 pub struct AuthedUser {
     pub uuid: uuid::Uuid,
     pub email: String,
+    pub is_admin: bool,
 }
 
 impl FromRequest for AuthedUser {
@@ -23,23 +30,154 @@ impl FromRequest for AuthedUser {
     fn from_request(req: &actix_web::HttpRequest, _: &mut actix_web::dev::Payload) -> Self::Future {
         let req = req.clone();
         Box::pin(async move {
+            let Some(pool) = req.app_data::<Data<Pool<AsyncPgConnection>>>() else {
+                return Err(ErrorInternalServerError(
+                    json!({"error": "Error verifying authorization"}),
+                ));
+            };
+
+            if let Some(token) = bearer_token(&req) {
+                return authorize_access_token(&req, pool, token).await;
+            }
+
             let session = req.get_session();
-            if let Some(pool) = req.app_data::<Data<Pool<AsyncPgConnection>>>() {
-                if let Some(user) = crate::utils::auth::check_auth(session, pool).await {
-                    Ok(AuthedUser {
-                        uuid: user.id,
-                        email: user.email,
-                    })
-                } else {
-                    Err(ErrorUnauthorized(
-                        json!({"error": "This request requires authorization"}),
-                    ))
-                }
+            if let Some(user) = crate::utils::auth::check_auth(session, pool).await {
+                Ok(AuthedUser {
+                    uuid: user.id,
+                    email: user.email,
+                    is_admin: user.is_admin,
+                })
             } else {
-                Err(ErrorInternalServerError(
-                    json!({"error": "Error verifying authorization"}),
+                Err(ErrorUnauthorized(
+                    json!({"error": "This request requires authorization"}),
                 ))
             }
         })
     }
 }
+
+fn bearer_token(req: &HttpRequest) -> Option<&str> {
+    req.headers()
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+}
+
+/// Authenticates a request made with a personal access token, which is only accepted by the
+/// endpoints its scopes cover. Tokens never grant admin access.
+async fn authorize_access_token(
+    req: &HttpRequest,
+    pool: &Pool<AsyncPgConnection>,
+    token: &str,
+) -> Result<AuthedUser, Error> {
+    let Some((user, scopes)) = check_access_token(pool, token).await else {
+        return Err(ErrorUnauthorized(
+            json!({"error": "This request requires authorization"}),
+        ));
+    };
+
+    let scope = req
+        .match_pattern()
+        .and_then(|pattern| required_scope(req.method(), &pattern));
+
+    match scope {
+        Some(scope) if scopes.contains(&scope) => Ok(AuthedUser {
+            uuid: user.id,
+            email: user.email,
+            is_admin: false,
+        }),
+        _ => Err(ErrorForbidden(json!(ErrorResponse {
+            error: "The access token doesn't have a scope allowing this request".into(),
+            translation_key: ErrorTranslationKey::AccessTokenMissingScope,
+        }))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{
+        http::{header, StatusCode},
+        test::TestRequest,
+        App,
+    };
+    use common::models::TokenScope;
+    use diesel::ExpressionMethods;
+    use diesel_async::RunQueryDsl;
+
+    use crate::utils::db_test::{
+        create_access_token, create_app, create_user, test_transaction_pool,
+    };
+
+    use super::*;
+
+    #[actix_web::test]
+    async fn test_access_token_scopes() -> anyhow::Result<()> {
+        use common::schema::apps;
+
+        let pool = test_transaction_pool().await;
+        let server = actix_web::test::init_service(
+            App::new()
+                .app_data(Data::new(pool.clone()))
+                .configure(crate::routes::dashboard_routes_config),
+        )
+        .await;
+
+        let (app, submit_token, read_token) = {
+            let mut con = pool.get().await?;
+
+            let user = create_user(&mut con, true).await?;
+            let app = create_app(&mut con, Some(&user), None).await?;
+
+            diesel::update(apps::table)
+                .filter(apps::id.eq(&app))
+                .set(apps::last_submitted_version.eq("1.0.0"))
+                .execute(&mut con)
+                .await?;
+
+            (
+                app,
+                create_access_token(&mut con, &user, &[TokenScope::Submit]).await?,
+                create_access_token(&mut con, &user, &[TokenScope::ReadApps]).await?,
+            )
+        };
+
+        let submission = |token: &str| {
+            TestRequest::post()
+                .uri("/api/dashboard/submit_app_update")
+                .insert_header((header::AUTHORIZATION, format!("Bearer {}", token)))
+                .set_json(json!({ "app_id": app, "version_tag": "1.0.0" }))
+                .to_request()
+        };
+
+        // The token is accepted, so the handler itself rejects the outdated version
+        let response = actix_web::test::call_service(&server, submission(&submit_token)).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = actix_web::test::read_body_json(response).await;
+        assert_eq!(
+            body["translation_key"],
+            "submit-app-update.version-not-newer"
+        );
+
+        let response = actix_web::test::call_service(&server, submission(&read_token)).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let body = actix_web::test::read_body(response).await;
+        let body: serde_json::Value = serde_json::from_slice(&body)?;
+        assert_eq!(body["translation_key"], "access-tokens.missing-scope");
+
+        // Tokens can't be used to manage tokens, whatever their scopes
+        let request = TestRequest::get()
+            .uri("/api/dashboard/access_tokens")
+            .insert_header((header::AUTHORIZATION, format!("Bearer {}", submit_token)))
+            .to_request();
+        let response = actix_web::test::call_service(&server, request).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response =
+            actix_web::test::call_service(&server, submission("appcenter_pat_unknown")).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        Ok(())
+    }
+}
@@ -1,3 +1,7 @@
+mod admin_user;
 mod authed_user;
+mod preferred_locales;
 
+pub use admin_user::AdminUser;
 pub use authed_user::AuthedUser;
+pub use preferred_locales::{LocaleQuery, PreferredLocales};
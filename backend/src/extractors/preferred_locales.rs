@@ -0,0 +1,131 @@
+use std::future::{ready, Ready};
+
+use actix_web::{error::Error, http::header::ACCEPT_LANGUAGE, web::Query, FromRequest};
+use common::models::Localize;
+use serde::Deserialize;
+#[cfg(feature = "openapi")]
+use utoipa::IntoParams;
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "openapi", derive(IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct LocaleQuery {
+    /// Collapse translatable strings to the best match for this locale, e.g. `pt_BR`. Takes
+    /// precedence over the `Accept-Language` header
+    pub locale: Option<String>,
+}
+
+/// The locales a client asked for with `?locale=` or the `Accept-Language` header, in order of
+/// preference. Empty if the client didn't ask for a locale, in which case every translation is
+/// returned.
+pub struct PreferredLocales(pub Vec<String>);
+
+impl PreferredLocales {
+    /// Collapses the translatable strings in `value` to the preferred locale, if any was requested
+    pub fn apply<T: Localize>(&self, mut value: T) -> T {
+        if !self.0.is_empty() {
+            value.localize(&self.0);
+        }
+
+        value
+    }
+}
+
+impl FromRequest for PreferredLocales {
+    type Error = Error;
+
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    #[inline]
+    fn from_request(req: &actix_web::HttpRequest, _: &mut actix_web::dev::Payload) -> Self::Future {
+        if let Ok(query) = Query::<LocaleQuery>::from_query(req.query_string()) {
+            if let Some(locale) = query.into_inner().locale.filter(|l| !l.is_empty()) {
+                return ready(Ok(PreferredLocales(vec![normalize(&locale)])));
+            }
+        }
+
+        let locales = req
+            .headers()
+            .get(ACCEPT_LANGUAGE)
+            .and_then(|h| h.to_str().ok())
+            .map(parse_accept_language)
+            .unwrap_or_default();
+
+        ready(Ok(PreferredLocales(locales)))
+    }
+}
+
+/// Converts a BCP 47 language tag like `pt-BR` to the AppStream locale format, `pt_BR`
+fn normalize(locale: &str) -> String {
+    locale.trim().replace('-', "_")
+}
+
+/// Parses an `Accept-Language` header value into a list of locales, most preferred first
+fn parse_accept_language(header: &str) -> Vec<String> {
+    let mut weighted = header
+        .split(',')
+        .filter_map(|part| {
+            let mut params = part.split(';');
+            let tag = params.next()?.trim();
+
+            let quality = params
+                .filter_map(|p| p.trim().strip_prefix("q="))
+                .find_map(|q| q.parse::<f32>().ok())
+                .unwrap_or(1.0);
+
+            if tag.is_empty() || tag == "*" || quality <= 0.0 {
+                None
+            } else {
+                Some((normalize(tag), quality))
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // Stable sort keeps the header order for equal weights
+    weighted.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    weighted.into_iter().map(|(tag, _)| tag).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::test::TestRequest;
+
+    use super::*;
+
+    #[test]
+    fn parsing_accept_language() {
+        assert_eq!(
+            parse_accept_language("fr-CH, fr;q=0.9, en;q=0.8, de;q=0.7, *;q=0.5"),
+            vec!["fr_CH", "fr", "en", "de"]
+        );
+        assert_eq!(
+            parse_accept_language("en;q=0.5, ja, pt-BR;q=0, es;q=0.8"),
+            vec!["ja", "es", "en"]
+        );
+        assert!(parse_accept_language("").is_empty());
+    }
+
+    #[actix_web::test]
+    async fn query_parameter_takes_precedence() {
+        let req = TestRequest::default()
+            .uri("/api/apps/search?q=photos&locale=pt-BR")
+            .insert_header((ACCEPT_LANGUAGE, "en-GB"))
+            .to_http_request();
+
+        let locales = PreferredLocales::extract(&req).await.unwrap();
+        assert_eq!(locales.0, vec!["pt_BR"]);
+
+        let req = TestRequest::default()
+            .insert_header((ACCEPT_LANGUAGE, "en-GB"))
+            .to_http_request();
+
+        let locales = PreferredLocales::extract(&req).await.unwrap();
+        assert_eq!(locales.0, vec!["en_GB"]);
+
+        let req = TestRequest::default().to_http_request();
+
+        let locales = PreferredLocales::extract(&req).await.unwrap();
+        assert!(locales.0.is_empty());
+    }
+}
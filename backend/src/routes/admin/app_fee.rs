@@ -0,0 +1,168 @@
+use actix_web::{
+    post,
+    web::{Data, Json, Path},
+    HttpResponse,
+};
+use common::models::{AuditAction, NewAuditEvent};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::{
+    pooled_connection::bb8::{Pool, PooledConnection},
+    AsyncPgConnection, RunQueryDsl,
+};
+use serde_json::json;
+
+use crate::{
+    extractors::AdminUser,
+    types::{dashboard::AppFeeOverride, ErrorResponse, ErrorTranslationKey},
+    utils::audit,
+};
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/admin/apps/{app_id}/fee",
+    request_body = AppFeeOverride,
+    responses(
+        (status = 200, description = "Fee policy overrides for the app were replaced"),
+        (status = 400, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Setting app fee policy", skip(admin, pool))
+)]
+#[post("/apps/{app_id}/fee")]
+pub async fn set_app_fee(
+    admin: AdminUser,
+    path: Path<(String,)>,
+    fee_override: Json<AppFeeOverride>,
+    pool: Data<Pool<AsyncPgConnection>>,
+) -> HttpResponse {
+    if !fee_override.is_valid() {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Fees can't be negative, or the minimum above the maximum".into(),
+            translation_key: ErrorTranslationKey::InvalidFeePolicy,
+        });
+    }
+
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for app fee: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let app_id = path.into_inner().0;
+
+    match set_fee_override(&mut con, &app_id, &fee_override).await {
+        Ok(0) => HttpResponse::NotFound().json(ErrorResponse {
+            error: "Specified app ID was not found".into(),
+            translation_key: ErrorTranslationKey::AppNotFound,
+        }),
+        Ok(_) => {
+            audit::record(
+                &mut con,
+                NewAuditEvent::new(&admin.uuid, AuditAction::AdminAppFeeSet)
+                    .app(&app_id)
+                    .details(json!({
+                        "percentage": fee_override.percentage,
+                        "minimum": fee_override.minimum,
+                        "maximum": fee_override.maximum,
+                    })),
+            )
+            .await;
+
+            HttpResponse::Ok().finish()
+        }
+        Err(e) => {
+            tracing::error!("Error setting fee policy on app: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+async fn set_fee_override(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    app_id: &str,
+    fee_override: &AppFeeOverride,
+) -> Result<usize, diesel::result::Error> {
+    use common::schema::apps;
+
+    diesel::update(apps::table.filter(apps::id.eq(app_id)))
+        .set((
+            apps::fee_percentage.eq(fee_override.percentage),
+            apps::fee_minimum.eq(fee_override.minimum),
+            apps::fee_maximum.eq(fee_override.maximum),
+        ))
+        .execute(con)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use common::settings::FeeSettings;
+    use diesel_async::AsyncConnection;
+
+    use crate::utils::{
+        db_test::{create_app, db_pool},
+        fees::fee_policy_for_app,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_override_validation() {
+        let fee_override = |percentage, minimum, maximum| AppFeeOverride {
+            percentage,
+            minimum,
+            maximum,
+        };
+
+        assert!(fee_override(None, None, None).is_valid());
+        assert!(fee_override(Some(0.0), Some(0.0), None).is_valid());
+        assert!(fee_override(Some(100.0), Some(1.0), Some(1.0)).is_valid());
+        assert!(!fee_override(Some(100.5), None, None).is_valid());
+        assert!(!fee_override(Some(-1.0), None, None).is_valid());
+        assert!(!fee_override(None, Some(2.0), Some(1.0)).is_valid());
+        assert!(!fee_override(None, None, Some(f64::NAN)).is_valid());
+    }
+
+    #[tokio::test]
+    async fn test_set_fee_override() -> anyhow::Result<()> {
+        let db_pool = db_pool().await;
+        let mut con = db_pool.get().await?;
+
+        con.begin_test_transaction().await?;
+
+        let app = create_app(&mut con, None, None).await?;
+        let global = FeeSettings {
+            percentage: 30.0,
+            minimum: 0.5,
+            maximum: None,
+        };
+
+        let fee_override = AppFeeOverride {
+            percentage: Some(5.0),
+            minimum: None,
+            maximum: Some(10.0),
+        };
+
+        assert_eq!(set_fee_override(&mut con, &app, &fee_override).await?, 1);
+        assert_eq!(
+            fee_policy_for_app(&mut con, &app, &global).await?,
+            FeeSettings {
+                percentage: 5.0,
+                minimum: 0.5,
+                maximum: Some(10.0),
+            }
+        );
+
+        assert_eq!(
+            set_fee_override(&mut con, "com.example.missing", &fee_override).await?,
+            0
+        );
+
+        Ok(())
+    }
+}
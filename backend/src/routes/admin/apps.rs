@@ -0,0 +1,300 @@
+use actix_web::{
+    post,
+    web::{Data, Json, Path},
+    HttpResponse,
+};
+use common::models::{AuditAction, NewAuditEvent};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::{
+    pooled_connection::bb8::{Pool, PooledConnection},
+    AsyncPgConnection, RunQueryDsl,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    extractors::AdminUser,
+    types::{admin::Verification, ErrorResponse, ErrorTranslationKey},
+    utils::audit,
+};
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/admin/apps/{app_id}/unpublish",
+    responses(
+        (status = 200, description = "The app no longer appears in listings or search"),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Unpublishing app", skip(admin, pool))
+)]
+#[post("/apps/{app_id}/unpublish")]
+pub async fn unpublish_app(
+    admin: AdminUser,
+    path: Path<(String,)>,
+    pool: Data<Pool<AsyncPgConnection>>,
+) -> HttpResponse {
+    set_app_published_response(&pool, &admin, &path.into_inner().0, false).await
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/admin/apps/{app_id}/publish",
+    responses(
+        (status = 200, description = "The app appears in listings and search again"),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Republishing app", skip(admin, pool))
+)]
+#[post("/apps/{app_id}/publish")]
+pub async fn publish_app(
+    admin: AdminUser,
+    path: Path<(String,)>,
+    pool: Data<Pool<AsyncPgConnection>>,
+) -> HttpResponse {
+    set_app_published_response(&pool, &admin, &path.into_inner().0, true).await
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/admin/apps/{app_id}/verification",
+    request_body = Verification,
+    responses(
+        (status = 200, description = "Whether the app is verified was changed"),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Setting app verification", skip(admin, pool))
+)]
+#[post("/apps/{app_id}/verification")]
+pub async fn set_app_verification(
+    admin: AdminUser,
+    path: Path<(String,)>,
+    verification: Json<Verification>,
+    pool: Data<Pool<AsyncPgConnection>>,
+) -> HttpResponse {
+    let app_id = path.into_inner().0;
+
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for app verification: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    match set_app_verified(&mut con, &app_id, verification.verified).await {
+        Ok(0) => app_not_found_response(),
+        Ok(_) => {
+            audit::record(
+                &mut con,
+                NewAuditEvent::new(&admin.uuid, AuditAction::AdminAppVerificationSet)
+                    .app(&app_id)
+                    .details(json!({ "verified": verification.verified })),
+            )
+            .await;
+
+            HttpResponse::Ok().finish()
+        }
+        Err(e) => {
+            tracing::error!("Error setting app verification: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/admin/apps/{app_id}/owners/{user_id}/verification",
+    request_body = Verification,
+    responses(
+        (status = 200, description = "Whether the user is a verified owner of the app was changed"),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Setting app owner verification", skip(admin, pool))
+)]
+#[post("/apps/{app_id}/owners/{user_id}/verification")]
+pub async fn set_owner_verification(
+    admin: AdminUser,
+    path: Path<(String, Uuid)>,
+    verification: Json<Verification>,
+    pool: Data<Pool<AsyncPgConnection>>,
+) -> HttpResponse {
+    let (app_id, user_id) = path.into_inner();
+
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for owner verification: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    match set_owner_verified(&mut con, &app_id, &user_id, verification.verified).await {
+        Ok(0) => HttpResponse::NotFound().json(ErrorResponse {
+            error: "The user isn't an owner of this app".into(),
+            translation_key: ErrorTranslationKey::AppOwnerNotFound,
+        }),
+        Ok(_) => {
+            audit::record(
+                &mut con,
+                NewAuditEvent::new(&admin.uuid, AuditAction::AdminOwnerVerificationSet)
+                    .app(&app_id)
+                    .target_user(&user_id)
+                    .details(json!({ "verified": verification.verified })),
+            )
+            .await;
+
+            HttpResponse::Ok().finish()
+        }
+        Err(e) => {
+            tracing::error!("Error setting owner verification: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+fn app_not_found_response() -> HttpResponse {
+    HttpResponse::NotFound().json(ErrorResponse {
+        error: "Specified app ID was not found".into(),
+        translation_key: ErrorTranslationKey::AppNotFound,
+    })
+}
+
+async fn set_app_published_response(
+    pool: &Pool<AsyncPgConnection>,
+    admin: &AdminUser,
+    app_id: &str,
+    published: bool,
+) -> HttpResponse {
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for publishing app: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    match set_app_published(&mut con, app_id, published).await {
+        Ok(0) => app_not_found_response(),
+        Ok(_) => {
+            let action = if published {
+                AuditAction::AdminAppPublished
+            } else {
+                AuditAction::AdminAppUnpublished
+            };
+            audit::record(
+                &mut con,
+                NewAuditEvent::new(&admin.uuid, action).app(app_id),
+            )
+            .await;
+
+            HttpResponse::Ok().finish()
+        }
+        Err(e) => {
+            tracing::error!("Error changing whether app is published: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+async fn set_app_published(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    app_id: &str,
+    published: bool,
+) -> Result<usize, diesel::result::Error> {
+    use common::schema::apps;
+
+    diesel::update(apps::table.filter(apps::id.eq(app_id)))
+        .set(apps::is_published.eq(published))
+        .execute(con)
+        .await
+}
+
+async fn set_app_verified(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    app_id: &str,
+    verified: bool,
+) -> Result<usize, diesel::result::Error> {
+    use common::schema::apps;
+
+    diesel::update(apps::table.filter(apps::id.eq(app_id)))
+        .set(apps::is_verified.eq(verified))
+        .execute(con)
+        .await
+}
+
+async fn set_owner_verified(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    app_id: &str,
+    user_id: &Uuid,
+    verified: bool,
+) -> Result<usize, diesel::result::Error> {
+    use common::schema::app_owners;
+
+    diesel::update(
+        app_owners::table
+            .filter(app_owners::app_id.eq(app_id))
+            .filter(app_owners::user_id.eq(user_id)),
+    )
+    .set(app_owners::verified_owner.eq(verified))
+    .execute(con)
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel_async::AsyncConnection;
+
+    use crate::utils::db_test::{create_app, create_user, db_pool};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_moderate_app() -> anyhow::Result<()> {
+        use common::schema::{app_owners, apps};
+
+        let db_pool = db_pool().await;
+        let mut con = db_pool.get().await?;
+
+        con.begin_test_transaction().await?;
+
+        let owner = create_user(&mut con, true).await?;
+        let app = create_app(&mut con, Some(&owner), None).await?;
+
+        assert_eq!(set_app_published(&mut con, &app, false).await?, 1);
+        assert_eq!(set_app_verified(&mut con, &app, true).await?, 1);
+        assert_eq!(set_owner_verified(&mut con, &app, &owner, false).await?, 1);
+        assert_eq!(
+            set_app_published(&mut con, "com.example.missing", true).await?,
+            0
+        );
+
+        let (published, verified) = apps::table
+            .filter(apps::id.eq(&app))
+            .select((apps::is_published, apps::is_verified))
+            .get_result::<(bool, bool)>(&mut con)
+            .await?;
+        assert_eq!((published, verified), (false, true));
+
+        let verified_owner = app_owners::table
+            .filter(app_owners::app_id.eq(&app))
+            .filter(app_owners::user_id.eq(owner))
+            .select(app_owners::verified_owner)
+            .get_result::<bool>(&mut con)
+            .await?;
+        assert!(!verified_owner);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,192 @@
+use actix_web::{
+    get,
+    web::{Data, Query},
+    HttpResponse,
+};
+use common::models::{AuditAction, AuditEvent};
+use diesel::{BoolExpressionMethods, ExpressionMethods, QueryDsl};
+use diesel_async::{
+    pooled_connection::bb8::{Pool, PooledConnection},
+    AsyncPgConnection, RunQueryDsl,
+};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{
+    extractors::AdminUser,
+    types::{admin::AuditEventParameters, pagination::Page},
+    utils::pagination::{into_page, invalid_cursor_response, Cursor},
+};
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/admin/audit_events",
+    params(AuditEventParameters),
+    responses(
+        (status = 200, description = "Page of audit events, newest first", body = AuditEventPage),
+        (status = 400, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Listing audit events", skip(_admin, pool))
+)]
+#[get("/audit_events")]
+pub async fn list_audit_events(
+    _admin: AdminUser,
+    parameters: Query<AuditEventParameters>,
+    pool: Data<Pool<AsyncPgConnection>>,
+) -> HttpResponse {
+    let after = match parameters.after.as_deref().map(Cursor::decode) {
+        None => None,
+        Some(Some(Cursor {
+            id,
+            timestamp: Some(timestamp),
+        })) => match Uuid::parse_str(&id) {
+            Ok(id) => Some((id, timestamp)),
+            Err(_) => return invalid_cursor_response(),
+        },
+        Some(_) => return invalid_cursor_response(),
+    };
+
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for audit events: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let filter = AuditEventFilter {
+        actor_id: parameters.actor_id,
+        app_id: parameters.app_id.as_deref(),
+        action: parameters.action,
+    };
+
+    match get_audit_events(&mut con, &filter, after, parameters.page_size()).await {
+        Ok(page) => HttpResponse::Ok().json(page),
+        Err(e) => {
+            tracing::error!("Error getting audit events: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+#[derive(Default)]
+struct AuditEventFilter<'a> {
+    actor_id: Option<Uuid>,
+    app_id: Option<&'a str>,
+    action: Option<AuditAction>,
+}
+
+async fn get_audit_events(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    filter: &AuditEventFilter<'_>,
+    after: Option<(Uuid, OffsetDateTime)>,
+    page_size: i64,
+) -> Result<Page<AuditEvent>, diesel::result::Error> {
+    use common::schema::audit_events;
+
+    let mut query = audit_events::table
+        .order((audit_events::created_at.desc(), audit_events::id.asc()))
+        .limit(page_size + 1)
+        .into_boxed();
+
+    if let Some(actor_id) = filter.actor_id {
+        query = query.filter(audit_events::actor_id.eq(actor_id));
+    }
+
+    if let Some(app_id) = filter.app_id {
+        query = query.filter(audit_events::app_id.eq(app_id.to_owned()));
+    }
+
+    if let Some(action) = filter.action {
+        query = query.filter(audit_events::action.eq(action));
+    }
+
+    if let Some((cursor_id, cursor_timestamp)) = after {
+        query = query.filter(
+            audit_events::created_at
+                .lt(cursor_timestamp)
+                .or(audit_events::created_at
+                    .eq(cursor_timestamp)
+                    .and(audit_events::id.gt(cursor_id))),
+        );
+    }
+
+    let events = query.load::<AuditEvent>(con).await?;
+
+    Ok(into_page(events, page_size, |e| Cursor {
+        id: e.id.to_string(),
+        timestamp: Some(e.created_at),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use common::models::NewAuditEvent;
+    use diesel_async::AsyncConnection;
+
+    use crate::utils::{
+        audit,
+        db_test::{create_user, db_pool},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_get_audit_events() -> anyhow::Result<()> {
+        let db_pool = db_pool().await;
+        let mut con = db_pool.get().await?;
+
+        con.begin_test_transaction().await?;
+
+        let admin = create_user(&mut con, true).await?;
+        let user = create_user(&mut con, true).await?;
+
+        audit::record(
+            &mut con,
+            NewAuditEvent::new(&admin, AuditAction::AdminUserDeactivated).target_user(&user),
+        )
+        .await;
+        audit::record(
+            &mut con,
+            NewAuditEvent::new(&admin, AuditAction::AdminAppPublished).app("com.example.audit"),
+        )
+        .await;
+        audit::record(&mut con, NewAuditEvent::new(&user, AuditAction::Login)).await;
+
+        let by_admin = AuditEventFilter {
+            actor_id: Some(admin),
+            ..Default::default()
+        };
+
+        let first = get_audit_events(&mut con, &by_admin, None, 1).await?;
+        assert_eq!(first.items.len(), 1);
+        let cursor = first.next.as_deref().and_then(Cursor::decode).unwrap();
+        let after = (Uuid::parse_str(&cursor.id)?, cursor.timestamp.unwrap());
+
+        let second = get_audit_events(&mut con, &by_admin, Some(after), 1).await?;
+        assert_eq!(second.items.len(), 1);
+        assert_eq!(second.next, None);
+        assert_ne!(first.items[0].id, second.items[0].id);
+
+        let logins = AuditEventFilter {
+            action: Some(AuditAction::Login),
+            ..Default::default()
+        };
+        let page = get_audit_events(&mut con, &logins, None, 10).await?;
+        assert!(page.items.iter().all(|e| e.action == AuditAction::Login));
+        assert!(page.items.iter().any(|e| e.actor_id == Some(user)));
+
+        let app = AuditEventFilter {
+            app_id: Some("com.example.audit"),
+            ..Default::default()
+        };
+        let page = get_audit_events(&mut con, &app, None, 10).await?;
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].action, AuditAction::AdminAppPublished);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,19 @@
+pub mod app_fee;
+pub mod apps;
+pub mod audit_events;
+pub mod users;
+
+pub fn admin_routes_config(cfg: &mut actix_web::web::ServiceConfig) {
+    cfg.service(
+        actix_web::web::scope("/api/admin")
+            .service(app_fee::set_app_fee)
+            .service(apps::publish_app)
+            .service(apps::set_app_verification)
+            .service(apps::set_owner_verification)
+            .service(apps::unpublish_app)
+            .service(audit_events::list_audit_events)
+            .service(users::deactivate_user)
+            .service(users::list_users)
+            .service(users::reactivate_user),
+    );
+}
@@ -0,0 +1,305 @@
+use actix_web::{
+    get, post,
+    web::{Data, Path, Query},
+    HttpResponse,
+};
+use common::models::{AuditAction, NewAuditEvent};
+use diesel::{ExpressionMethods, PgTextExpressionMethods, QueryDsl};
+use diesel_async::{
+    pooled_connection::bb8::{Pool, PooledConnection},
+    AsyncPgConnection, RunQueryDsl,
+};
+use uuid::Uuid;
+
+use crate::{
+    extractors::AdminUser,
+    types::{
+        admin::UserSearchParameters, pagination::Page, ErrorResponse, ErrorTranslationKey,
+        UserVisible,
+    },
+    utils::{
+        audit,
+        pagination::{into_page, invalid_cursor_response, Cursor},
+    },
+};
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/admin/users",
+    params(UserSearchParameters),
+    responses(
+        (status = 200, description = "Page of users, ordered by email address", body = UserPage),
+        (status = 400, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Listing users for admin", skip(_admin, pool))
+)]
+#[get("/users")]
+pub async fn list_users(
+    _admin: AdminUser,
+    parameters: Query<UserSearchParameters>,
+    pool: Data<Pool<AsyncPgConnection>>,
+) -> HttpResponse {
+    let after = match parameters.after.as_deref().map(Cursor::decode) {
+        None => None,
+        Some(Some(Cursor { id, timestamp: _ })) => Some(id),
+        Some(None) => return invalid_cursor_response(),
+    };
+
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for users: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    match search_users(
+        &mut con,
+        parameters.q.as_deref(),
+        after.as_deref(),
+        parameters.page_size(),
+    )
+    .await
+    {
+        Ok(page) => HttpResponse::Ok().json(page),
+        Err(e) => {
+            tracing::error!("Error searching users: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/admin/users/{user_id}/deactivate",
+    responses(
+        (status = 200, description = "The user can no longer sign in, and their sessions stopped working"),
+        (status = 400, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Deactivating user", skip(admin, pool))
+)]
+#[post("/users/{user_id}/deactivate")]
+pub async fn deactivate_user(
+    admin: AdminUser,
+    path: Path<(Uuid,)>,
+    pool: Data<Pool<AsyncPgConnection>>,
+) -> HttpResponse {
+    let user_id = path.into_inner().0;
+
+    if user_id == admin.uuid {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Administrators can't deactivate their own account".into(),
+            translation_key: ErrorTranslationKey::CannotDeactivateSelf,
+        });
+    }
+
+    set_user_deactivated_response(&pool, &admin, &user_id, true).await
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/admin/users/{user_id}/reactivate",
+    responses(
+        (status = 200, description = "The user can sign in again"),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Reactivating user", skip(admin, pool))
+)]
+#[post("/users/{user_id}/reactivate")]
+pub async fn reactivate_user(
+    admin: AdminUser,
+    path: Path<(Uuid,)>,
+    pool: Data<Pool<AsyncPgConnection>>,
+) -> HttpResponse {
+    set_user_deactivated_response(&pool, &admin, &path.into_inner().0, false).await
+}
+
+async fn set_user_deactivated_response(
+    pool: &Pool<AsyncPgConnection>,
+    admin: &AdminUser,
+    user_id: &Uuid,
+    deactivated: bool,
+) -> HttpResponse {
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for users: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    match set_user_deactivated(&mut con, user_id, deactivated).await {
+        Ok(0) => HttpResponse::NotFound().json(ErrorResponse {
+            error: "Specified user was not found".into(),
+            translation_key: ErrorTranslationKey::UserDoesntExist,
+        }),
+        Ok(_) => {
+            let action = if deactivated {
+                AuditAction::AdminUserDeactivated
+            } else {
+                AuditAction::AdminUserReactivated
+            };
+            audit::record(
+                &mut con,
+                NewAuditEvent::new(&admin.uuid, action).target_user(user_id),
+            )
+            .await;
+
+            HttpResponse::Ok().finish()
+        }
+        Err(e) => {
+            tracing::error!("Error changing whether user is deactivated: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+/// Escapes the wildcards of `LIKE` patterns so searches match the text literally
+fn like_pattern(text: &str) -> String {
+    format!(
+        "%{}%",
+        text.replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    )
+}
+
+async fn search_users(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    search: Option<&str>,
+    after: Option<&str>,
+    page_size: i64,
+) -> Result<Page<UserVisible>, diesel::result::Error> {
+    use common::schema::users;
+
+    let mut query = users::table
+        .select((
+            users::id,
+            users::email,
+            users::is_active,
+            users::is_admin,
+            users::deactivated_at.is_not_null(),
+        ))
+        .order(users::email.asc())
+        .limit(page_size + 1)
+        .into_boxed();
+
+    if let Some(search) = search.map(str::trim).filter(|s| !s.is_empty()) {
+        query = query.filter(users::email.ilike(like_pattern(search)));
+    }
+
+    if let Some(after) = after {
+        query = query.filter(users::email.gt(after.to_owned()));
+    }
+
+    let rows = query
+        .load::<(Uuid, String, bool, bool, bool)>(con)
+        .await?
+        .into_iter()
+        .map(
+            |(id, email, is_active, is_admin, is_deactivated)| UserVisible {
+                id,
+                email,
+                is_active,
+                is_admin,
+                is_deactivated,
+            },
+        )
+        .collect();
+
+    Ok(into_page(rows, page_size, |u| Cursor {
+        id: u.email.to_owned(),
+        timestamp: None,
+    }))
+}
+
+/// Deactivating keeps `is_active` as it is, so confirming the email address again can't undo it
+async fn set_user_deactivated(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    user_id: &Uuid,
+    deactivated: bool,
+) -> Result<usize, diesel::result::Error> {
+    use common::schema::users;
+
+    let deactivated_at = deactivated.then(time::OffsetDateTime::now_utc);
+
+    diesel::update(users::table.filter(users::id.eq(user_id)))
+        .set(users::deactivated_at.eq(deactivated_at))
+        .execute(con)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel_async::AsyncConnection;
+
+    use crate::utils::db_test::{create_user, db_pool};
+
+    use super::*;
+
+    #[test]
+    fn test_like_pattern() {
+        assert_eq!(like_pattern("example"), "%example%");
+        assert_eq!(like_pattern("a_b%c\\"), "%a\\_b\\%c\\\\%");
+    }
+
+    #[tokio::test]
+    async fn test_search_and_deactivate_users() -> anyhow::Result<()> {
+        use common::schema::users;
+
+        let db_pool = db_pool().await;
+        let mut con = db_pool.get().await?;
+
+        con.begin_test_transaction().await?;
+
+        let user = create_user(&mut con, true).await?;
+        let email = users::table
+            .filter(users::id.eq(user))
+            .select(users::email)
+            .get_result::<String>(&mut con)
+            .await?;
+
+        let search = email.split('@').next().unwrap().to_uppercase();
+        let page = search_users(&mut con, Some(&search), None, 10).await?;
+        assert_eq!(
+            page.items,
+            vec![UserVisible {
+                id: user,
+                email: email.clone(),
+                is_active: true,
+                is_admin: false,
+                is_deactivated: false,
+            }]
+        );
+        assert_eq!(page.next, None);
+
+        let after = search_users(&mut con, Some(&search), Some(&email), 10).await?;
+        assert!(after.items.is_empty());
+
+        assert_eq!(set_user_deactivated(&mut con, &user, true).await?, 1);
+        assert_eq!(
+            set_user_deactivated(&mut con, &Uuid::new_v4(), true).await?,
+            0
+        );
+
+        let page = search_users(&mut con, Some(&search), None, 10).await?;
+        assert!(page.items[0].is_active);
+        assert!(page.items[0].is_deactivated);
+
+        assert_eq!(set_user_deactivated(&mut con, &user, false).await?, 1);
+        let page = search_users(&mut con, Some(&search), None, 10).await?;
+        assert!(!page.items[0].is_deactivated);
+
+        Ok(())
+    }
+}
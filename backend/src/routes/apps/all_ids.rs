@@ -1,20 +1,33 @@
-use actix_web::{get, web::Data, HttpResponse};
-use common::models::App;
-use diesel::{query_dsl::methods::FilterDsl, ExpressionMethods};
+use actix_web::{
+    get,
+    web::{Data, Query},
+    HttpResponse,
+};
+use diesel::{ExpressionMethods, QueryDsl};
 use diesel_async::{pooled_connection::bb8::Pool, AsyncPgConnection, RunQueryDsl};
 
+use crate::{
+    types::pagination::PaginationParameters,
+    utils::pagination::{into_page, invalid_cursor_response, Cursor},
+};
+
 #[cfg(feature = "openapi")]
 const EXAMPLE_JSON: &str = include_str!("examples/all_ids.json");
 
 #[cfg_attr(feature = "openapi", utoipa::path(
     path = "/apps/all_ids",
+    params(PaginationParameters),
     responses(
         (
             status = 200,
-            description = "List of all applications ids",
-            body = Vec<String>,
-            example = json!(serde_json::from_str::<Vec<String>>(EXAMPLE_JSON).unwrap())
+            description = "Page of published application ids, in alphabetical order",
+            body = AppIdPage,
+            example = json!({
+                "items": serde_json::from_str::<Vec<String>>(EXAMPLE_JSON).unwrap(),
+                "next": null
+            })
         ),
+        (status = 400, body = ErrorResponse),
     )
 ))]
 #[cfg_attr(
@@ -22,31 +35,49 @@ const EXAMPLE_JSON: &str = include_str!("examples/all_ids.json");
     tracing::instrument(name = "Getting all app ids", skip(pool))
 )]
 #[get("/all_ids")]
-pub async fn all_ids(pool: Data<Pool<AsyncPgConnection>>) -> actix_web::HttpResponse {
+pub async fn all_ids(
+    parameters: Query<PaginationParameters>,
+    pool: Data<Pool<AsyncPgConnection>>,
+) -> actix_web::HttpResponse {
     use common::schema::apps::dsl::*;
 
+    let after = match parameters.after.as_deref().map(Cursor::decode) {
+        None => None,
+        Some(Some(c)) => Some(c.id),
+        Some(None) => return invalid_cursor_response(),
+    };
+
     let mut con = match pool.get().await {
         Ok(c) => c,
         Err(e) => {
-            tracing::error!("Unable to get DB connection for recent apps: {}", e);
+            tracing::error!("Unable to get DB connection for all app ids: {}", e);
             return HttpResponse::InternalServerError().finish();
         }
     };
 
-    let all_apps: Vec<String> = match apps
+    let page_size = parameters.page_size();
+
+    let mut query = apps
         .filter(is_published.eq(true))
-        .load::<App>(&mut con)
-        .await
-    {
+        .select(id)
+        .order(id.asc())
+        .limit(page_size + 1)
+        .into_boxed();
+
+    if let Some(after) = after {
+        query = query.filter(id.gt(after));
+    }
+
+    let app_ids = match query.load::<String>(&mut con).await {
         Ok(a) => a,
         Err(e) => {
             tracing::error!("Error getting all apps from db: {}", e);
             return HttpResponse::InternalServerError().finish();
         }
-    }
-    .iter()
-    .map(|a| a.id.to_owned())
-    .collect();
+    };
 
-    HttpResponse::Ok().json(all_apps)
+    HttpResponse::Ok().json(into_page(app_ids, page_size, |app_id| Cursor {
+        id: app_id.to_owned(),
+        timestamp: None,
+    }))
 }
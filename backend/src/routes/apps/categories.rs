@@ -0,0 +1,232 @@
+use std::collections::{HashMap, HashSet};
+
+use actix_web::{
+    get,
+    web::{Data, Path},
+    HttpResponse,
+};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::{
+    pooled_connection::bb8::{Pool, PooledConnection},
+    AsyncPgConnection, RunQueryDsl,
+};
+
+use common::models::{CategoryApps, CategorySummary};
+
+use crate::{
+    extractors::PreferredLocales,
+    types::{ErrorResponse, ErrorTranslationKey},
+};
+
+use super::get_component_summaries;
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/apps/categories",
+    responses(
+        (
+            status = 200,
+            description = "List of AppStream categories with the number of published apps in each",
+            body = Vec<CategorySummary>,
+        ),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Getting app categories", skip(pool, redis_pool))
+)]
+#[get("/categories")]
+pub async fn categories(
+    pool: Data<Pool<AsyncPgConnection>>,
+    redis_pool: Data<deadpool_redis::Pool>,
+) -> HttpResponse {
+    let mut redis_con = match redis_pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get redis connection for categories: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let all_categories = match deadpool_redis::redis::cmd("hgetall")
+        .arg(common::APP_CATEGORIES_REDIS_KEY)
+        .query_async::<_, HashMap<String, String>>(&mut redis_con)
+        .await
+    {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Error getting categories from redis: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for categories: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let published = match get_published_app_ids(&mut con).await {
+        Ok(p) => p,
+        Err(e) => {
+            tracing::error!("Error getting published apps from db: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let mut summaries = all_categories
+        .into_iter()
+        .map(|(name, members)| CategorySummary {
+            count: published_members(&members, &published).len(),
+            name,
+        })
+        .filter(|c| c.count > 0)
+        .collect::<Vec<_>>();
+
+    summaries.sort_by(|a, b| a.name.cmp(&b.name));
+
+    HttpResponse::Ok().json(summaries)
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/apps/categories/{name}",
+    params(
+        crate::extractors::LocaleQuery,
+    ),
+    responses(
+        (
+            status = 200,
+            description = "The published apps in the given AppStream category",
+            body = CategoryApps,
+        ),
+        (status = 404, body = ErrorResponse),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Getting apps in category", skip(locales, pool, redis_pool))
+)]
+#[get("/categories/{name}")]
+pub async fn category(
+    path: Path<(String,)>,
+    locales: PreferredLocales,
+    pool: Data<Pool<AsyncPgConnection>>,
+    redis_pool: Data<deadpool_redis::Pool>,
+) -> HttpResponse {
+    let name = path.into_inner().0;
+
+    let mut redis_con = match redis_pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get redis connection for category: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let members = match deadpool_redis::redis::cmd("hget")
+        .arg(common::APP_CATEGORIES_REDIS_KEY)
+        .arg(&name)
+        .query_async::<_, Option<String>>(&mut redis_con)
+        .await
+    {
+        Ok(Some(m)) => m,
+        Ok(None) => {
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "Specified category was not found".into(),
+                translation_key: ErrorTranslationKey::CategoryNotFound,
+            });
+        }
+        Err(e) => {
+            tracing::error!("Error getting category from redis: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for category: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let published = match get_published_app_ids(&mut con).await {
+        Ok(p) => p,
+        Err(e) => {
+            tracing::error!("Error getting published apps from db: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let ids = published_members(&members, &published);
+
+    let apps = match get_component_summaries(&mut redis_con, &ids).await {
+        Ok(a) => a,
+        Err(e) => {
+            tracing::error!("Error getting category app summaries from redis: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    HttpResponse::Ok().json(locales.apply(CategoryApps {
+        name,
+        count: ids.len(),
+        apps,
+    }))
+}
+
+async fn get_published_app_ids(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+) -> Result<HashSet<String>, diesel::result::Error> {
+    use common::schema::apps::dsl::*;
+
+    Ok(apps
+        .filter(is_published.eq(true))
+        .select(id)
+        .load::<String>(con)
+        .await?
+        .into_iter()
+        .collect())
+}
+
+/// Filters a serialized list of category members down to the published apps, sorted by ID
+fn published_members(members: &str, published: &HashSet<String>) -> Vec<String> {
+    let mut ids = match serde_json::from_str::<Vec<String>>(members) {
+        Ok(m) => m,
+        Err(e) => {
+            tracing::warn!("Error deserializing category members from redis: {}", e);
+            return Vec::new();
+        }
+    }
+    .into_iter()
+    .filter(|id| published.contains(id))
+    .collect::<Vec<_>>();
+
+    ids.sort();
+    ids.dedup();
+
+    ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filtering_members() {
+        let published = HashSet::from([
+            "com.example.camera".to_string(),
+            "com.example.photos".to_string(),
+        ]);
+
+        assert_eq!(
+            published_members(
+                r#"["com.example.photos", "com.example.unpublished", "com.example.camera"]"#,
+                &published
+            ),
+            vec!["com.example.camera", "com.example.photos"]
+        );
+        assert!(published_members("not json", &published).is_empty());
+    }
+}
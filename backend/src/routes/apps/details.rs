@@ -0,0 +1,146 @@
+use actix_web::{
+    get,
+    web::{Data, Path},
+    HttpResponse,
+};
+
+use common::models::AppDetails;
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::{
+    pooled_connection::bb8::{Pool, PooledConnection},
+    AsyncPgConnection, RunQueryDsl,
+};
+
+use crate::{
+    extractors::PreferredLocales,
+    types::{ErrorResponse, ErrorTranslationKey},
+};
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/apps/{id}/details",
+    params(
+        crate::extractors::LocaleQuery,
+    ),
+    responses(
+        (
+            status = 200,
+            description = "AppStream metadata for the app, as published in the Flatpak remote",
+            body = AppDetails,
+        ),
+        (status = 404, body = ErrorResponse),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Getting app details", skip(locales, pool, redis_pool))
+)]
+#[get("/{id}/details")]
+pub async fn details(
+    path: Path<(String,)>,
+    locales: PreferredLocales,
+    pool: Data<Pool<AsyncPgConnection>>,
+    redis_pool: Data<deadpool_redis::Pool>,
+) -> HttpResponse {
+    let id = path.into_inner().0;
+
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for app details: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    match is_app_published(&mut con, &id).await {
+        Ok(true) => {}
+        Ok(false) => return app_not_found_response(),
+        Err(e) => {
+            tracing::error!("Error checking whether app is published: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    }
+
+    let mut redis_con = match redis_pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get redis connection for app details: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let details = match deadpool_redis::redis::cmd("hget")
+        .arg(common::APP_DETAILS_REDIS_KEY)
+        .arg(&id)
+        .query_async::<_, Option<String>>(&mut redis_con)
+        .await
+    {
+        Ok(Some(d)) => d,
+        Ok(None) => return app_not_found_response(),
+        Err(e) => {
+            tracing::error!("Error getting app details from redis: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    match serde_json::de::from_str::<AppDetails>(&details) {
+        Ok(d) => HttpResponse::Ok().json(locales.apply(d)),
+        Err(e) => {
+            tracing::error!("Error deserializing app details from redis: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+fn app_not_found_response() -> HttpResponse {
+    HttpResponse::NotFound().json(ErrorResponse {
+        error: "Specified app ID was not found".into(),
+        translation_key: ErrorTranslationKey::AppNotFound,
+    })
+}
+
+async fn is_app_published(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    app_id: &str,
+) -> Result<bool, diesel::result::Error> {
+    use common::schema::apps::dsl::*;
+
+    let published = apps
+        .filter(id.eq(app_id))
+        .filter(is_published.eq(true))
+        .count()
+        .get_result::<i64>(con)
+        .await?;
+
+    Ok(published > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel_async::AsyncConnection;
+
+    use crate::utils::db_test::{create_app, db_pool};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_is_app_published() -> anyhow::Result<()> {
+        use common::schema::apps;
+
+        let db_pool = db_pool().await;
+        let mut con = db_pool.get().await?;
+
+        con.begin_test_transaction().await?;
+
+        let app = create_app(&mut con, None, None).await?;
+        assert!(!is_app_published(&mut con, &app).await?);
+
+        diesel::update(apps::table.filter(apps::id.eq(&app)))
+            .set(apps::is_published.eq(true))
+            .execute(&mut con)
+            .await?;
+        assert!(is_app_published(&mut con, &app).await?);
+        assert!(!is_app_published(&mut con, "com.example.missing").await?);
+
+        Ok(())
+    }
+}
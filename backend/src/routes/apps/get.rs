@@ -24,7 +24,11 @@ use crate::types::{ErrorResponse, ErrorTranslationKey};
                 first_seen: Some(datetime!(2020-01-01 0:00 UTC)),
                 last_update: Some(datetime!(2023-03-27 17:22 UTC)),
                 is_published: true,
-                stripe_connect_id: Some("acct_1NEYZOPEvkLnkEch".into())
+                stripe_connect_id: Some("acct_1NEYZOPEvkLnkEch".into()),
+                suggested_price: Some(300),
+                minimum_price: 0,
+                price_currency: "usd".into(),
+                free_allowed: true,
             })
         ),
     )
@@ -74,5 +78,21 @@ pub async fn get_app_by_id(
 ) -> Result<App, diesel::result::Error> {
     use common::schema::apps::dsl::*;
 
-    apps.filter(id.eq(app_id_to_find)).get_result(con).await
+    apps.filter(id.eq(app_id_to_find))
+        .select((
+            id,
+            repository,
+            is_verified,
+            last_submitted_version,
+            first_seen,
+            last_update,
+            is_published,
+            stripe_connect_id,
+            suggested_price,
+            minimum_price,
+            price_currency,
+            free_allowed,
+        ))
+        .get_result(con)
+        .await
 }
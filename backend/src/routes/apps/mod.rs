@@ -1,7 +1,12 @@
+use common::models::ComponentSummary;
+
 pub(crate) mod all_ids;
+pub(crate) mod categories;
+pub(crate) mod details;
 pub(crate) mod get;
 pub(crate) mod recently_added;
 pub(crate) mod recently_updated;
+pub(crate) mod search;
 
 pub fn apps_routes_config(cfg: &mut actix_web::web::ServiceConfig) {
     cfg.service(
@@ -9,6 +14,37 @@ pub fn apps_routes_config(cfg: &mut actix_web::web::ServiceConfig) {
             .service(all_ids::all_ids)
             .service(recently_added::recently_added)
             .service(recently_updated::recently_updated)
+            .service(search::search)
+            .service(categories::categories)
+            .service(categories::category)
+            .service(details::details)
             .service(get::get),
     );
 }
+
+/// Fetches the cached component summaries for the given app IDs from redis, preserving
+/// the order of `ids`. Apps without a cached summary are skipped.
+pub(crate) async fn get_component_summaries(
+    redis_con: &mut deadpool_redis::Connection,
+    ids: &[String],
+) -> Result<Vec<ComponentSummary>, deadpool_redis::redis::RedisError> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    Ok(deadpool_redis::redis::cmd("hmget")
+        .arg(common::APP_SUMMARIES_REDIS_KEY)
+        .arg(ids)
+        .query_async::<_, Vec<Option<String>>>(redis_con)
+        .await?
+        .into_iter()
+        .flatten()
+        .filter_map(|s| match serde_json::de::from_str::<ComponentSummary>(&s) {
+            Ok(c) => Some(c),
+            Err(e) => {
+                tracing::warn!("Error deserializing component summary from redis: {}", e);
+                None
+            }
+        })
+        .collect())
+}
@@ -1,43 +1,77 @@
-use actix_web::{get, web::Data, HttpResponse};
-use diesel::{ExpressionMethods, QueryDsl};
+use actix_web::{
+    get,
+    web::{Data, Query},
+    HttpResponse,
+};
+use diesel::{BoolExpressionMethods, ExpressionMethods, PgSortExpressionMethods, QueryDsl};
 use diesel_async::{pooled_connection::bb8::Pool, AsyncPgConnection, RunQueryDsl};
+use time::OffsetDateTime;
 
-use common::models::{App, ComponentSummary};
+#[cfg(feature = "openapi")]
+use common::models::ComponentSummary;
+
+use crate::{
+    extractors::PreferredLocales,
+    types::pagination::{Page, PaginationParameters},
+    utils::pagination::{into_page, invalid_cursor_response, Cursor},
+};
+
+use super::get_component_summaries;
 
 #[cfg(feature = "openapi")]
 const EXAMPLE_JSON: &str = include_str!("examples/recently_updated.json");
 
 #[cfg_attr(feature = "openapi", utoipa::path(
     path = "/apps/recently_updated",
+    params(
+        PaginationParameters,
+        crate::extractors::LocaleQuery,
+    ),
     responses(
         (
             status = 200,
-            description = "List of recently updated applications",
-            body = Vec<ComponentSummary>,
-            example = json!(serde_json::from_str::<Vec<ComponentSummary>>(EXAMPLE_JSON).unwrap())
+            description = "Page of recently updated applications, most recent first",
+            body = ComponentSummaryPage,
+            example = json!({
+                "items": serde_json::from_str::<Vec<ComponentSummary>>(EXAMPLE_JSON).unwrap(),
+                "next": null
+            })
         ),
+        (status = 400, body = ErrorResponse),
     )
 ))]
 #[cfg_attr(
     not(coverage),
-    tracing::instrument(name = "Getting recently updated apps", skip(pool, redis_pool))
+    tracing::instrument(
+        name = "Getting recently updated apps",
+        skip(locales, pool, redis_pool)
+    )
 )]
 #[get("/recently_updated")]
 pub async fn recently_updated(
+    parameters: Query<PaginationParameters>,
+    locales: PreferredLocales,
     pool: Data<Pool<AsyncPgConnection>>,
-    redis_pool: actix_web::web::Data<deadpool_redis::Pool>,
+    redis_pool: Data<deadpool_redis::Pool>,
 ) -> actix_web::HttpResponse {
     use common::schema::apps::dsl::*;
 
-    let mut redis_con = redis_pool
-        .get()
-        .await
-        .map_err(|e| {
-            tracing::event!(target: "backend", tracing::Level::ERROR, "{}", e);
+    let after = match parameters.after.as_deref().map(Cursor::decode) {
+        None => None,
+        Some(Some(Cursor {
+            id: cursor_id,
+            timestamp: cursor_timestamp,
+        })) => Some((cursor_id, cursor_timestamp)),
+        Some(None) => return invalid_cursor_response(),
+    };
 
-            actix_web::HttpResponse::InternalServerError().finish()
-        })
-        .expect("Redis connection cannot be gotten.");
+    let mut redis_con = match redis_pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get redis connection for recent apps: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
 
     let mut con = match pool.get().await {
         Ok(c) => c,
@@ -47,40 +81,61 @@ pub async fn recently_updated(
         }
     };
 
-    let recent_apps = match apps
+    let page_size = parameters.page_size();
+
+    let mut query = apps
         .filter(is_published.eq(true))
-        .order(last_update.desc())
-        .limit(20)
-        .load::<App>(&mut con)
+        .select((id, last_update))
+        .order((last_update.desc().nulls_last(), id.asc()))
+        .limit(page_size + 1)
+        .into_boxed();
+
+    // Apps without a date come last, so a cursor without a timestamp is already among them
+    match after {
+        Some((cursor_id, Some(cursor_timestamp))) => {
+            query = query.filter(
+                last_update
+                    .lt(cursor_timestamp)
+                    .or(last_update.eq(cursor_timestamp).and(id.gt(cursor_id)))
+                    .or(last_update.is_null()),
+            );
+        }
+        Some((cursor_id, None)) => {
+            query = query.filter(last_update.is_null().and(id.gt(cursor_id)));
+        }
+        None => {}
+    }
+
+    let recent_apps = match query
+        .load::<(String, Option<OffsetDateTime>)>(&mut con)
         .await
     {
-        Ok(r) => match deadpool_redis::redis::cmd("hmget")
-            .arg(common::APP_SUMMARIES_REDIS_KEY)
-            .arg(r.iter().map(|a| a.id.to_owned()).collect::<Vec<_>>())
-            .query_async::<_, Vec<Option<String>>>(&mut redis_con)
-            .await
-        {
-            Ok(a) => a,
-            Err(e) => {
-                tracing::error!("Error getting recently updated apps from redis: {}", e);
-                return actix_web::HttpResponse::InternalServerError().finish();
-            }
-        }
-        .into_iter()
-        .flatten()
-        .filter_map(|s| match serde_json::de::from_str::<ComponentSummary>(&s) {
-            Ok(c) => Some(c),
-            Err(e) => {
-                tracing::warn!("Error deserializing component summary from redis: {}", e);
-                None
-            }
-        })
-        .collect::<Vec<_>>(),
+        Ok(a) => a,
         Err(e) => {
-            tracing::error!("Unable to get DB connection for recent apps: {}", e);
+            tracing::error!("Error getting recently updated apps from db: {}", e);
             return HttpResponse::InternalServerError().finish();
         }
     };
 
-    HttpResponse::Ok().json(recent_apps)
+    let page = into_page(recent_apps, page_size, |(app_id, updated)| Cursor {
+        id: app_id.to_owned(),
+        timestamp: *updated,
+    });
+
+    let app_ids = page
+        .items
+        .into_iter()
+        .map(|(app_id, _)| app_id)
+        .collect::<Vec<_>>();
+
+    match get_component_summaries(&mut redis_con, &app_ids).await {
+        Ok(summaries) => HttpResponse::Ok().json(Page {
+            items: locales.apply(summaries),
+            next: page.next,
+        }),
+        Err(e) => {
+            tracing::error!("Error getting recently updated apps from redis: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
 }
@@ -0,0 +1,186 @@
+use std::collections::{HashMap, HashSet};
+
+use actix_web::{
+    get,
+    web::{Data, Query},
+    HttpResponse,
+};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::{pooled_connection::bb8::Pool, AsyncPgConnection, RunQueryDsl};
+
+use common::{
+    models::ComponentSummary,
+    search::{tokenize, SearchDocument},
+};
+
+#[cfg(feature = "openapi")]
+use utoipa::IntoParams;
+
+use crate::extractors::PreferredLocales;
+
+use super::get_component_summaries;
+
+const MAX_SEARCH_RESULTS: usize = 50;
+
+#[derive(serde::Deserialize, Debug)]
+#[cfg_attr(feature = "openapi", derive(IntoParams))]
+pub struct SearchParameters {
+    /// The search query, matched against app names, summaries, keywords and descriptions
+    q: String,
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/apps/search",
+    params(
+        SearchParameters,
+        crate::extractors::LocaleQuery,
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Published applications matching the search query, best matches first",
+            body = Vec<ComponentSummary>,
+        ),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Searching apps", skip(locales, pool, redis_pool))
+)]
+#[get("/search")]
+pub async fn search(
+    parameters: Query<SearchParameters>,
+    locales: PreferredLocales,
+    pool: Data<Pool<AsyncPgConnection>>,
+    redis_pool: Data<deadpool_redis::Pool>,
+) -> HttpResponse {
+    use common::schema::apps::dsl::*;
+
+    let query_terms = tokenize(&parameters.q);
+    if query_terms.is_empty() {
+        return HttpResponse::Ok().json(Vec::<ComponentSummary>::new());
+    }
+
+    let mut redis_con = match redis_pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get redis connection for search: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let index = match deadpool_redis::redis::cmd("hgetall")
+        .arg(common::APP_SEARCH_INDEX_REDIS_KEY)
+        .query_async::<_, HashMap<String, String>>(&mut redis_con)
+        .await
+    {
+        Ok(i) => i,
+        Err(e) => {
+            tracing::error!("Error getting search index from redis: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let scores = rank_documents(index, &query_terms);
+    if scores.is_empty() {
+        return HttpResponse::Ok().json(Vec::<ComponentSummary>::new());
+    }
+
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for search: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let candidate_ids = scores
+        .iter()
+        .map(|(app_id, _)| app_id.to_owned())
+        .collect::<Vec<_>>();
+
+    let published_ids = match apps
+        .filter(is_published.eq(true))
+        .filter(id.eq_any(candidate_ids))
+        .select(id)
+        .load::<String>(&mut con)
+        .await
+    {
+        Ok(p) => p.into_iter().collect::<HashSet<_>>(),
+        Err(e) => {
+            tracing::error!("Error filtering search results by published apps: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let result_ids = scores
+        .into_iter()
+        .filter(|(app_id, _)| published_ids.contains(app_id))
+        .take(MAX_SEARCH_RESULTS)
+        .map(|(app_id, _)| app_id)
+        .collect::<Vec<_>>();
+
+    match get_component_summaries(&mut redis_con, &result_ids).await {
+        Ok(s) => HttpResponse::Ok().json(locales.apply(s)),
+        Err(e) => {
+            tracing::error!("Error getting search result summaries from redis: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+/// Scores every document in the index against the query, returning the matching app IDs
+/// sorted by descending score.
+fn rank_documents(index: HashMap<String, String>, query_terms: &[String]) -> Vec<(String, u32)> {
+    let mut scores = index
+        .into_iter()
+        .filter_map(
+            |(app_id, document)| match serde_json::from_str::<SearchDocument>(&document) {
+                Ok(d) => Some((app_id, d.score(query_terms))),
+                Err(e) => {
+                    tracing::warn!("Error deserializing search document from redis: {}", e);
+                    None
+                }
+            },
+        )
+        .filter(|(_, score)| *score > 0)
+        .collect::<Vec<_>>();
+
+    scores.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    scores
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranking() {
+        let index = HashMap::from([
+            (
+                "com.example.photos".to_string(),
+                r#"{"terms":{"photos":10,"camera":3}}"#.to_string(),
+            ),
+            (
+                "com.example.camera".to_string(),
+                r#"{"terms":{"camera":10,"photos":1}}"#.to_string(),
+            ),
+            (
+                "com.example.music".to_string(),
+                r#"{"terms":{"music":10}}"#.to_string(),
+            ),
+            ("com.example.broken".to_string(), "not json".to_string()),
+        ]);
+
+        let ranked = rank_documents(index, &tokenize("camera"));
+
+        assert_eq!(
+            ranked
+                .iter()
+                .map(|(app_id, _)| app_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["com.example.camera", "com.example.photos"]
+        );
+    }
+}
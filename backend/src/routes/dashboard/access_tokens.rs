@@ -0,0 +1,288 @@
+use actix_web::{
+    get, post,
+    web::{Data, Json, Path},
+    HttpResponse,
+};
+use common::models::{AccessToken, AuditAction, NewAccessToken, NewAuditEvent};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::{
+    pooled_connection::bb8::{Pool, PooledConnection},
+    AsyncPgConnection, RunQueryDsl,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    extractors::AuthedUser,
+    types::{
+        dashboard::{CreateAccessToken, CreatedAccessToken},
+        ErrorResponse, ErrorTranslationKey,
+    },
+    utils::{
+        audit,
+        auth::access_tokens::{generate_access_token, hash_access_token},
+    },
+};
+
+const MAX_NAME_LENGTH: usize = 100;
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/dashboard/access_tokens",
+    responses(
+        (status = 200, description = "The user's personal access tokens, newest first", body = Vec<AccessToken>),
+        (status = 401),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Listing access tokens", skip(user, pool))
+)]
+#[get("/access_tokens")]
+pub async fn get_access_tokens(
+    user: AuthedUser,
+    pool: Data<Pool<AsyncPgConnection>>,
+) -> HttpResponse {
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for access tokens: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    match list_access_tokens(&mut con, &user.uuid).await {
+        Ok(tokens) => HttpResponse::Ok().json(tokens),
+        Err(e) => {
+            tracing::error!("Error getting access tokens: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/dashboard/access_tokens",
+    request_body = CreateAccessToken,
+    responses(
+        (status = 200, description = "The new token, whose secret isn't shown again", body = CreatedAccessToken),
+        (status = 400, body = ErrorResponse),
+        (status = 401),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Creating access token", skip(user, pool))
+)]
+#[post("/access_tokens")]
+pub async fn create_access_token(
+    user: AuthedUser,
+    request: Json<CreateAccessToken>,
+    pool: Data<Pool<AsyncPgConnection>>,
+) -> HttpResponse {
+    let name = request.name.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LENGTH || request.scopes.is_empty() {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: format!(
+                "Access tokens need a name of up to {} characters and at least one scope",
+                MAX_NAME_LENGTH
+            ),
+            translation_key: ErrorTranslationKey::InvalidAccessToken,
+        });
+    }
+
+    let mut scopes = request.scopes.clone();
+    scopes.sort_by_key(|s| s.as_str());
+    scopes.dedup();
+
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for access tokens: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let token = generate_access_token();
+    let access_token = match insert_access_token(
+        &mut con,
+        NewAccessToken {
+            user_id: &user.uuid,
+            name,
+            token_hash: &hash_access_token(&token),
+            scopes: &scopes,
+        },
+    )
+    .await
+    {
+        Ok(t) => t,
+        Err(e) => {
+            tracing::error!("Error creating access token: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    audit::record(
+        &mut con,
+        NewAuditEvent::new(&user.uuid, AuditAction::AccessTokenCreated).details(json!({
+            "token_id": access_token.id,
+            "scopes": access_token.scopes,
+        })),
+    )
+    .await;
+
+    HttpResponse::Ok().json(CreatedAccessToken {
+        token,
+        access_token,
+    })
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/dashboard/access_tokens/{token_id}/revoke",
+    responses(
+        (status = 200, description = "The token can no longer be used"),
+        (status = 401),
+        (status = 404, body = ErrorResponse),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Revoking access token", skip(user, pool))
+)]
+#[post("/access_tokens/{token_id}/revoke")]
+pub async fn revoke_access_token(
+    user: AuthedUser,
+    path: Path<(Uuid,)>,
+    pool: Data<Pool<AsyncPgConnection>>,
+) -> HttpResponse {
+    let token_id = path.into_inner().0;
+
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for access tokens: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    match delete_access_token(&mut con, &user.uuid, &token_id).await {
+        Ok(0) => HttpResponse::NotFound().json(ErrorResponse {
+            error: "Specified access token was not found".into(),
+            translation_key: ErrorTranslationKey::AccessTokenNotFound,
+        }),
+        Ok(_) => {
+            audit::record(
+                &mut con,
+                NewAuditEvent::new(&user.uuid, AuditAction::AccessTokenRevoked)
+                    .details(json!({ "token_id": token_id })),
+            )
+            .await;
+
+            HttpResponse::Ok().finish()
+        }
+        Err(e) => {
+            tracing::error!("Error revoking access token: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+async fn list_access_tokens(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    user_id: &Uuid,
+) -> Result<Vec<AccessToken>, diesel::result::Error> {
+    use common::schema::access_tokens;
+
+    access_tokens::table
+        .filter(access_tokens::user_id.eq(user_id))
+        .order(access_tokens::created_at.desc())
+        .select((
+            access_tokens::id,
+            access_tokens::name,
+            access_tokens::scopes,
+            access_tokens::created_at,
+            access_tokens::last_used_at,
+        ))
+        .load::<AccessToken>(con)
+        .await
+}
+
+async fn insert_access_token(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    new_token: NewAccessToken<'_>,
+) -> Result<AccessToken, diesel::result::Error> {
+    use common::schema::access_tokens;
+
+    diesel::insert_into(access_tokens::table)
+        .values(new_token)
+        .returning((
+            access_tokens::id,
+            access_tokens::name,
+            access_tokens::scopes,
+            access_tokens::created_at,
+            access_tokens::last_used_at,
+        ))
+        .get_result::<AccessToken>(con)
+        .await
+}
+
+async fn delete_access_token(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    user_id: &Uuid,
+    token_id: &Uuid,
+) -> Result<usize, diesel::result::Error> {
+    use common::schema::access_tokens;
+
+    diesel::delete(
+        access_tokens::table
+            .filter(access_tokens::id.eq(token_id))
+            .filter(access_tokens::user_id.eq(user_id)),
+    )
+    .execute(con)
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use common::models::TokenScope;
+    use diesel_async::AsyncConnection;
+
+    use crate::utils::db_test::{create_user, db_pool};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_access_tokens() -> anyhow::Result<()> {
+        let db_pool = db_pool().await;
+        let mut con = db_pool.get().await?;
+
+        con.begin_test_transaction().await?;
+
+        let user = create_user(&mut con, true).await?;
+        let other = create_user(&mut con, true).await?;
+
+        let created = insert_access_token(
+            &mut con,
+            NewAccessToken {
+                user_id: &user,
+                name: "Release pipeline",
+                token_hash: &hash_access_token(&generate_access_token()),
+                scopes: &[TokenScope::Submit, TokenScope::ReadApps],
+            },
+        )
+        .await?;
+        assert_eq!(created.name, "Release pipeline");
+        assert_eq!(created.last_used_at, None);
+
+        assert_eq!(
+            list_access_tokens(&mut con, &user).await?,
+            vec![created.clone()]
+        );
+        assert!(list_access_tokens(&mut con, &other).await?.is_empty());
+
+        // Only the owner of a token can revoke it
+        assert_eq!(delete_access_token(&mut con, &other, &created.id).await?, 0);
+        assert_eq!(delete_access_token(&mut con, &user, &created.id).await?, 1);
+        assert!(list_access_tokens(&mut con, &user).await?.is_empty());
+
+        Ok(())
+    }
+}
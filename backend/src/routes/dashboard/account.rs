@@ -0,0 +1,851 @@
+use actix_web::{get, post, web::Data, HttpResponse};
+use common::models::{AccessToken, AppOwnerRole, AuditEvent, Submission};
+use diesel::{
+    BoolExpressionMethods, ExpressionMethods, NullableExpressionMethods, OptionalExtension,
+    QueryDsl,
+};
+use diesel_async::{
+    pooled_connection::bb8::{Pool, PooledConnection},
+    scoped_futures::ScopedFutureExt,
+    AsyncConnection, AsyncPgConnection, RunQueryDsl,
+};
+use secrecy::SecretString;
+use serde::Serialize;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{
+    extractors::AuthedUser,
+    types::{ErrorResponse, ErrorTranslationKey, SuccessResponse},
+    utils::{
+        forges::{forge_by_id, revoke_token},
+        sql::lower,
+    },
+};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+#[derive(Serialize, Debug, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ExportedUser {
+    pub id: Uuid,
+    pub email: String,
+    pub is_active: bool,
+    pub is_admin: bool,
+    #[cfg_attr(feature = "openapi", schema(format = DateTime, value_type = String))]
+    pub date_joined: OffsetDateTime,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ExportedProfile {
+    pub profile_picture_url: Option<String>,
+    pub github_link: Option<String>,
+}
+
+/// The GitHub account linked to the user. Tokens themselves are never exported.
+#[derive(Serialize, Debug, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ExportedGithubAuth {
+    pub github_user_id: Option<String>,
+    pub has_access_token: bool,
+    pub has_refresh_token: bool,
+}
+
+/// An account on another forge linked to the user. Tokens themselves are never exported.
+#[derive(Serialize, Debug, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ExportedForgeAuth {
+    #[cfg_attr(feature = "openapi", schema(example = "gitlab"))]
+    pub forge: String,
+    pub forge_user_id: String,
+    pub has_access_token: bool,
+    pub has_refresh_token: bool,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ExportedAppOwnership {
+    #[cfg_attr(
+        feature = "openapi",
+        schema(example = "com.github.davidmhewitt.torrential")
+    )]
+    pub app_id: String,
+    pub verified_owner: bool,
+    pub role: AppOwnerRole,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ExportedDomainChallenge {
+    #[cfg_attr(feature = "openapi", schema(example = "com.example.myapp"))]
+    pub app_id: String,
+    #[cfg_attr(feature = "openapi", schema(example = "example.com"))]
+    pub domain: String,
+    pub token: String,
+    #[cfg_attr(feature = "openapi", schema(format = DateTime, value_type = String))]
+    pub created_at: OffsetDateTime,
+    #[cfg_attr(feature = "openapi", schema(format = DateTime, value_type = Option<String>))]
+    pub verified_at: Option<OffsetDateTime>,
+}
+
+/// An invitation to co-own an app, either sent by the user or to their email address
+#[derive(Serialize, Debug, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ExportedInvitation {
+    #[cfg_attr(
+        feature = "openapi",
+        schema(example = "com.github.davidmhewitt.torrential")
+    )]
+    pub app_id: String,
+    #[cfg_attr(feature = "openapi", schema(example = "maintainer@example.com"))]
+    pub email: String,
+    pub role: AppOwnerRole,
+    pub invited_by: Uuid,
+    #[cfg_attr(feature = "openapi", schema(format = DateTime, value_type = String))]
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AccountExport {
+    pub user: ExportedUser,
+    pub profile: Option<ExportedProfile>,
+    pub github: Option<ExportedGithubAuth>,
+    pub forges: Vec<ExportedForgeAuth>,
+    pub apps: Vec<ExportedAppOwnership>,
+    #[cfg_attr(feature = "openapi", schema(example = json!(["acct_1NCliJPBGjCwUDHc"])))]
+    pub stripe_accounts: Vec<String>,
+    /// App updates submitted by the user
+    pub submissions: Vec<Submission>,
+    pub domain_challenges: Vec<ExportedDomainChallenge>,
+    pub access_tokens: Vec<AccessToken>,
+    pub invitations: Vec<ExportedInvitation>,
+    /// Actions taken by or on the user
+    pub audit_events: Vec<AuditEvent>,
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/dashboard/account/export",
+    responses(
+        (
+            status = 200,
+            description = "Everything stored about the current user",
+            body = AccountExport,
+        ),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Exporting account data", skip(user, pool))
+)]
+#[get("/account/export")]
+pub async fn export_account(user: AuthedUser, pool: Data<Pool<AsyncPgConnection>>) -> HttpResponse {
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for account export: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    match get_account_export(&mut con, &user.uuid).await {
+        Ok(export) => HttpResponse::Ok()
+            .insert_header((
+                actix_web::http::header::CONTENT_DISPOSITION,
+                "attachment; filename=\"account.json\"",
+            ))
+            .json(export),
+        Err(e) => {
+            tracing::error!("Error exporting account data: {}", e);
+            HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Error exporting account data".into(),
+                translation_key: ErrorTranslationKey::GenericServerProblem,
+            })
+        }
+    }
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/dashboard/account/delete",
+    responses(
+        (status = 200, description = "The account was deleted and the user logged out"),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Deleting account", skip(user, pool, session))
+)]
+#[post("/account/delete")]
+pub async fn delete_account(
+    user: AuthedUser,
+    pool: Data<Pool<AsyncPgConnection>>,
+    session: actix_session::Session,
+) -> HttpResponse {
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for account deletion: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let tokens = match delete_user(&mut con, user.uuid).await {
+        Ok(DeletionOutcome::Deleted(t)) => t,
+        Ok(DeletionOutcome::OwnershipNeedsTransfer(apps)) => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: format!(
+                    "Transfer primary ownership of these apps to one of their other owners first: {}",
+                    apps.join(", ")
+                ),
+                translation_key: ErrorTranslationKey::LastAppOwner,
+            });
+        }
+        Err(e) => {
+            tracing::error!("Error deleting account: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Error deleting account".into(),
+                translation_key: ErrorTranslationKey::GenericServerProblem,
+            });
+        }
+    };
+
+    session.purge();
+
+    // The account is already gone at this point, so a failure here only needs following up
+    if let Some(token) = tokens.github {
+        if let Err(e) = github_utils::revoke_oauth_grant(&SecretString::new(token)).await {
+            tracing::error!("Error revoking GitHub grant for deleted account: {}", e);
+        }
+    }
+
+    let settings = common::settings::get_settings().expect("Failed to read settings.");
+    for (forge_id, token) in tokens.forges {
+        let Some(forge) = forge_by_id(&forge_id) else {
+            continue;
+        };
+
+        // Codeberg can't revoke tokens, but its access tokens expire after an hour and the
+        // refresh token needed for a new one has been deleted with the account
+        let (Some(revoke_url), Some(application)) =
+            (forge.revoke_url(), settings.forges.get(forge.id()))
+        else {
+            continue;
+        };
+
+        if let Err(e) =
+            revoke_token(forge, revoke_url, application, &SecretString::new(token)).await
+        {
+            tracing::error!(
+                "Error revoking {} token for deleted account: {}",
+                forge.name(),
+                e
+            );
+        }
+    }
+
+    tracing::info!("Account deleted.");
+    HttpResponse::Ok().json(SuccessResponse {
+        message: "Your account has been deleted".to_string(),
+    })
+}
+
+async fn get_account_export(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    uuid: &Uuid,
+) -> Result<AccountExport, diesel::result::Error> {
+    use common::schema::{
+        access_tokens, app_invitations, app_owners, audit_events, domain_challenges, forge_auth,
+        github_auth, stripe_accounts, submissions, user_profile, users,
+    };
+
+    let user = users::table
+        .filter(users::id.eq(uuid))
+        .select((
+            users::id,
+            users::email,
+            users::is_active,
+            users::is_admin,
+            users::date_joined,
+        ))
+        .get_result::<(Uuid, String, bool, bool, OffsetDateTime)>(con)
+        .await
+        .map(
+            |(id, email, is_active, is_admin, date_joined)| ExportedUser {
+                id,
+                email,
+                is_active,
+                is_admin,
+                date_joined,
+            },
+        )?;
+
+    let profile = user_profile::table
+        .filter(user_profile::user_id.eq(uuid))
+        .select((user_profile::profile_picture_url, user_profile::github_link))
+        .get_result::<(Option<String>, Option<String>)>(con)
+        .await
+        .optional()?
+        .map(|(profile_picture_url, github_link)| ExportedProfile {
+            profile_picture_url,
+            github_link,
+        });
+
+    let github = github_auth::table
+        .filter(github_auth::user_id.eq(uuid))
+        .select((
+            github_auth::github_user_id,
+            github_auth::github_access_token.is_not_null(),
+            github_auth::github_refresh_token.is_not_null(),
+        ))
+        .get_result::<(Option<String>, bool, bool)>(con)
+        .await
+        .optional()?
+        .map(
+            |(github_user_id, has_access_token, has_refresh_token)| ExportedGithubAuth {
+                github_user_id,
+                has_access_token,
+                has_refresh_token,
+            },
+        );
+
+    let forges = forge_auth::table
+        .filter(forge_auth::user_id.eq(uuid))
+        .select((
+            forge_auth::forge,
+            forge_auth::forge_user_id,
+            forge_auth::access_token.is_not_null(),
+            forge_auth::refresh_token.is_not_null(),
+        ))
+        .order(forge_auth::forge.asc())
+        .load::<(String, String, bool, bool)>(con)
+        .await?
+        .into_iter()
+        .map(
+            |(forge, forge_user_id, has_access_token, has_refresh_token)| ExportedForgeAuth {
+                forge,
+                forge_user_id,
+                has_access_token,
+                has_refresh_token,
+            },
+        )
+        .collect();
+
+    let apps = app_owners::table
+        .filter(app_owners::user_id.eq(uuid))
+        .select((
+            app_owners::app_id,
+            app_owners::verified_owner,
+            app_owners::role,
+        ))
+        .order(app_owners::app_id.asc())
+        .load::<(String, bool, AppOwnerRole)>(con)
+        .await?
+        .into_iter()
+        .map(|(app_id, verified_owner, role)| ExportedAppOwnership {
+            app_id,
+            verified_owner,
+            role,
+        })
+        .collect();
+
+    let stripe_accounts = stripe_accounts::table
+        .filter(stripe_accounts::user_id.eq(uuid))
+        .select(stripe_accounts::stripe_account_id)
+        .load::<String>(con)
+        .await?;
+
+    let submissions = submissions::table
+        .filter(submissions::user_id.eq(uuid))
+        .order(submissions::created_at.asc())
+        .load::<Submission>(con)
+        .await?;
+
+    let domain_challenges = domain_challenges::table
+        .filter(domain_challenges::user_id.eq(uuid))
+        .select((
+            domain_challenges::app_id,
+            domain_challenges::domain,
+            domain_challenges::token,
+            domain_challenges::created_at,
+            domain_challenges::verified_at,
+        ))
+        .order(domain_challenges::app_id.asc())
+        .load::<(
+            String,
+            String,
+            String,
+            OffsetDateTime,
+            Option<OffsetDateTime>,
+        )>(con)
+        .await?
+        .into_iter()
+        .map(
+            |(app_id, domain, token, created_at, verified_at)| ExportedDomainChallenge {
+                app_id,
+                domain,
+                token,
+                created_at,
+                verified_at,
+            },
+        )
+        .collect();
+
+    let access_tokens = access_tokens::table
+        .filter(access_tokens::user_id.eq(uuid))
+        .select((
+            access_tokens::id,
+            access_tokens::name,
+            access_tokens::scopes,
+            access_tokens::created_at,
+            access_tokens::last_used_at,
+        ))
+        .order(access_tokens::created_at.asc())
+        .load::<AccessToken>(con)
+        .await?;
+
+    let invitations = app_invitations::table
+        .filter(
+            app_invitations::invited_by
+                .eq(uuid)
+                .or(lower(app_invitations::email).eq(user.email.to_lowercase())),
+        )
+        .select((
+            app_invitations::app_id,
+            app_invitations::email,
+            app_invitations::role,
+            app_invitations::invited_by,
+            app_invitations::created_at,
+        ))
+        .order(app_invitations::created_at.asc())
+        .load::<(String, String, AppOwnerRole, Uuid, OffsetDateTime)>(con)
+        .await?
+        .into_iter()
+        .map(
+            |(app_id, email, role, invited_by, created_at)| ExportedInvitation {
+                app_id,
+                email,
+                role,
+                invited_by,
+                created_at,
+            },
+        )
+        .collect();
+
+    let audit_events = audit_events::table
+        .filter(
+            audit_events::actor_id
+                .eq(uuid)
+                .or(audit_events::target_user_id.eq(uuid)),
+        )
+        .order((audit_events::created_at.asc(), audit_events::id.asc()))
+        .load::<AuditEvent>(con)
+        .await?;
+
+    Ok(AccountExport {
+        user,
+        profile,
+        github,
+        forges,
+        apps,
+        stripe_accounts,
+        submissions,
+        domain_challenges,
+        access_tokens,
+        invitations,
+        audit_events,
+    })
+}
+
+/// OAuth access tokens of a deleted user, to revoke once the deletion has been committed
+#[derive(Debug, PartialEq, Default)]
+struct RevocableTokens {
+    github: Option<String>,
+    /// Access tokens for other forges, with the forge's identifier
+    forges: Vec<(String, String)>,
+}
+
+#[derive(Debug, PartialEq)]
+enum DeletionOutcome {
+    /// The user was deleted, with their access tokens so they can be revoked
+    Deleted(RevocableTokens),
+    /// Nothing was deleted, as the user is the primary owner of these apps and other verified
+    /// owners would be left without one
+    OwnershipNeedsTransfer(Vec<String>),
+}
+
+/// Deletes a user and everything that only belongs to them.
+///
+/// Apps the user shares with other verified owners are kept, as long as one of those is also
+/// a primary owner. Published apps are kept too, as they're listed from the reviews repository
+/// regardless of who owns them. Apps that would pay out to the user's Stripe account stop
+/// accepting payments.
+async fn delete_user(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    uuid: Uuid,
+) -> Result<DeletionOutcome, diesel::result::Error> {
+    use common::schema::{app_owners, apps, forge_auth, github_auth, stripe_accounts, users};
+
+    con.transaction::<_, diesel::result::Error, _>(|con| {
+        async move {
+            let other_owners = diesel::alias!(app_owners as other_owners);
+
+            // Apps with other verified owners, but no other primary owner to hand them to
+            let needing_transfer = app_owners::table
+                .filter(app_owners::user_id.eq(uuid))
+                .filter(app_owners::verified_owner.eq(true))
+                .filter(app_owners::role.eq(AppOwnerRole::Owner))
+                .filter(diesel::dsl::exists(
+                    other_owners.filter(
+                        other_owners
+                            .field(app_owners::app_id)
+                            .eq(app_owners::app_id)
+                            .and(other_owners.field(app_owners::user_id).ne(uuid))
+                            .and(other_owners.field(app_owners::verified_owner).eq(true)),
+                    ),
+                ))
+                .filter(diesel::dsl::not(diesel::dsl::exists(
+                    other_owners.filter(
+                        other_owners
+                            .field(app_owners::app_id)
+                            .eq(app_owners::app_id)
+                            .and(other_owners.field(app_owners::user_id).ne(uuid))
+                            .and(other_owners.field(app_owners::verified_owner).eq(true))
+                            .and(other_owners.field(app_owners::role).eq(AppOwnerRole::Owner)),
+                    ),
+                )))
+                .select(app_owners::app_id)
+                .order(app_owners::app_id.asc())
+                .load::<String>(con)
+                .await?;
+
+            if !needing_transfer.is_empty() {
+                return Ok(DeletionOutcome::OwnershipNeedsTransfer(needing_transfer));
+            }
+
+            let github_access_token = github_auth::table
+                .filter(github_auth::user_id.eq(uuid))
+                .select(github_auth::github_access_token)
+                .get_result::<Option<String>>(con)
+                .await
+                .optional()?
+                .flatten();
+
+            let forge_access_tokens = forge_auth::table
+                .filter(forge_auth::user_id.eq(uuid))
+                .filter(forge_auth::access_token.is_not_null())
+                .select((
+                    forge_auth::forge,
+                    forge_auth::access_token.assume_not_null(),
+                ))
+                .order(forge_auth::forge.asc())
+                .load::<(String, String)>(con)
+                .await?;
+
+            let stripe_account_ids = stripe_accounts::table
+                .filter(stripe_accounts::user_id.eq(uuid))
+                .select(stripe_accounts::stripe_account_id)
+                .load::<String>(con)
+                .await?;
+
+            diesel::update(apps::table)
+                .filter(apps::stripe_connect_id.eq_any(stripe_account_ids))
+                .set(apps::stripe_connect_id.eq(None::<String>))
+                .execute(con)
+                .await?;
+
+            // Unverified claims on an app don't count, they'd leave it without anyone managing it
+            let solely_owned_apps = app_owners::table
+                .filter(app_owners::user_id.eq(uuid))
+                .filter(diesel::dsl::not(diesel::dsl::exists(
+                    other_owners.filter(
+                        other_owners
+                            .field(app_owners::app_id)
+                            .eq(app_owners::app_id)
+                            .and(other_owners.field(app_owners::user_id).ne(uuid))
+                            .and(other_owners.field(app_owners::verified_owner).eq(true)),
+                    ),
+                )))
+                .select(app_owners::app_id)
+                .load::<String>(con)
+                .await?;
+
+            diesel::delete(apps::table)
+                .filter(apps::id.eq_any(solely_owned_apps))
+                .filter(apps::is_published.eq(false))
+                .execute(con)
+                .await?;
+
+            // Cascades to the profile, GitHub and Stripe links, and app ownership
+            diesel::delete(users::table)
+                .filter(users::id.eq(uuid))
+                .execute(con)
+                .await?;
+
+            Ok(DeletionOutcome::Deleted(RevocableTokens {
+                github: github_access_token,
+                forges: forge_access_tokens,
+            }))
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::db_test::{create_app, create_user, db_pool};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_export_account() -> anyhow::Result<()> {
+        let db_pool = db_pool().await;
+        let mut con = db_pool.get().await?;
+
+        con.begin_test_transaction().await?;
+
+        let user = create_user(&mut con, true).await?;
+        let app = create_app(&mut con, Some(&user), None).await?;
+
+        let export = get_account_export(&mut con, &user).await?;
+
+        assert_eq!(export.user.id, user);
+        assert_eq!(
+            export.apps,
+            vec![ExportedAppOwnership {
+                app_id: app,
+                verified_owner: true,
+                role: AppOwnerRole::Owner,
+            }]
+        );
+        assert!(export.forges.is_empty());
+        assert!(export.stripe_accounts.is_empty());
+        assert!(export.submissions.is_empty());
+        assert!(export.access_tokens.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_export_account_activity() -> anyhow::Result<()> {
+        use common::models::{
+            AuditAction, NewAccessToken, NewAuditEvent, NewSubmission, SubmissionState,
+        };
+        use common::schema::{app_invitations, domain_challenges, submissions};
+
+        let db_pool = db_pool().await;
+        let mut con = db_pool.get().await?;
+
+        con.begin_test_transaction().await?;
+
+        let user = create_user(&mut con, true).await?;
+        let app = create_app(&mut con, Some(&user), None).await?;
+
+        diesel::insert_into(submissions::table)
+            .values(NewSubmission {
+                app_id: &app,
+                user_id: Some(&user),
+                version_tag: "1.0.0",
+                state: SubmissionState::Queued,
+            })
+            .execute(&mut con)
+            .await?;
+        diesel::insert_into(domain_challenges::table)
+            .values((
+                domain_challenges::app_id.eq(&app),
+                domain_challenges::user_id.eq(user),
+                domain_challenges::domain.eq("example.com"),
+                domain_challenges::token.eq("appcenter-verification=abc"),
+            ))
+            .execute(&mut con)
+            .await?;
+        diesel::insert_into(common::schema::access_tokens::table)
+            .values(NewAccessToken {
+                user_id: &user,
+                name: "CI",
+                token_hash: &Uuid::new_v4().to_string(),
+                scopes: &[],
+            })
+            .execute(&mut con)
+            .await?;
+        diesel::insert_into(app_invitations::table)
+            .values((
+                app_invitations::app_id.eq(&app),
+                app_invitations::email.eq("maintainer@example.com"),
+                app_invitations::role.eq(AppOwnerRole::Maintainer),
+                app_invitations::invited_by.eq(user),
+            ))
+            .execute(&mut con)
+            .await?;
+        diesel::insert_into(common::schema::audit_events::table)
+            .values(NewAuditEvent::new(&user, AuditAction::Login))
+            .execute(&mut con)
+            .await?;
+
+        let export = get_account_export(&mut con, &user).await?;
+
+        assert_eq!(export.submissions.len(), 1);
+        assert_eq!(export.submissions[0].version_tag, "1.0.0");
+        assert_eq!(export.domain_challenges.len(), 1);
+        assert_eq!(export.domain_challenges[0].domain, "example.com");
+        assert_eq!(export.access_tokens.len(), 1);
+        assert_eq!(export.access_tokens[0].name, "CI");
+        assert_eq!(export.invitations.len(), 1);
+        assert_eq!(export.invitations[0].invited_by, user);
+        assert_eq!(export.audit_events.len(), 1);
+        assert_eq!(export.audit_events[0].action, AuditAction::Login);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delete_user() -> anyhow::Result<()> {
+        use common::schema::{apps, forge_auth, stripe_accounts, users};
+
+        let db_pool = db_pool().await;
+        let mut con = db_pool.get().await?;
+
+        con.begin_test_transaction().await?;
+
+        let user = create_user(&mut con, true).await?;
+        let other_user = create_user(&mut con, true).await?;
+
+        diesel::insert_into(stripe_accounts::table)
+            .values((
+                stripe_accounts::user_id.eq(user),
+                stripe_accounts::stripe_account_id.eq("acct_deleted"),
+            ))
+            .execute(&mut con)
+            .await?;
+
+        diesel::insert_into(forge_auth::table)
+            .values((
+                forge_auth::user_id.eq(user),
+                forge_auth::forge.eq("gitlab"),
+                forge_auth::forge_user_id.eq("1234"),
+                forge_auth::access_token.eq("gitlab-token"),
+            ))
+            .execute(&mut con)
+            .await?;
+
+        let own_app = create_app(&mut con, Some(&user), Some("acct_deleted")).await?;
+        let shared_app = create_app(&mut con, Some(&user), Some("acct_deleted")).await?;
+        create_app_owner(
+            &mut con,
+            &other_user,
+            &shared_app,
+            true,
+            AppOwnerRole::Owner,
+        )
+        .await?;
+        let claimed_app = create_app(&mut con, Some(&user), None).await?;
+        create_app_owner(
+            &mut con,
+            &other_user,
+            &claimed_app,
+            false,
+            AppOwnerRole::Owner,
+        )
+        .await?;
+        let published_app = create_app(&mut con, Some(&user), None).await?;
+        diesel::update(apps::table)
+            .filter(apps::id.eq(&published_app))
+            .set(apps::is_published.eq(true))
+            .execute(&mut con)
+            .await?;
+
+        assert_eq!(
+            delete_user(&mut con, user).await?,
+            DeletionOutcome::Deleted(RevocableTokens {
+                github: None,
+                forges: vec![("gitlab".into(), "gitlab-token".into())],
+            })
+        );
+
+        // Only an unverified claim was left on the claimed app, so it's deleted like the own app
+        let remaining_apps = apps::table
+            .filter(apps::id.eq_any([&own_app, &shared_app, &claimed_app, &published_app]))
+            .select((apps::id, apps::stripe_connect_id))
+            .order(apps::id.asc())
+            .load::<(String, Option<String>)>(&mut con)
+            .await?;
+
+        let mut expected = vec![(shared_app, None), (published_app, None)];
+        expected.sort();
+        assert_eq!(remaining_apps, expected);
+
+        let user_exists = users::table
+            .filter(users::id.eq(user))
+            .select(users::id)
+            .get_result::<Uuid>(&mut con)
+            .await
+            .optional()?;
+        assert_eq!(user_exists, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delete_primary_owner() -> anyhow::Result<()> {
+        use common::schema::users;
+
+        let db_pool = db_pool().await;
+        let mut con = db_pool.get().await?;
+
+        con.begin_test_transaction().await?;
+
+        let user = create_user(&mut con, true).await?;
+        let maintainer = create_user(&mut con, true).await?;
+        let app = create_app(&mut con, Some(&user), None).await?;
+        create_app_owner(&mut con, &maintainer, &app, true, AppOwnerRole::Maintainer).await?;
+
+        // Deleting the only primary owner would leave the maintainer unable to manage owners
+        assert_eq!(
+            delete_user(&mut con, user).await?,
+            DeletionOutcome::OwnershipNeedsTransfer(vec![app.clone()])
+        );
+        let user_exists = users::table
+            .filter(users::id.eq(user))
+            .select(users::id)
+            .get_result::<Uuid>(&mut con)
+            .await
+            .optional()?;
+        assert_eq!(user_exists, Some(user));
+
+        // Once the maintainer is a primary owner too, the user can go
+        create_app_owner(&mut con, &maintainer, &app, true, AppOwnerRole::Owner).await?;
+        assert_eq!(
+            delete_user(&mut con, user).await?,
+            DeletionOutcome::Deleted(RevocableTokens::default())
+        );
+
+        Ok(())
+    }
+
+    async fn create_app_owner(
+        con: &mut AsyncPgConnection,
+        owner: &Uuid,
+        owned_app_id: &str,
+        verified: bool,
+        owner_role: AppOwnerRole,
+    ) -> anyhow::Result<()> {
+        use common::schema::app_owners::dsl::*;
+
+        diesel::insert_into(app_owners)
+            .values((
+                user_id.eq(owner),
+                app_id.eq(owned_app_id),
+                verified_owner.eq(verified),
+                role.eq(owner_role),
+            ))
+            .on_conflict((user_id, app_id))
+            .do_update()
+            .set((verified_owner.eq(verified), role.eq(owner_role)))
+            .execute(con)
+            .await?;
+
+        Ok(())
+    }
+}
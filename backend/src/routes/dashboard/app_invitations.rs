@@ -0,0 +1,437 @@
+use actix_web::{
+    post,
+    web::{Data, Json, Path},
+    HttpResponse,
+};
+use common::models::{AppOwnerRole, AuditAction, NewAuditEvent};
+use diesel::{dsl::count, ExpressionMethods, OptionalExtension, QueryDsl};
+use diesel_async::{
+    pooled_connection::bb8::{Pool, PooledConnection},
+    scoped_futures::ScopedFutureExt,
+    AsyncConnection, AsyncPgConnection, RunQueryDsl,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+use super::app_owners::{get_app_role, insufficient_role_response};
+use crate::{
+    extractors::AuthedUser,
+    types::{
+        dashboard::{AcceptAppInvitation, AppOwnerInvitation},
+        ErrorResponse, ErrorTranslationKey,
+    },
+    utils::{
+        audit,
+        auth::tokens::{
+            generate_invitation_token, verify_invitation_token, INVITATION_EXPIRATION_DAYS,
+        },
+        sql::lower,
+    },
+};
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/dashboard/app_owners/{app_id}/invite",
+    request_body = AppOwnerInvitation,
+    responses(
+        (status = 200, description = "An invitation was emailed to the address"),
+        (status = 400, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Inviting app owner", skip(user, pool))
+)]
+#[post("/app_owners/{app_id}/invite")]
+pub async fn invite_app_owner(
+    user: AuthedUser,
+    path: Path<(String,)>,
+    invitation: Json<AppOwnerInvitation>,
+    pool: Data<Pool<AsyncPgConnection>>,
+) -> HttpResponse {
+    let app_id = path.into_inner().0;
+    let email = invitation.email.trim().to_lowercase();
+
+    if invitation.role == AppOwnerRole::Owner {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Primary ownership can only be transferred to an existing owner".into(),
+            translation_key: ErrorTranslationKey::InvalidAppOwnerRole,
+        });
+    }
+
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for app invitation: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    match get_app_role(&mut con, &app_id, &user.uuid).await {
+        Ok(Some(r)) if r.can_manage_owners() => {}
+        Ok(Some(_)) => return insufficient_role_response(),
+        Ok(None) => {
+            return HttpResponse::Forbidden().json(ErrorResponse {
+                error: "Current user is not a verified owner of this app".into(),
+                translation_key: ErrorTranslationKey::AppNotOwned,
+            })
+        }
+        Err(e) => {
+            tracing::error!("Error checking app ownership: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    }
+
+    match is_verified_owner_email(&mut con, &app_id, &email).await {
+        Ok(false) => {}
+        Ok(true) => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "A user with this email address already owns the app".into(),
+                translation_key: ErrorTranslationKey::AlreadyAppOwner,
+            })
+        }
+        Err(e) => {
+            tracing::error!("Error checking existing app owners: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    }
+
+    let invitation_id =
+        match create_invitation(&mut con, &app_id, &email, invitation.role, &user.uuid).await {
+            Ok(i) => i,
+            Err(e) => {
+                tracing::error!("Error creating app invitation: {}", e);
+                return HttpResponse::InternalServerError().finish();
+            }
+        };
+
+    if let Err(e) =
+        send_invitation_email(&email, &user.email, &app_id, invitation.role, invitation_id)
+    {
+        tracing::error!("Error sending app invitation: {}", e);
+        return HttpResponse::InternalServerError().finish();
+    }
+
+    audit::record(
+        &mut con,
+        NewAuditEvent::new(&user.uuid, AuditAction::AppOwnerInvited)
+            .app(&app_id)
+            .details(json!({ "email": email, "role": invitation.role })),
+    )
+    .await;
+
+    HttpResponse::Ok().finish()
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/dashboard/invitations/accept",
+    request_body = AcceptAppInvitation,
+    responses(
+        (status = 200, description = "The current user is now an owner of the app"),
+        (status = 400, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Accepting app invitation", skip(user, pool, acceptance))
+)]
+#[post("/invitations/accept")]
+pub async fn accept_app_invitation(
+    user: AuthedUser,
+    acceptance: Json<AcceptAppInvitation>,
+    pool: Data<Pool<AsyncPgConnection>>,
+) -> HttpResponse {
+    let settings = common::settings::get_settings().expect("Failed to read settings.");
+
+    let invalid_response = || {
+        HttpResponse::BadRequest().json(ErrorResponse {
+            error: "This invitation has expired or was already used".into(),
+            translation_key: ErrorTranslationKey::AppInvitationInvalid,
+        })
+    };
+
+    let invitation_id = match verify_invitation_token(&acceptance.token, &settings.secret) {
+        Ok(i) => i,
+        Err(e) => {
+            tracing::info!("Invalid app invitation token: {}", e);
+            return invalid_response();
+        }
+    };
+
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for app invitation: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    match accept_invitation(&mut con, &invitation_id, &user.uuid, &user.email).await {
+        Ok(AcceptOutcome::Accepted { app_id, role }) => {
+            audit::record(
+                &mut con,
+                NewAuditEvent::new(&user.uuid, AuditAction::AppInvitationAccepted)
+                    .app(&app_id)
+                    .details(json!({ "role": role })),
+            )
+            .await;
+
+            HttpResponse::Ok().finish()
+        }
+        Ok(AcceptOutcome::NotFound) => invalid_response(),
+        Ok(AcceptOutcome::WrongAccount) => HttpResponse::Forbidden().json(ErrorResponse {
+            error: "This invitation was sent to a different email address".into(),
+            translation_key: ErrorTranslationKey::AppInvitationWrongAccount,
+        }),
+        Err(e) => {
+            tracing::error!("Error accepting app invitation: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+async fn is_verified_owner_email(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    app_id: &str,
+    email: &str,
+) -> Result<bool, diesel::result::Error> {
+    use common::schema::{app_owners, users};
+
+    let owners = app_owners::table
+        .inner_join(users::table)
+        .filter(app_owners::app_id.eq(app_id))
+        .filter(app_owners::verified_owner.eq(true))
+        .filter(lower(users::email).eq(email))
+        .select(count(app_owners::user_id))
+        .get_result::<i64>(con)
+        .await?;
+
+    Ok(owners > 0)
+}
+
+/// Records the invitation, replacing any earlier invitation of the same address so that links
+/// from older emails stop working
+async fn create_invitation(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    app_id: &str,
+    email: &str,
+    role: AppOwnerRole,
+    invited_by: &Uuid,
+) -> Result<Uuid, diesel::result::Error> {
+    use common::schema::app_invitations;
+
+    let invitation_id = Uuid::new_v4();
+
+    diesel::insert_into(app_invitations::table)
+        .values((
+            app_invitations::id.eq(invitation_id),
+            app_invitations::app_id.eq(app_id),
+            app_invitations::email.eq(email),
+            app_invitations::role.eq(role),
+            app_invitations::invited_by.eq(invited_by),
+        ))
+        .on_conflict((app_invitations::app_id, app_invitations::email))
+        .do_update()
+        .set((
+            app_invitations::id.eq(invitation_id),
+            app_invitations::role.eq(role),
+            app_invitations::invited_by.eq(invited_by),
+            app_invitations::created_at.eq(diesel::dsl::now),
+        ))
+        .execute(con)
+        .await?;
+
+    Ok(invitation_id)
+}
+
+fn send_invitation_email(
+    email: &str,
+    inviter: &str,
+    app_id: &str,
+    role: AppOwnerRole,
+    invitation_id: Uuid,
+) -> anyhow::Result<()> {
+    let settings = common::settings::get_settings().expect("Unable to load settings.");
+
+    let token = generate_invitation_token(invitation_id, &settings.secret)?;
+    let confirmation_link = format!(
+        "{}/dashboard/invitations/accept?token={}",
+        settings.frontend_url, token
+    );
+
+    let title = format!("You've been invited to help manage {}", app_id);
+    let template = crate::ENV.get_template("app_invitation_email.html")?;
+    let html_text = template.render(minijinja::context! {
+        title => &title,
+        inviter => inviter,
+        app_id => app_id,
+        role => role.as_str(),
+        confirmation_link => &confirmation_link,
+        expiration_time => INVITATION_EXPIRATION_DAYS,
+    })?;
+
+    let text = format!(
+        r#"
+        {} invited you to help manage {} on AppCenter as its {}. Tap the link below to accept the invitation.
+        {}
+        "#,
+        inviter,
+        app_id,
+        role.as_str(),
+        confirmation_link
+    );
+
+    tokio::spawn(crate::utils::emails::send_email(
+        None,
+        email.to_owned(),
+        title,
+        html_text,
+        text,
+    ));
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq)]
+enum AcceptOutcome {
+    Accepted { app_id: String, role: AppOwnerRole },
+    NotFound,
+    WrongAccount,
+}
+
+/// Makes the user a verified owner of the app they were invited to, with the invited role. The
+/// invitation is deleted so its link can only be used once.
+async fn accept_invitation(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    invitation_id: &Uuid,
+    uuid: &Uuid,
+    user_email: &str,
+) -> Result<AcceptOutcome, diesel::result::Error> {
+    use common::schema::{app_invitations, app_owners};
+
+    let invitation_id = *invitation_id;
+    let uuid = *uuid;
+    let user_email = user_email.trim().to_owned();
+
+    con.transaction::<_, diesel::result::Error, _>(|transaction| {
+        async move {
+            let Some((app_id, email, role)) = app_invitations::table
+                .filter(app_invitations::id.eq(invitation_id))
+                .select((
+                    app_invitations::app_id,
+                    app_invitations::email,
+                    app_invitations::role,
+                ))
+                .get_result::<(String, String, AppOwnerRole)>(transaction)
+                .await
+                .optional()?
+            else {
+                return Ok(AcceptOutcome::NotFound);
+            };
+
+            if !email.eq_ignore_ascii_case(&user_email) {
+                return Ok(AcceptOutcome::WrongAccount);
+            }
+
+            diesel::insert_into(app_owners::table)
+                .values((
+                    app_owners::user_id.eq(uuid),
+                    app_owners::app_id.eq(&app_id),
+                    app_owners::verified_owner.eq(true),
+                    app_owners::role.eq(role),
+                ))
+                .on_conflict((app_owners::user_id, app_owners::app_id))
+                .do_update()
+                .set((
+                    app_owners::verified_owner.eq(true),
+                    app_owners::role.eq(role),
+                ))
+                .execute(transaction)
+                .await?;
+
+            diesel::delete(app_invitations::table.filter(app_invitations::id.eq(invitation_id)))
+                .execute(transaction)
+                .await?;
+
+            Ok(AcceptOutcome::Accepted { app_id, role })
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::db_test::{create_app, create_user, db_pool};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_accept_invitation() -> anyhow::Result<()> {
+        use common::schema::users;
+
+        let db_pool = db_pool().await;
+        let mut con = db_pool.get().await?;
+
+        con.begin_test_transaction().await?;
+
+        let owner = create_user(&mut con, true).await?;
+        let invitee = create_user(&mut con, true).await?;
+        let app = create_app(&mut con, Some(&owner), None).await?;
+
+        let invitee_email = users::table
+            .filter(users::id.eq(invitee))
+            .select(users::email)
+            .get_result::<String>(&mut con)
+            .await?;
+
+        let first = create_invitation(
+            &mut con,
+            &app,
+            &invitee_email,
+            AppOwnerRole::Finance,
+            &owner,
+        )
+        .await?;
+        let second = create_invitation(
+            &mut con,
+            &app,
+            &invitee_email,
+            AppOwnerRole::Maintainer,
+            &owner,
+        )
+        .await?;
+
+        // Inviting the same address again replaces the earlier invitation
+        assert_eq!(
+            accept_invitation(&mut con, &first, &invitee, &invitee_email).await?,
+            AcceptOutcome::NotFound
+        );
+        assert_eq!(
+            accept_invitation(&mut con, &second, &invitee, "someone-else@example.com").await?,
+            AcceptOutcome::WrongAccount
+        );
+        assert!(!is_verified_owner_email(&mut con, &app, &invitee_email).await?);
+
+        assert_eq!(
+            accept_invitation(&mut con, &second, &invitee, &invitee_email).await?,
+            AcceptOutcome::Accepted {
+                app_id: app.clone(),
+                role: AppOwnerRole::Maintainer,
+            }
+        );
+        assert_eq!(
+            get_app_role(&mut con, &app, &invitee).await?,
+            Some(AppOwnerRole::Maintainer)
+        );
+        assert!(is_verified_owner_email(&mut con, &app, &invitee_email).await?);
+
+        assert_eq!(
+            accept_invitation(&mut con, &second, &invitee, &invitee_email).await?,
+            AcceptOutcome::NotFound
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,423 @@
+use actix_web::{
+    get, post,
+    web::{Data, Json, Path},
+    HttpResponse,
+};
+use common::models::{AppOwnerRole, AuditAction, NewAuditEvent};
+use diesel::{dsl::count, ExpressionMethods, OptionalExtension, QueryDsl};
+use diesel_async::{
+    pooled_connection::bb8::{Pool, PooledConnection},
+    scoped_futures::ScopedFutureExt,
+    AsyncConnection, AsyncPgConnection, RunQueryDsl,
+};
+use uuid::Uuid;
+
+use crate::{
+    extractors::AuthedUser,
+    types::{
+        dashboard::{AppCoOwner, AppOwnershipTransfer},
+        ErrorResponse, ErrorTranslationKey,
+    },
+    utils::audit,
+};
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/dashboard/app_owners/{app_id}",
+    responses(
+        (status = 200, description = "Owners of the app and their roles", body = Vec<AppCoOwner>),
+        (status = 403, body = ErrorResponse),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Fetching app owners", skip(user, pool))
+)]
+#[get("/app_owners/{app_id}")]
+pub async fn get_app_owners(
+    user: AuthedUser,
+    path: Path<(String,)>,
+    pool: Data<Pool<AsyncPgConnection>>,
+) -> HttpResponse {
+    let app_id = path.into_inner().0;
+
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for app owners: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    match get_app_role(&mut con, &app_id, &user.uuid).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return not_owned_response(),
+        Err(e) => {
+            tracing::error!("Error checking app ownership: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    }
+
+    match list_app_owners(&mut con, &app_id).await {
+        Ok(o) => HttpResponse::Ok().json(o),
+        Err(e) => {
+            tracing::error!("Error getting app owners: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/dashboard/app_owners/{app_id}/{user_id}/remove",
+    responses(
+        (status = 200, description = "The owner was removed from the app"),
+        (status = 400, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Removing app owner", skip(user, pool))
+)]
+#[post("/app_owners/{app_id}/{user_id}/remove")]
+pub async fn remove_app_owner(
+    user: AuthedUser,
+    path: Path<(String, Uuid)>,
+    pool: Data<Pool<AsyncPgConnection>>,
+) -> HttpResponse {
+    let (app_id, owner_id) = path.into_inner();
+
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for app owners: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    // Anyone can leave an app, but only its primary owner can remove others
+    match get_app_role(&mut con, &app_id, &user.uuid).await {
+        Ok(Some(r)) if r.can_manage_owners() || owner_id == user.uuid => {}
+        Ok(Some(_)) => return insufficient_role_response(),
+        Ok(None) => return not_owned_response(),
+        Err(e) => {
+            tracing::error!("Error checking app ownership: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    }
+
+    match remove_owner(&mut con, &app_id, &owner_id).await {
+        Ok(RemovalOutcome::Removed) => {
+            audit::record(
+                &mut con,
+                NewAuditEvent::new(&user.uuid, AuditAction::AppOwnerRemoved)
+                    .app(&app_id)
+                    .target_user(&owner_id),
+            )
+            .await;
+
+            HttpResponse::Ok().finish()
+        }
+        Ok(RemovalOutcome::NotFound) => owner_not_found_response(),
+        Ok(RemovalOutcome::LastOwner) => HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Transfer primary ownership of the app before removing its last owner".into(),
+            translation_key: ErrorTranslationKey::LastAppOwner,
+        }),
+        Err(e) => {
+            tracing::error!("Error removing app owner: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/dashboard/app_owners/{app_id}/transfer",
+    request_body = AppOwnershipTransfer,
+    responses(
+        (status = 200, description = "Primary ownership was transferred, the current user is now a maintainer"),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Transferring app ownership", skip(user, pool))
+)]
+#[post("/app_owners/{app_id}/transfer")]
+pub async fn transfer_app_ownership(
+    user: AuthedUser,
+    path: Path<(String,)>,
+    transfer: Json<AppOwnershipTransfer>,
+    pool: Data<Pool<AsyncPgConnection>>,
+) -> HttpResponse {
+    let app_id = path.into_inner().0;
+
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for app owners: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    match get_app_role(&mut con, &app_id, &user.uuid).await {
+        Ok(Some(r)) if r.can_manage_owners() => {}
+        Ok(Some(_)) => return insufficient_role_response(),
+        Ok(None) => return not_owned_response(),
+        Err(e) => {
+            tracing::error!("Error checking app ownership: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    }
+
+    if transfer.user_id == user.uuid {
+        return HttpResponse::Ok().finish();
+    }
+
+    match transfer_ownership(&mut con, &app_id, &user.uuid, &transfer.user_id).await {
+        Ok(true) => {
+            audit::record(
+                &mut con,
+                NewAuditEvent::new(&user.uuid, AuditAction::AppOwnershipTransferred)
+                    .app(&app_id)
+                    .target_user(&transfer.user_id),
+            )
+            .await;
+
+            HttpResponse::Ok().finish()
+        }
+        Ok(false) => owner_not_found_response(),
+        Err(e) => {
+            tracing::error!("Error transferring app ownership: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+fn not_owned_response() -> HttpResponse {
+    HttpResponse::Forbidden().json(ErrorResponse {
+        error: "Current user is not a verified owner of this app".into(),
+        translation_key: ErrorTranslationKey::AppNotOwned,
+    })
+}
+
+pub(crate) fn insufficient_role_response() -> HttpResponse {
+    HttpResponse::Forbidden().json(ErrorResponse {
+        error: "Current user's role doesn't allow this for the app".into(),
+        translation_key: ErrorTranslationKey::InsufficientAppRole,
+    })
+}
+
+fn owner_not_found_response() -> HttpResponse {
+    HttpResponse::NotFound().json(ErrorResponse {
+        error: "The user isn't a verified owner of this app".into(),
+        translation_key: ErrorTranslationKey::AppOwnerNotFound,
+    })
+}
+
+/// The role of the user on the app, if they're one of its verified owners
+pub async fn get_app_role(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    app_id: &str,
+    uuid: &Uuid,
+) -> Result<Option<AppOwnerRole>, diesel::result::Error> {
+    use common::schema::app_owners;
+
+    app_owners::table
+        .filter(app_owners::app_id.eq(app_id))
+        .filter(app_owners::user_id.eq(uuid))
+        .filter(app_owners::verified_owner.eq(true))
+        .select(app_owners::role)
+        .get_result::<AppOwnerRole>(con)
+        .await
+        .optional()
+}
+
+async fn list_app_owners(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    app_id: &str,
+) -> Result<Vec<AppCoOwner>, diesel::result::Error> {
+    use common::schema::{app_owners, users};
+
+    Ok(app_owners::table
+        .inner_join(users::table)
+        .filter(app_owners::app_id.eq(app_id))
+        .select((
+            app_owners::user_id,
+            users::email,
+            app_owners::role,
+            app_owners::verified_owner,
+        ))
+        .order(users::email.asc())
+        .load::<(Uuid, String, AppOwnerRole, bool)>(con)
+        .await?
+        .into_iter()
+        .map(|(user_id, email, role, verified_owner)| AppCoOwner {
+            user_id,
+            email,
+            role,
+            verified_owner,
+        })
+        .collect())
+}
+
+#[derive(Debug, PartialEq)]
+enum RemovalOutcome {
+    Removed,
+    NotFound,
+    LastOwner,
+}
+
+async fn remove_owner(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    app_id: &str,
+    owner_id: &Uuid,
+) -> Result<RemovalOutcome, diesel::result::Error> {
+    use common::schema::app_owners;
+
+    let Some(role) = get_app_role(con, app_id, owner_id).await? else {
+        return Ok(RemovalOutcome::NotFound);
+    };
+
+    if role == AppOwnerRole::Owner {
+        let owners = app_owners::table
+            .filter(app_owners::app_id.eq(app_id))
+            .filter(app_owners::verified_owner.eq(true))
+            .filter(app_owners::role.eq(AppOwnerRole::Owner))
+            .select(count(app_owners::user_id))
+            .get_result::<i64>(con)
+            .await?;
+
+        if owners < 2 {
+            return Ok(RemovalOutcome::LastOwner);
+        }
+    }
+
+    diesel::delete(
+        app_owners::table
+            .filter(app_owners::app_id.eq(app_id))
+            .filter(app_owners::user_id.eq(owner_id)),
+    )
+    .execute(con)
+    .await?;
+
+    Ok(RemovalOutcome::Removed)
+}
+
+/// Makes another verified owner the primary owner of the app, and the current primary owner a
+/// maintainer. Returns false if the new owner isn't a verified owner of the app.
+async fn transfer_ownership(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    app_id: &str,
+    current_owner: &Uuid,
+    new_owner: &Uuid,
+) -> Result<bool, diesel::result::Error> {
+    use common::schema::app_owners;
+
+    let app_id = app_id.to_owned();
+    let current_owner = *current_owner;
+    let new_owner = *new_owner;
+
+    con.transaction::<_, diesel::result::Error, _>(|transaction| {
+        async move {
+            let promoted = diesel::update(
+                app_owners::table
+                    .filter(app_owners::app_id.eq(&app_id))
+                    .filter(app_owners::user_id.eq(new_owner))
+                    .filter(app_owners::verified_owner.eq(true)),
+            )
+            .set(app_owners::role.eq(AppOwnerRole::Owner))
+            .execute(transaction)
+            .await?;
+
+            if promoted == 0 {
+                return Ok(false);
+            }
+
+            diesel::update(
+                app_owners::table
+                    .filter(app_owners::app_id.eq(&app_id))
+                    .filter(app_owners::user_id.eq(current_owner)),
+            )
+            .set(app_owners::role.eq(AppOwnerRole::Maintainer))
+            .execute(transaction)
+            .await?;
+
+            Ok(true)
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::db_test::{create_app, create_user, db_pool};
+
+    use super::*;
+
+    async fn add_owner(
+        con: &mut PooledConnection<'_, AsyncPgConnection>,
+        app_id: &str,
+        uuid: &Uuid,
+        role: AppOwnerRole,
+    ) -> anyhow::Result<()> {
+        use common::schema::app_owners;
+
+        diesel::insert_into(app_owners::table)
+            .values((
+                app_owners::app_id.eq(app_id),
+                app_owners::user_id.eq(uuid),
+                app_owners::verified_owner.eq(true),
+                app_owners::role.eq(role),
+            ))
+            .execute(con)
+            .await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_transfer_and_remove_owners() -> anyhow::Result<()> {
+        let db_pool = db_pool().await;
+        let mut con = db_pool.get().await?;
+
+        con.begin_test_transaction().await?;
+
+        let owner = create_user(&mut con, true).await?;
+        let maintainer = create_user(&mut con, true).await?;
+        let stranger = create_user(&mut con, true).await?;
+        let app = create_app(&mut con, Some(&owner), None).await?;
+        add_owner(&mut con, &app, &maintainer, AppOwnerRole::Maintainer).await?;
+
+        assert_eq!(list_app_owners(&mut con, &app).await?.len(), 2);
+
+        assert_eq!(
+            remove_owner(&mut con, &app, &owner).await?,
+            RemovalOutcome::LastOwner
+        );
+        assert!(!transfer_ownership(&mut con, &app, &owner, &stranger).await?);
+
+        assert!(transfer_ownership(&mut con, &app, &owner, &maintainer).await?);
+        assert_eq!(
+            get_app_role(&mut con, &app, &maintainer).await?,
+            Some(AppOwnerRole::Owner)
+        );
+        assert_eq!(
+            get_app_role(&mut con, &app, &owner).await?,
+            Some(AppOwnerRole::Maintainer)
+        );
+
+        assert_eq!(
+            remove_owner(&mut con, &app, &owner).await?,
+            RemovalOutcome::Removed
+        );
+        assert_eq!(
+            remove_owner(&mut con, &app, &owner).await?,
+            RemovalOutcome::NotFound
+        );
+        assert_eq!(get_app_role(&mut con, &app, &owner).await?, None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,251 @@
+use actix_web::{
+    post,
+    web::{Data, Json, Path},
+    HttpResponse,
+};
+use std::collections::HashMap;
+
+use common::models::{AuditAction, NewAuditEvent};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::{
+    pooled_connection::bb8::{Pool, PooledConnection},
+    AsyncPgConnection, RunQueryDsl,
+};
+use serde_json::json;
+
+use crate::{
+    extractors::AuthedUser,
+    types::{dashboard::AppPricing, ErrorResponse, ErrorTranslationKey},
+    utils::{
+        audit,
+        currency::{validate_amount, AmountError},
+    },
+};
+
+use super::app_owners::{get_app_role, insufficient_role_response};
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/dashboard/apps/{app_id}/pricing",
+    request_body = AppPricing,
+    responses(
+        (status = 200, description = "Pricing of the app was updated"),
+        (status = 400, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Setting app pricing", skip(user, pool))
+)]
+#[post("/apps/{app_id}/pricing")]
+pub async fn set_app_pricing(
+    user: AuthedUser,
+    path: Path<(String,)>,
+    pricing: Json<AppPricing>,
+    pool: Data<Pool<AsyncPgConnection>>,
+) -> HttpResponse {
+    let app_id = path.into_inner().0;
+
+    if !pricing.is_valid() {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "Prices must be at least the minimum, which paid-only apps must set".into(),
+            translation_key: ErrorTranslationKey::InvalidAppPricing,
+        });
+    }
+
+    let settings = common::settings::get_settings().expect("Failed to read settings.");
+
+    match validate_pricing_currency(&pricing, &settings.stripe.minimum_amounts) {
+        Ok(()) => {}
+        Err(AmountError::UnsupportedCurrency) => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "Payments in the requested currency aren't supported".into(),
+                translation_key: ErrorTranslationKey::UnsupportedCurrency,
+            });
+        }
+        Err(_) => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "Prices must be payable in the chosen currency".into(),
+                translation_key: ErrorTranslationKey::InvalidAppPricing,
+            });
+        }
+    }
+
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for app pricing: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    match get_app_role(&mut con, &app_id, &user.uuid).await {
+        Ok(Some(r)) if r.can_manage_payments() => {}
+        Ok(Some(_)) => return insufficient_role_response(),
+        Ok(None) => {
+            return HttpResponse::Forbidden().json(ErrorResponse {
+                error: "Only verified owners can change the pricing of an app".into(),
+                translation_key: ErrorTranslationKey::AppNotOwned,
+            });
+        }
+        Err(e) => {
+            tracing::error!("Error checking app ownership: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    }
+
+    if let Err(e) = set_pricing(&mut con, &app_id, &pricing).await {
+        tracing::error!("Error setting pricing on app: {}", e);
+        return HttpResponse::InternalServerError().finish();
+    }
+
+    audit::record(
+        &mut con,
+        NewAuditEvent::new(&user.uuid, AuditAction::AppPricingSet)
+            .app(&app_id)
+            .details(json!({
+                "suggested_price": pricing.suggested_price,
+                "minimum_price": pricing.minimum_price,
+                "currency": pricing.currency,
+                "free_allowed": pricing.free_allowed,
+            })),
+    )
+    .await;
+
+    HttpResponse::Ok().finish()
+}
+
+/// Checks buyers could actually pay the prices in `pricing`, a price of zero is always allowed
+fn validate_pricing_currency(
+    pricing: &AppPricing,
+    minimum_amounts: &HashMap<String, f64>,
+) -> Result<(), AmountError> {
+    for price in [Some(pricing.minimum_price), pricing.suggested_price]
+        .into_iter()
+        .flatten()
+    {
+        match validate_amount(price.into(), &pricing.currency, minimum_amounts) {
+            Ok(_) => {}
+            Err(AmountError::BelowMinimum(_)) if price == 0 => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(())
+}
+
+async fn set_pricing(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    app_id: &str,
+    pricing: &AppPricing,
+) -> Result<usize, diesel::result::Error> {
+    use common::schema::apps;
+
+    diesel::update(apps::table.filter(apps::id.eq(app_id)))
+        .set((
+            apps::suggested_price.eq(pricing.suggested_price),
+            apps::minimum_price.eq(pricing.minimum_price),
+            apps::price_currency.eq(pricing.currency.to_lowercase()),
+            apps::free_allowed.eq(pricing.free_allowed),
+        ))
+        .execute(con)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel_async::AsyncConnection;
+
+    use crate::utils::db_test::{create_app, create_user, db_pool};
+
+    use super::*;
+
+    #[test]
+    fn test_pricing_validation() {
+        let pricing = |suggested_price, minimum_price, free_allowed| AppPricing {
+            suggested_price,
+            minimum_price,
+            currency: "usd".into(),
+            free_allowed,
+        };
+
+        assert!(pricing(None, 0, true).is_valid());
+        assert!(pricing(Some(300), 100, true).is_valid());
+        assert!(pricing(Some(500), 500, false).is_valid());
+        assert!(!pricing(None, -1, true).is_valid());
+        assert!(!pricing(Some(100), 300, true).is_valid());
+        assert!(!pricing(Some(300), 0, false).is_valid());
+    }
+
+    #[test]
+    fn test_pricing_currency_validation() {
+        let minimums = HashMap::from([("usd".to_string(), 1.0), ("jpy".to_string(), 150.0)]);
+        let pricing = |suggested_price, minimum_price, currency: &str| AppPricing {
+            suggested_price,
+            minimum_price,
+            currency: currency.into(),
+            free_allowed: true,
+        };
+
+        assert_eq!(
+            validate_pricing_currency(&pricing(None, 0, "usd"), &minimums),
+            Ok(())
+        );
+        assert_eq!(
+            validate_pricing_currency(&pricing(Some(300), 100, "USD"), &minimums),
+            Ok(())
+        );
+        assert_eq!(
+            validate_pricing_currency(&pricing(Some(500), 0, "jpy"), &minimums),
+            Ok(())
+        );
+        assert_eq!(
+            validate_pricing_currency(&pricing(None, 50, "usd"), &minimums),
+            Err(AmountError::BelowMinimum(100))
+        );
+        assert_eq!(
+            validate_pricing_currency(&pricing(Some(100), 0, "jpy"), &minimums),
+            Err(AmountError::BelowMinimum(150))
+        );
+        assert_eq!(
+            validate_pricing_currency(&pricing(None, 0, "eur"), &minimums),
+            Err(AmountError::UnsupportedCurrency)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_pricing() -> anyhow::Result<()> {
+        use common::schema::apps;
+
+        let db_pool = db_pool().await;
+        let mut con = db_pool.get().await?;
+
+        con.begin_test_transaction().await?;
+
+        let user = create_user(&mut con, true).await?;
+        let app = create_app(&mut con, Some(&user), None).await?;
+
+        let pricing = AppPricing {
+            suggested_price: Some(500),
+            minimum_price: 200,
+            currency: "EUR".into(),
+            free_allowed: false,
+        };
+        set_pricing(&mut con, &app, &pricing).await?;
+
+        let stored = apps::table
+            .filter(apps::id.eq(&app))
+            .select((
+                apps::suggested_price,
+                apps::minimum_price,
+                apps::price_currency,
+                apps::free_allowed,
+            ))
+            .get_result::<(Option<i32>, i32, String, bool)>(&mut con)
+            .await?;
+
+        assert_eq!(stored, (Some(500), 200, "eur".to_string(), false));
+
+        Ok(())
+    }
+}
@@ -1,16 +1,25 @@
 use actix_web::web::{Data, Json};
 use actix_web::{get, post, HttpResponse};
 use anyhow::{anyhow, Result};
-use diesel::{ExpressionMethods, OptionalExtension, QueryDsl};
+use diesel::upsert::excluded;
+use diesel::{BoolExpressionMethods, ExpressionMethods, OptionalExtension, QueryDsl};
 use diesel_async::pooled_connection::bb8::{Pool, PooledConnection};
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use secrecy::SecretString;
 use url::Url;
 use uuid::Uuid;
 
 use crate::extractors::AuthedUser;
 use crate::types::dashboard::CreateApp;
 use crate::types::{ErrorResponse, ErrorTranslationKey};
-use common::models::App;
+use crate::utils::audit;
+use crate::utils::forges::{
+    forge_for_rdnn, user_administers_repo, validate_forge_url_and_rdnn, Forge, GitHub,
+    RdnnValidationResult,
+};
+use common::models::{App, AuditAction, NewAuditEvent};
+use common::network::check_public_https_url;
+use serde_json::json;
 
 #[cfg_attr(feature = "openapi", utoipa::path(
     path = "/dashboard/apps",
@@ -30,6 +39,10 @@ use common::models::App;
                         last_update: None,
                         is_published: true,
                         stripe_connect_id: Some("acct_1NCliJPBGjCwUDHc".into()),
+                        suggested_price: Some(300),
+                        minimum_price: 100,
+                        price_currency: "usd".into(),
+                        free_allowed: true,
                     },
                     App {
                         id: "io.elementary.photos".into(),
@@ -40,6 +53,10 @@ use common::models::App;
                         last_update: None,
                         is_published: true,
                         stripe_connect_id: None,
+                        suggested_price: None,
+                        minimum_price: 0,
+                        price_currency: "usd".into(),
+                        free_allowed: true,
                     }
                 ]
             )
@@ -86,6 +103,10 @@ pub async fn get_apps_from_db(
             last_update,
             is_published,
             stripe_connect_id,
+            suggested_price,
+            minimum_price,
+            price_currency,
+            free_allowed,
         ))
         .filter(user_id.eq(uuid))
         .get_results::<App>(con)
@@ -111,8 +132,6 @@ pub async fn add_app(
     pool: Data<Pool<AsyncPgConnection>>,
     app: Json<CreateApp>,
 ) -> HttpResponse {
-    let github_user_id = get_github_user_id(&pool, &user.uuid).await;
-
     let url = match Url::parse(&app.repository) {
         Ok(u) => u,
         Err(_) => {
@@ -123,11 +142,27 @@ pub async fn add_app(
         }
     };
 
+    // The repository is fetched from our servers when submitting updates
+    let repository = app.repository.to_owned();
+    match actix_web::web::block(move || check_public_https_url(&repository)).await {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: format!("The repository must be a public https URL: {}", e),
+                translation_key: ErrorTranslationKey::AddAppInvalidRepositoryUrl,
+            });
+        }
+        Err(e) => {
+            tracing::error!("Error checking repository URL: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    }
+
     let mut verified = false;
-    if app.app_id.starts_with("com.github.") || app.app_id.starts_with("io.github.") {
-        let (owner, path_repo_name) = match validate_github_url_and_rdnn(&url, &app.app_id) {
-            GithubRdnnValidationResult::Valid((owner, repo)) => (owner, repo),
-            GithubRdnnValidationResult::Invalid((error, translation_key)) => {
+    if let Some(forge) = forge_for_rdnn(&app.app_id) {
+        let (owner, path_repo_name) = match validate_forge_url_and_rdnn(forge, &url, &app.app_id) {
+            RdnnValidationResult::Valid((owner, repo)) => (owner, repo),
+            RdnnValidationResult::Invalid((error, translation_key)) => {
                 return HttpResponse::BadRequest().json(ErrorResponse {
                     error,
                     translation_key,
@@ -135,19 +170,12 @@ pub async fn add_app(
             }
         };
 
-        if let Ok(Some(github_user_id)) = github_user_id {
-            let owner_id = github_utils::get_github_repo_owner_id(&owner, &path_repo_name)
-                .await
-                .ok();
-
-            if let Some(owner_id) = owner_id {
-                if let github_utils::GithubOwner::User(repo_owner_id) = owner_id {
-                    verified = repo_owner_id == github_user_id;
-                } else if let github_utils::GithubOwner::Org(org_id) = owner_id {
-                    verified = is_user_admin_member_of_github_org(&pool, &user.uuid, org_id).await;
-                }
-            }
-        }
+        // GitHub apps can also be verified through the organisation owning the repository
+        verified = if forge.id() == GitHub.id() {
+            is_user_github_owner(&pool, &user.uuid, &owner, &path_repo_name).await
+        } else {
+            is_user_forge_repo_admin(&pool, &user.uuid, forge, &owner, &path_repo_name).await
+        };
     }
 
     let mut con = match pool.get().await {
@@ -166,6 +194,14 @@ pub async fn add_app(
         }
     }
 
+    audit::record(
+        &mut con,
+        NewAuditEvent::new(&user.uuid, AuditAction::AppAdded)
+            .app(&app.app_id)
+            .details(json!({ "repository": app.repository, "verified": verified })),
+    )
+    .await;
+
     HttpResponse::Ok().finish()
 }
 
@@ -201,32 +237,91 @@ pub async fn add_app_to_db(
         .execute(con)
         .await?;
 
+    // Adding an app again once ownership can be verified should verify the existing owner
     diesel::insert_into(app_owners)
         .values((
             user_id.eq(owner),
             app_id.eq(new_app_id),
             verified_owner.eq(verified),
         ))
-        .on_conflict_do_nothing()
+        .on_conflict((user_id, app_id))
+        .do_update()
+        .set(verified_owner.eq(verified_owner.or(excluded(verified_owner))))
         .execute(con)
         .await?;
 
     Ok(())
 }
 
-async fn is_user_admin_member_of_github_org(
+async fn is_user_github_owner(
+    pool: &Data<Pool<AsyncPgConnection>>,
+    uuid: &Uuid,
+    owner: &str,
+    repo: &str,
+) -> bool {
+    let Ok(Some(github_user_id)) = get_github_user_id(pool, uuid).await else {
+        return false;
+    };
+
+    match github_utils::get_github_repo_owner_id(owner, repo).await {
+        Ok(github_utils::GithubOwner::User(repo_owner_id)) => repo_owner_id == github_user_id,
+        Ok(github_utils::GithubOwner::Org(org_id)) => {
+            is_user_admin_member_of_github_org(pool, uuid, org_id).await
+        }
+        Err(_) => false,
+    }
+}
+
+async fn is_user_forge_repo_admin(
     pool: &Data<Pool<AsyncPgConnection>>,
     uuid: &Uuid,
-    _org_id: String,
+    forge: &dyn Forge,
+    owner: &str,
+    repo: &str,
 ) -> bool {
-    let _tokens = match get_github_user_tokens(pool, uuid).await {
-        Ok(t) => t,
-        Err(_) => return false,
+    let access_token = match get_forge_access_token(pool, uuid, forge.id()).await {
+        Ok(Some(t)) => SecretString::new(t),
+        Ok(None) => return false,
+        Err(e) => {
+            tracing::error!("Error getting {} access token: {}", forge.name(), e);
+            return false;
+        }
     };
 
-    // TODO: Awaiting https://github.com/XAMPPRocky/octocrab/pull/357
+    match user_administers_repo(forge, forge.api_url(), &access_token, owner, repo).await {
+        Ok(admin) => admin,
+        Err(e) => {
+            tracing::warn!(
+                "Unable to check {} repository permissions: {}",
+                forge.name(),
+                e
+            );
+            false
+        }
+    }
+}
+
+async fn is_user_admin_member_of_github_org(
+    pool: &Data<Pool<AsyncPgConnection>>,
+    uuid: &Uuid,
+    org_id: String,
+) -> bool {
+    let access_token = match get_github_access_token(pool, uuid).await {
+        Ok(Some(t)) => SecretString::new(t),
+        Ok(None) => return false,
+        Err(e) => {
+            tracing::error!("Error getting GitHub access token: {}", e);
+            return false;
+        }
+    };
 
-    false
+    match github_utils::is_user_admin_member_of_github_org(&access_token, &org_id).await {
+        Ok(admin) => admin,
+        Err(e) => {
+            tracing::warn!("Unable to check GitHub organisation membership: {}", e);
+            false
+        }
+    }
 }
 
 async fn get_github_user_id(
@@ -244,99 +339,86 @@ async fn get_github_user_id(
         .await?)
 }
 
-async fn get_github_user_tokens(
+async fn get_github_access_token(
     pool: &Data<Pool<AsyncPgConnection>>,
     user: &Uuid,
-) -> Result<(Option<String>, Option<String>)> {
+) -> Result<Option<String>> {
     use common::schema::github_auth::dsl::*;
 
     let mut con = pool.get().await?;
 
     Ok(github_auth
-        .select((github_access_token, github_refresh_token))
+        .select(github_access_token)
         .filter(user_id.eq(user))
-        .get_result(&mut con)
-        .await?)
+        .get_result::<Option<String>>(&mut con)
+        .await
+        .optional()?
+        .flatten())
 }
 
-#[derive(Debug, PartialEq)]
-enum GithubRdnnValidationResult {
-    Valid((String, String)),
-    Invalid((String, ErrorTranslationKey)),
+async fn get_forge_access_token(
+    pool: &Data<Pool<AsyncPgConnection>>,
+    user: &Uuid,
+    forge_id: &str,
+) -> Result<Option<String>> {
+    use common::schema::forge_auth::dsl::*;
+
+    let mut con = pool.get().await?;
+
+    Ok(forge_auth
+        .select(access_token)
+        .filter(user_id.eq(user))
+        .filter(forge.eq(forge_id))
+        .get_result::<Option<String>>(&mut con)
+        .await
+        .optional()?
+        .flatten())
 }
 
-fn validate_github_url_and_rdnn(url: &Url, rdnn: &str) -> GithubRdnnValidationResult {
-    let rdnn_parts = rdnn.split('.').collect::<Vec<&str>>();
-    if rdnn_parts.len() != 4 {
-        return GithubRdnnValidationResult::Invalid((
-            "GitHub RDNNs must have exactly 4 sections/components".into(),
-            ErrorTranslationKey::AddAppNonMatchingGithubRDNN,
-        ));
-    }
+#[cfg(test)]
+mod tests {
+    use diesel_async::AsyncConnection;
 
-    let path_segments = match url.path_segments() {
-        Some(s) => s,
-        None => {
-            return GithubRdnnValidationResult::Invalid((
-                "Invalid GitHub repository URL passed in `git_repo_url`".into(),
-                ErrorTranslationKey::AddAppInvalidRepositoryUrl,
-            ));
-        }
-    }
-    .collect::<Vec<&str>>();
+    use crate::utils::db_test::{create_user, db_pool};
 
-    let path_repo_name = path_segments.get(1).unwrap();
-    let path_repo_name = if path_repo_name.ends_with(".git") {
-        path_repo_name.strip_suffix(".git").unwrap()
-    } else {
-        path_repo_name
-    };
+    use super::*;
 
-    if url.host_str() != Some("github.com") {
-        return GithubRdnnValidationResult::Invalid((
-            "GitHub RDNN repositories must be served from GitHub".into(),
-            ErrorTranslationKey::AddAppNonMatchingGithubRDNN,
-        ));
+    async fn owner_verified(
+        con: &mut PooledConnection<'_, AsyncPgConnection>,
+        owner: &Uuid,
+        app: &str,
+    ) -> Result<bool> {
+        use common::schema::app_owners::dsl::*;
+
+        Ok(app_owners
+            .filter(user_id.eq(owner))
+            .filter(app_id.eq(app))
+            .select(verified_owner)
+            .get_result::<bool>(con)
+            .await?)
     }
 
-    if path_segments.len() != 2 {
-        return GithubRdnnValidationResult::Invalid((
-            "Invalid GitHub repository URL passed in `git_repo_url`".into(),
-            ErrorTranslationKey::AddAppInvalidRepositoryUrl,
-        ));
-    }
+    #[tokio::test]
+    async fn test_readding_app_verifies_owner() -> anyhow::Result<()> {
+        let db_pool = db_pool().await;
+        let mut con = db_pool.get().await?;
 
-    if *rdnn_parts.get(2).unwrap() != *path_segments.get(0).unwrap() {
-        return GithubRdnnValidationResult::Invalid((
-            "RDNN owner doesn't match GitHub URL owner".into(),
-            ErrorTranslationKey::AddAppNonMatchingGithubRDNN,
-        ));
-    }
+        con.begin_test_transaction().await?;
 
-    if *rdnn_parts.get(3).unwrap() != path_repo_name {
-        return GithubRdnnValidationResult::Invalid((
-            "RDNN repo doesn't match GitHub URL repo".into(),
-            ErrorTranslationKey::AddAppNonMatchingGithubRDNN,
-        ));
-    }
+        let user = create_user(&mut con, true).await?;
+        let app = "com.github.elementary.readded";
+        let repository = "https://github.com/elementary/readded.git";
 
-    GithubRdnnValidationResult::Valid((
-        (*path_segments.get(0).unwrap()).to_owned(),
-        path_repo_name.to_owned(),
-    ))
-}
+        add_app_to_db(&mut con, &user, app, repository, false).await?;
+        assert!(!owner_verified(&mut con, &user, app).await?);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        add_app_to_db(&mut con, &user, app, repository, true).await?;
+        assert!(owner_verified(&mut con, &user, app).await?);
+
+        // Failing to verify again later doesn't take the verification away
+        add_app_to_db(&mut con, &user, app, repository, false).await?;
+        assert!(owner_verified(&mut con, &user, app).await?);
 
-    #[test]
-    fn github_rdnn_validation() {
-        let url = Url::parse("https://github.com/davidmhewitt/torrential.git")
-            .expect("Couldn't parse URL");
-        assert_eq!(
-            validate_github_url_and_rdnn(&url, "com.github.davidmhewitt.torrential"),
-            GithubRdnnValidationResult::Valid(("davidmhewitt".into(), "torrential".into()))
-        );
+        Ok(())
     }
 }
@@ -1,14 +1,17 @@
 use actix_web::{post, HttpResponse};
+use common::models::{AuditAction, NewAuditEvent};
 use diesel::ExpressionMethods;
 use diesel_async::{
     pooled_connection::bb8::{Pool, PooledConnection},
     AsyncPgConnection, RunQueryDsl,
 };
+use serde_json::json;
 use stripe::{Account, AccountId, AccountType, Client, CreateAccount, StripeError};
 
 use crate::{
     extractors::AuthedUser,
     types::{ErrorResponse, ErrorTranslationKey},
+    utils::audit,
 };
 
 #[cfg_attr(feature = "openapi", utoipa::path(
@@ -52,6 +55,13 @@ pub async fn create(
         });
     }
 
+    audit::record(
+        &mut con,
+        NewAuditEvent::new(&user.uuid, AuditAction::StripeAccountCreated)
+            .details(json!({ "stripe_account_id": account.id.as_str() })),
+    )
+    .await;
+
     HttpResponse::Ok().finish()
 }
 
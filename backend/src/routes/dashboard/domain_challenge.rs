@@ -0,0 +1,383 @@
+use actix_web::{
+    post,
+    web::{Data, Path},
+    HttpResponse,
+};
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl};
+use diesel_async::{
+    pooled_connection::bb8::{Pool, PooledConnection},
+    scoped_futures::ScopedFutureExt,
+    AsyncConnection, AsyncPgConnection, RunQueryDsl,
+};
+use uuid::Uuid;
+
+use crate::{
+    extractors::AuthedUser,
+    types::{dashboard::DomainChallenge, ErrorResponse, ErrorTranslationKey},
+    utils::{
+        domain_verification::{
+            check_dns_txt, check_well_known, domain_for_app_id, new_challenge_token,
+            DNS_OVER_HTTPS_URL, DNS_RECORD_PREFIX, WELL_KNOWN_PATH,
+        },
+        forges::forge_for_rdnn,
+    },
+};
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/dashboard/domain_challenge/{app_id}",
+    responses(
+        (status = 200, description = "The challenge to prove control of the app's domain with", body = DomainChallenge),
+        (status = 400, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Issuing domain challenge", skip(user, pool))
+)]
+#[post("/domain_challenge/{app_id}")]
+pub async fn issue_domain_challenge(
+    user: AuthedUser,
+    path: Path<(String,)>,
+    pool: Data<Pool<AsyncPgConnection>>,
+) -> HttpResponse {
+    let app_id = path.into_inner().0;
+
+    // Apps hosted on a forge are verified through the forge account owning the repository
+    let domain = match domain_for_app_id(&app_id) {
+        Some(d) if forge_for_rdnn(&app_id).is_none() => d,
+        _ => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "The ID of this app doesn't belong to a domain that can be verified".into(),
+                translation_key: ErrorTranslationKey::DomainVerificationUnavailable,
+            });
+        }
+    };
+
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for domain challenge: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    match get_or_create_challenge(&mut con, &app_id, &user.uuid, &domain).await {
+        Ok(Some(c)) => HttpResponse::Ok().json(c),
+        Ok(None) => HttpResponse::Forbidden().json(ErrorResponse {
+            error: "Only owners of an app can verify its domain".into(),
+            translation_key: ErrorTranslationKey::AppNotOwned,
+        }),
+        Err(e) => {
+            tracing::error!("Error issuing domain challenge: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/dashboard/domain_challenge/{app_id}/check",
+    responses(
+        (status = 200, description = "Control of the domain was proven and the user is now a verified owner of the app", body = DomainChallenge),
+        (status = 400, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Checking domain challenge", skip(user, pool))
+)]
+#[post("/domain_challenge/{app_id}/check")]
+pub async fn check_domain_challenge(
+    user: AuthedUser,
+    path: Path<(String,)>,
+    pool: Data<Pool<AsyncPgConnection>>,
+) -> HttpResponse {
+    let app_id = path.into_inner().0;
+
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for domain challenge: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let challenge = match get_challenge(&mut con, &app_id, &user.uuid).await {
+        Ok(Some(c)) => c,
+        Ok(None) => {
+            return HttpResponse::NotFound().json(ErrorResponse {
+                error: "No domain challenge has been issued for this app".into(),
+                translation_key: ErrorTranslationKey::DomainChallengeNotFound,
+            });
+        }
+        Err(e) => {
+            tracing::error!("Error getting domain challenge: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    if challenge.verified {
+        return HttpResponse::Ok().json(challenge);
+    }
+
+    if !domain_proven(DNS_OVER_HTTPS_URL, &challenge).await {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "The challenge token wasn't found on the domain's website or DNS records".into(),
+            translation_key: ErrorTranslationKey::DomainVerificationFailed,
+        });
+    }
+
+    if let Err(e) = mark_domain_verified(&mut con, &app_id, &user.uuid).await {
+        tracing::error!("Error verifying app owner after domain challenge: {}", e);
+        return HttpResponse::InternalServerError().finish();
+    }
+
+    HttpResponse::Ok().json(DomainChallenge {
+        verified: true,
+        ..challenge
+    })
+}
+
+fn to_challenge(app_id: &str, domain: String, token: String, verified: bool) -> DomainChallenge {
+    DomainChallenge {
+        app_id: app_id.to_owned(),
+        well_known_url: format!("https://{}{}", domain, WELL_KNOWN_PATH),
+        dns_record_name: format!("{}.{}", DNS_RECORD_PREFIX, domain),
+        domain,
+        token,
+        verified,
+    }
+}
+
+async fn get_challenge(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    app_id: &str,
+    uuid: &Uuid,
+) -> Result<Option<DomainChallenge>, diesel::result::Error> {
+    use common::schema::domain_challenges;
+
+    Ok(domain_challenges::table
+        .filter(domain_challenges::app_id.eq(app_id))
+        .filter(domain_challenges::user_id.eq(uuid))
+        .select((
+            domain_challenges::domain,
+            domain_challenges::token,
+            domain_challenges::verified_at.is_not_null(),
+        ))
+        .get_result::<(String, String, bool)>(con)
+        .await
+        .optional()?
+        .map(|(domain, token, verified)| to_challenge(app_id, domain, token, verified)))
+}
+
+/// Returns the user's existing challenge for the app, or issues a new one if they're one of its
+/// owners
+async fn get_or_create_challenge(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    app_id: &str,
+    uuid: &Uuid,
+    domain: &str,
+) -> Result<Option<DomainChallenge>, diesel::result::Error> {
+    use common::schema::{app_owners, domain_challenges};
+
+    if let Some(challenge) = get_challenge(con, app_id, uuid).await? {
+        return Ok(Some(challenge));
+    }
+
+    let is_owner = app_owners::table
+        .filter(app_owners::app_id.eq(app_id))
+        .filter(app_owners::user_id.eq(uuid))
+        .select(app_owners::user_id)
+        .get_result::<Uuid>(con)
+        .await
+        .optional()?
+        .is_some();
+
+    if !is_owner {
+        return Ok(None);
+    }
+
+    diesel::insert_into(domain_challenges::table)
+        .values((
+            domain_challenges::app_id.eq(app_id),
+            domain_challenges::user_id.eq(uuid),
+            domain_challenges::domain.eq(domain),
+            domain_challenges::token.eq(new_challenge_token()),
+        ))
+        .on_conflict_do_nothing()
+        .execute(con)
+        .await?;
+
+    get_challenge(con, app_id, uuid).await
+}
+
+/// Looks for the challenge token on the domain's website, falling back to its DNS records
+async fn domain_proven(resolver: &str, challenge: &DomainChallenge) -> bool {
+    match check_well_known(&challenge.domain, &challenge.token).await {
+        Ok(true) => return true,
+        Ok(false) => {}
+        Err(e) => tracing::info!("Couldn't fetch {}: {}", challenge.well_known_url, e),
+    }
+
+    match check_dns_txt(resolver, &challenge.domain, &challenge.token).await {
+        Ok(found) => found,
+        Err(e) => {
+            tracing::warn!("Couldn't look up {}: {}", challenge.dns_record_name, e);
+            false
+        }
+    }
+}
+
+async fn mark_domain_verified(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    app_id: &str,
+    uuid: &Uuid,
+) -> Result<(), diesel::result::Error> {
+    use common::schema::{app_owners, apps, domain_challenges};
+
+    let app_id = app_id.to_owned();
+    let uuid = *uuid;
+
+    con.transaction::<_, diesel::result::Error, _>(|transaction| {
+        async move {
+            diesel::update(
+                domain_challenges::table
+                    .filter(domain_challenges::app_id.eq(&app_id))
+                    .filter(domain_challenges::user_id.eq(uuid)),
+            )
+            .set(domain_challenges::verified_at.eq(diesel::dsl::now))
+            .execute(transaction)
+            .await?;
+
+            diesel::update(
+                app_owners::table
+                    .filter(app_owners::app_id.eq(&app_id))
+                    .filter(app_owners::user_id.eq(uuid)),
+            )
+            .set(app_owners::verified_owner.eq(true))
+            .execute(transaction)
+            .await?;
+
+            diesel::update(apps::table.filter(apps::id.eq(&app_id)))
+                .set(apps::is_verified.eq(true))
+                .execute(transaction)
+                .await?;
+
+            Ok(())
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use crate::utils::db_test::{create_app, create_user, db_pool};
+
+    use super::*;
+
+    async fn owner_verified(
+        con: &mut PooledConnection<'_, AsyncPgConnection>,
+        app_id: &str,
+        uuid: &Uuid,
+    ) -> anyhow::Result<bool> {
+        use common::schema::app_owners;
+
+        Ok(app_owners::table
+            .filter(app_owners::app_id.eq(app_id))
+            .filter(app_owners::user_id.eq(uuid))
+            .select(app_owners::verified_owner)
+            .get_result::<bool>(con)
+            .await?)
+    }
+
+    #[tokio::test]
+    async fn test_domain_challenge() -> anyhow::Result<()> {
+        use common::schema::app_owners;
+
+        let db_pool = db_pool().await;
+        let mut con = db_pool.get().await?;
+
+        con.begin_test_transaction().await?;
+
+        let owner = create_user(&mut con, true).await?;
+        let stranger = create_user(&mut con, true).await?;
+        let app = create_app(&mut con, Some(&owner), None).await?;
+
+        diesel::update(app_owners::table.filter(app_owners::app_id.eq(&app)))
+            .set(app_owners::verified_owner.eq(false))
+            .execute(&mut con)
+            .await?;
+
+        assert_eq!(
+            get_or_create_challenge(&mut con, &app, &stranger, "example.com").await?,
+            None
+        );
+
+        let challenge = get_or_create_challenge(&mut con, &app, &owner, "example.com")
+            .await?
+            .expect("Owners should be issued a challenge");
+        assert_eq!(
+            challenge.well_known_url,
+            "https://example.com/.well-known/appcenter-verification"
+        );
+        assert!(!challenge.verified);
+
+        // Requesting the challenge again keeps the same token
+        let again = get_or_create_challenge(&mut con, &app, &owner, "example.com").await?;
+        assert_eq!(again, Some(challenge));
+
+        mark_domain_verified(&mut con, &app, &owner).await?;
+
+        assert!(owner_verified(&mut con, &app, &owner).await?);
+        assert!(get_challenge(&mut con, &app, &owner)
+            .await?
+            .is_some_and(|c| c.verified));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_domain_proven() {
+        // The well-known check refuses loopback addresses, so only the DNS records count here
+        let challenge = to_challenge(
+            "localhost.myapp",
+            "localhost".into(),
+            "appcenter-verification=0123456789abcdef".into(),
+            false,
+        );
+
+        let resolver = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/dns-query"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "Status": 3 })))
+            .up_to_n_times(1)
+            .mount(&resolver)
+            .await;
+
+        let resolver_url = format!("{}/dns-query", resolver.uri());
+        assert!(!domain_proven(&resolver_url, &challenge).await);
+
+        Mock::given(method("GET"))
+            .and(path("/dns-query"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "Status": 0,
+                "Answer": [{
+                    "name": "_appcenter-verification.localhost",
+                    "type": 16,
+                    "TTL": 300,
+                    "data": format!("\"{}\"", challenge.token),
+                }],
+            })))
+            .mount(&resolver)
+            .await;
+
+        assert!(domain_proven(&resolver_url, &challenge).await);
+    }
+}
@@ -0,0 +1,280 @@
+use std::collections::BTreeMap;
+
+use actix_web::{get, web::Data, HttpResponse};
+use anyhow::Result;
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::{
+    pooled_connection::bb8::{Pool, PooledConnection},
+    AsyncPgConnection, RunQueryDsl,
+};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::{
+    extractors::AuthedUser, routes::dashboard::apps::get_apps_from_db,
+    types::dashboard::AppEarnings,
+};
+
+/// A recorded payment, as needed to work out earnings
+struct EarningsRow {
+    app_id: String,
+    created_at: OffsetDateTime,
+    currency: String,
+    amount: i64,
+    amount_refunded: i64,
+    platform_fee: i64,
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/dashboard/earnings",
+    responses(
+        (
+            status = 200,
+            description = "Earnings of the current user's apps per month and currency, newest month first",
+            body = Vec<AppEarnings>,
+        ),
+    )
+))]
+#[get("/earnings")]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Fetching earnings for dashboard", skip(user, pool))
+)]
+pub async fn get_earnings(user: AuthedUser, pool: Data<Pool<AsyncPgConnection>>) -> HttpResponse {
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Error getting database connection: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    match get_payments_for_user(&mut con, &user.uuid).await {
+        Ok(p) => HttpResponse::Ok().json(aggregate_earnings(p)),
+        Err(e) => {
+            tracing::error!("Error getting payments for user: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+async fn get_payments_for_user(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    uuid: &Uuid,
+) -> Result<Vec<EarningsRow>> {
+    use common::schema::payments::dsl::*;
+
+    let owned_apps = get_apps_from_db(con, uuid)
+        .await?
+        .into_iter()
+        .map(|a| a.id)
+        .collect::<Vec<_>>();
+
+    Ok(payments
+        .filter(app_id.eq_any(owned_apps))
+        .select((
+            app_id,
+            created_at,
+            currency,
+            amount,
+            amount_refunded,
+            platform_fee,
+        ))
+        .load::<(Option<String>, OffsetDateTime, String, i64, i64, i64)>(con)
+        .await?
+        .into_iter()
+        .filter_map(|(app, created, cur, paid, refunded, fee)| {
+            Some(EarningsRow {
+                app_id: app?,
+                created_at: created,
+                currency: cur,
+                amount: paid,
+                amount_refunded: refunded,
+                platform_fee: fee,
+            })
+        })
+        .collect())
+}
+
+fn aggregate_earnings(rows: Vec<EarningsRow>) -> Vec<AppEarnings> {
+    let mut totals = BTreeMap::<(String, String, String), AppEarnings>::new();
+
+    for row in rows {
+        let gross = row.amount - row.amount_refunded;
+        if gross <= 0 {
+            continue;
+        }
+
+        // Fees depend on the policy at the time of the payment, so use the one recorded at checkout.
+        // It isn't returned on refunds, but can never be more than what was kept
+        let fee = row.platform_fee.min(gross);
+        let month = format!(
+            "{}-{:02}",
+            row.created_at.year(),
+            u8::from(row.created_at.month())
+        );
+
+        let entry = totals
+            .entry((month.clone(), row.app_id.clone(), row.currency.clone()))
+            .or_insert_with(|| AppEarnings {
+                app_id: row.app_id,
+                month,
+                currency: row.currency,
+                payments: 0,
+                gross: 0,
+                platform_fee: 0,
+                net: 0,
+            });
+
+        entry.payments += 1;
+        entry.gross += gross;
+        entry.platform_fee += fee;
+        entry.net += gross - fee;
+    }
+
+    let mut earnings = totals.into_values().collect::<Vec<_>>();
+    earnings.sort_by(|a, b| b.month.cmp(&a.month));
+
+    earnings
+}
+
+#[cfg(test)]
+mod tests {
+    use common::models::NewPayment;
+    use diesel_async::AsyncConnection;
+    use time::macros::datetime;
+
+    use crate::utils::db_test::{create_app, create_user, db_pool};
+
+    use super::*;
+
+    fn row(
+        app_id: &str,
+        created_at: OffsetDateTime,
+        amount: i64,
+        refunded: i64,
+        fee: i64,
+    ) -> EarningsRow {
+        EarningsRow {
+            app_id: app_id.into(),
+            created_at,
+            currency: "usd".into(),
+            amount,
+            amount_refunded: refunded,
+            platform_fee: fee,
+        }
+    }
+
+    #[test]
+    fn test_aggregation() {
+        let earnings = aggregate_earnings(vec![
+            row(
+                "com.example.camera",
+                datetime!(2023-07-31 23:59 UTC),
+                300,
+                0,
+                90,
+            ),
+            row(
+                "com.example.camera",
+                datetime!(2023-08-01 00:00 UTC),
+                500,
+                0,
+                150,
+            ),
+            row(
+                "com.example.camera",
+                datetime!(2023-08-14 12:00 UTC),
+                1000,
+                400,
+                300,
+            ),
+            row(
+                "com.example.camera",
+                datetime!(2023-08-20 12:00 UTC),
+                300,
+                300,
+                90,
+            ),
+            row(
+                "com.example.photos",
+                datetime!(2023-08-02 12:00 UTC),
+                100,
+                0,
+                50,
+            ),
+        ]);
+
+        assert_eq!(
+            earnings,
+            vec![
+                AppEarnings {
+                    app_id: "com.example.camera".into(),
+                    month: "2023-08".into(),
+                    currency: "usd".into(),
+                    payments: 2,
+                    gross: 1100,
+                    platform_fee: 450,
+                    net: 650,
+                },
+                AppEarnings {
+                    app_id: "com.example.photos".into(),
+                    month: "2023-08".into(),
+                    currency: "usd".into(),
+                    payments: 1,
+                    gross: 100,
+                    platform_fee: 50,
+                    net: 50,
+                },
+                AppEarnings {
+                    app_id: "com.example.camera".into(),
+                    month: "2023-07".into(),
+                    currency: "usd".into(),
+                    payments: 1,
+                    gross: 300,
+                    platform_fee: 90,
+                    net: 210,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_payments_for_user() -> anyhow::Result<()> {
+        use common::schema::payments;
+
+        let db_pool = db_pool().await;
+        let mut con = db_pool.get().await?;
+
+        con.begin_test_transaction().await?;
+
+        let user = create_user(&mut con, true).await?;
+        let owned = create_app(&mut con, Some(&user), None).await?;
+        let other = create_app(&mut con, None, None).await?;
+
+        for app in [&owned, &other] {
+            diesel::insert_into(payments::table)
+                .values(NewPayment {
+                    checkout_session_id: &format!("cs_test_{}", app),
+                    payment_intent_id: None,
+                    app_id: Some(app),
+                    amount: 500,
+                    currency: "usd",
+                    platform_fee: 150,
+                    status: "paid",
+                })
+                .execute(&mut con)
+                .await?;
+        }
+
+        let earnings = aggregate_earnings(get_payments_for_user(&mut con, &user).await?);
+
+        assert_eq!(earnings.len(), 1);
+        assert_eq!(earnings[0].app_id, owned);
+        assert_eq!(earnings[0].gross, 500);
+        assert_eq!(earnings[0].platform_fee, 150);
+        assert_eq!(earnings[0].net, 350);
+
+        Ok(())
+    }
+}
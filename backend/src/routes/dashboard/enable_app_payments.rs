@@ -1,15 +1,17 @@
 use actix_web::{post, web::Data, HttpResponse};
 use anyhow::anyhow;
-use diesel::{dsl::count, ExpressionMethods, QueryDsl};
+use common::models::{AuditAction, NewAuditEvent};
+use diesel::{ExpressionMethods, QueryDsl};
 use diesel_async::{
     pooled_connection::bb8::{Pool, PooledConnection},
     AsyncPgConnection, RunQueryDsl,
 };
+use serde_json::json;
 use uuid::Uuid;
 
-use crate::extractors::AuthedUser;
+use crate::{extractors::AuthedUser, utils::audit};
 
-use super::link_stripe_account::get_stripe_account_id_for_user;
+use super::{app_owners::get_app_role, link_stripe_account::get_stripe_account_id_for_user};
 
 #[cfg_attr(feature = "openapi", utoipa::path(
     path = "/dashboard/enable_app_payments/{app_id}",
@@ -44,13 +46,21 @@ pub async fn enable_app_payments(
         }
     };
 
-    if let Err(e) =
-        set_stripe_token_on_app(&mut con, &app_id.into_inner().0, &user.uuid, &stripe_id).await
-    {
+    let app_id = app_id.into_inner().0;
+
+    if let Err(e) = set_stripe_token_on_app(&mut con, &app_id, &user.uuid, &stripe_id).await {
         tracing::error!("Error setting stripe account id on app: {}", e);
         return HttpResponse::InternalServerError().finish();
     }
 
+    audit::record(
+        &mut con,
+        NewAuditEvent::new(&user.uuid, AuditAction::PaymentsEnabled)
+            .app(&app_id)
+            .details(json!({ "stripe_account_id": stripe_id })),
+    )
+    .await;
+
     HttpResponse::Ok().finish()
 }
 
@@ -62,8 +72,11 @@ async fn set_stripe_token_on_app(
 ) -> anyhow::Result<()> {
     use common::schema::apps;
 
-    if !user_owns_app(con, app_id, uuid).await? {
-        return Err(anyhow!("Current user does not own this app"));
+    if !get_app_role(con, app_id, uuid)
+        .await?
+        .is_some_and(|r| r.can_manage_payments())
+    {
+        return Err(anyhow!("Current user can't manage payments of this app"));
     }
 
     let rows_updated = diesel::update(apps::table.filter(apps::id.eq(app_id)))
@@ -78,26 +91,6 @@ async fn set_stripe_token_on_app(
     Ok(())
 }
 
-async fn user_owns_app(
-    con: &mut PooledConnection<'_, AsyncPgConnection>,
-    app_id: &str,
-    uuid: &Uuid,
-) -> Result<bool, diesel::result::Error> {
-    use common::schema::app_owners;
-    use common::schema::apps::dsl::*;
-
-    let num_apps = apps
-        .inner_join(app_owners::table)
-        .select(count(id))
-        .filter(app_owners::user_id.eq(uuid))
-        .filter(id.eq(app_id))
-        .filter(app_owners::verified_owner.eq(true))
-        .get_result::<i64>(con)
-        .await?;
-
-    Ok(num_apps > 0)
-}
-
 #[cfg(test)]
 mod tests {
     use diesel_async::{AsyncConnection, RunQueryDsl};
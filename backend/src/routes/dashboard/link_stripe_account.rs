@@ -1,16 +1,19 @@
 use std::str::FromStr;
 
 use actix_web::{get, HttpResponse};
+use common::models::{AuditAction, NewAuditEvent};
 use diesel::{result::Error::NotFound, ExpressionMethods, QueryDsl};
 use diesel_async::{
     pooled_connection::bb8::{Pool, PooledConnection},
     AsyncPgConnection, RunQueryDsl,
 };
+use serde_json::json;
 use stripe::{AccountId, AccountLink, Client, CreateAccountLink};
 
 use crate::{
     extractors::AuthedUser,
     types::{ErrorResponse, ErrorTranslationKey},
+    utils::audit,
 };
 
 #[cfg_attr(feature = "openapi", utoipa::path(
@@ -87,6 +90,13 @@ pub async fn link(
         }
     };
 
+    audit::record(
+        &mut con,
+        NewAuditEvent::new(&user.uuid, AuditAction::StripeAccountLinked)
+            .details(json!({ "stripe_account_id": account_id })),
+    )
+    .await;
+
     HttpResponse::SeeOther()
         .insert_header(("Location", link_result.url))
         .finish()
@@ -1,19 +1,42 @@
+pub mod access_tokens;
+pub mod account;
+pub mod app_invitations;
+pub mod app_owners;
+pub mod app_pricing;
 pub mod apps;
 pub mod create_stripe_account;
+pub mod domain_challenge;
+pub mod earnings;
 pub mod enable_app_payments;
 pub mod link_stripe_account;
 pub mod stripe_account;
+pub mod submissions;
 pub mod submit_app_update;
 
 pub fn dashboard_routes_config(cfg: &mut actix_web::web::ServiceConfig) {
     cfg.service(
         actix_web::web::scope("/api/dashboard")
+            .service(access_tokens::create_access_token)
+            .service(access_tokens::get_access_tokens)
+            .service(access_tokens::revoke_access_token)
+            .service(account::delete_account)
+            .service(account::export_account)
+            .service(app_invitations::accept_app_invitation)
+            .service(app_invitations::invite_app_owner)
+            .service(app_owners::get_app_owners)
+            .service(app_owners::remove_app_owner)
+            .service(app_owners::transfer_app_ownership)
+            .service(app_pricing::set_app_pricing)
             .service(apps::add_app)
             .service(apps::get_apps)
             .service(create_stripe_account::create)
+            .service(domain_challenge::issue_domain_challenge)
+            .service(domain_challenge::check_domain_challenge)
+            .service(earnings::get_earnings)
             .service(enable_app_payments::enable_app_payments)
             .service(link_stripe_account::link)
             .service(stripe_account::get_stripe_account)
+            .service(submissions::get_submissions)
             .service(submit_app_update::submit),
     );
 }
@@ -0,0 +1,107 @@
+use actix_web::{get, web::Data, HttpResponse};
+use common::models::Submission;
+use diesel::{ExpressionMethods, JoinOnDsl, QueryDsl};
+use diesel_async::{
+    pooled_connection::bb8::{Pool, PooledConnection},
+    AsyncPgConnection, RunQueryDsl,
+};
+use uuid::Uuid;
+
+use crate::extractors::AuthedUser;
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/dashboard/submissions",
+    responses(
+        (
+            status = 200,
+            description = "Update submissions for the current user's apps, newest first",
+            body = Vec<Submission>,
+        ),
+    )
+))]
+#[get("/submissions")]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Fetching submissions for dashboard", skip(user, pool))
+)]
+pub async fn get_submissions(
+    user: AuthedUser,
+    pool: Data<Pool<AsyncPgConnection>>,
+) -> HttpResponse {
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Error getting database connection: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    match get_submissions_for_user(&mut con, &user.uuid).await {
+        Ok(s) => HttpResponse::Ok().json(s),
+        Err(e) => {
+            tracing::error!("Error getting submissions for user: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+async fn get_submissions_for_user(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    uuid: &Uuid,
+) -> Result<Vec<Submission>, diesel::result::Error> {
+    use common::schema::{app_owners, submissions};
+
+    submissions::table
+        .inner_join(app_owners::table.on(app_owners::app_id.eq(submissions::app_id)))
+        .filter(app_owners::user_id.eq(uuid))
+        .filter(app_owners::verified_owner.eq(true))
+        .select(submissions::all_columns)
+        .order(submissions::created_at.desc())
+        .load::<Submission>(con)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use common::models::{NewSubmission, SubmissionState};
+    use diesel_async::AsyncConnection;
+
+    use crate::utils::db_test::{create_app, create_user, db_pool};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_get_submissions_for_user() -> anyhow::Result<()> {
+        use common::schema::submissions;
+
+        let db_pool = db_pool().await;
+        let mut con = db_pool.get().await?;
+
+        con.begin_test_transaction().await?;
+
+        let user = create_user(&mut con, true).await?;
+        let owned = create_app(&mut con, Some(&user), None).await?;
+        let other = create_app(&mut con, None, None).await?;
+
+        for app in [&owned, &other] {
+            diesel::insert_into(submissions::table)
+                .values(NewSubmission {
+                    app_id: app,
+                    user_id: None,
+                    version_tag: "1.0.0",
+                    state: SubmissionState::Queued,
+                })
+                .execute(&mut con)
+                .await?;
+        }
+
+        let submissions = get_submissions_for_user(&mut con, &user).await?;
+
+        assert_eq!(submissions.len(), 1);
+        assert_eq!(submissions[0].app_id, owned);
+        assert_eq!(submissions[0].version_tag, "1.0.0");
+        assert_eq!(submissions[0].state, SubmissionState::Queued);
+
+        Ok(())
+    }
+}
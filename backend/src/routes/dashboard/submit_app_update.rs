@@ -1,31 +1,457 @@
-use actix_web::{post, web::Json, HttpResponse};
+use std::time::Duration;
+
+use actix_web::{
+    post,
+    web::{Data, Json},
+    HttpResponse,
+};
 use background_worker::tasks::SubmitAppUpdate;
+use common::models::{AuditAction, NewAuditEvent, NewSubmission, Submission, SubmissionState};
+use common::network::{check_public_https_url, PublicUrlError};
+use diesel::{
+    result::{DatabaseErrorKind, Error},
+    ExpressionMethods, OptionalExtension, QueryDsl,
+};
+use diesel_async::{
+    pooled_connection::bb8::{Pool, PooledConnection},
+    AsyncPgConnection, RunQueryDsl,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+use super::app_owners::{get_app_role, insufficient_role_response};
+use crate::{
+    extractors::AuthedUser,
+    types::{dashboard::AppUpdateSubmission, ErrorResponse, ErrorTranslationKey},
+    utils::{audit, versions::is_newer_version},
+};
 
-use crate::{extractors::AuthedUser, types::dashboard::AppUpdateSubmission};
+/// Upper bound on looking up the submitted tag, on top of the git-worker's own timeouts
+const TAG_LOOKUP_TIMEOUT: Duration = Duration::from_secs(60);
 
 #[cfg_attr(feature = "openapi", utoipa::path(
     path = "/dashboard/submit_app_update",
     request_body = AppUpdateSubmission,
     responses(
-        (status = 200, description = "App update submitted for processing"),
-        (status = 500, description = "Error occurred while submitting app for processing")
+        (status = 200, description = "App update submitted for processing", body = Submission),
+        (status = 400, body = ErrorResponse),
+        (status = 403, body = ErrorResponse),
+        (status = 404, body = ErrorResponse),
+        (status = 500, description = "Error occurred while submitting app for processing"),
+        (status = 502, body = ErrorResponse)
     )
 ))]
 #[post("/submit_app_update")]
 #[cfg_attr(
     not(coverage),
-    tracing::instrument(name = "Submitting app update", skip(user))
+    tracing::instrument(name = "Submitting app update", skip(user, pool))
 )]
-pub async fn submit(user: AuthedUser, submission: Json<AppUpdateSubmission>) -> HttpResponse {
+pub async fn submit(
+    user: AuthedUser,
+    submission: Json<AppUpdateSubmission>,
+    pool: Data<Pool<AsyncPgConnection>>,
+) -> HttpResponse {
+    // Looking up the tag can take a while, so the connection isn't held on to in the meantime
+    let repository = {
+        let mut con = match pool.get().await {
+            Ok(c) => c,
+            Err(e) => {
+                tracing::error!("Unable to get DB connection for app submission: {}", e);
+                return HttpResponse::InternalServerError().finish();
+            }
+        };
+
+        match get_app_role(&mut con, &submission.app_id, &user.uuid).await {
+            Ok(Some(r)) if r.can_submit_updates() => {}
+            Ok(Some(_)) => return insufficient_role_response(),
+            Ok(None) => {
+                return HttpResponse::Forbidden().json(ErrorResponse {
+                    error: "Only verified owners can submit updates of an app".into(),
+                    translation_key: ErrorTranslationKey::AppNotOwned,
+                })
+            }
+            Err(e) => {
+                tracing::error!("Error checking app ownership: {}", e);
+                return HttpResponse::InternalServerError().finish();
+            }
+        }
+
+        let (repository, last_version) = match get_app_versions(&mut con, &submission.app_id).await
+        {
+            Ok(Some(a)) => a,
+            Ok(None) => return app_not_found_response(),
+            Err(e) => {
+                tracing::error!("Error getting app for submission: {}", e);
+                return HttpResponse::InternalServerError().finish();
+            }
+        };
+
+        if let Err(e) = check_version_order(&submission.version_tag, last_version.as_deref()) {
+            return HttpResponse::BadRequest().json(e);
+        }
+
+        repository
+    };
+
+    // Looking up the tag talks to the app's git host, so keep it off the async executor. Apps
+    // added before repositories were checked may point anywhere, so check it again here.
+    let tag = submission.version_tag.to_owned();
+    let lookup = actix_web::web::block(move || {
+        check_public_https_url(&repository).map_err(TagLookupError::Repository)?;
+        git_worker::find_remote_tag(&repository, &tag).map_err(TagLookupError::Git)
+    });
+    match actix_web::rt::time::timeout(TAG_LOOKUP_TIMEOUT, lookup).await {
+        Ok(Ok(Ok(Some(_)))) => {}
+        Ok(Ok(Ok(None))) => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: format!(
+                    "The tag {} couldn't be found in the app's repository",
+                    submission.version_tag
+                ),
+                translation_key: ErrorTranslationKey::SubmitAppUpdateTagNotFound,
+            });
+        }
+        Ok(Ok(Err(TagLookupError::Repository(PublicUrlError::Unresolvable(_, e))))) => {
+            tracing::warn!("Unable to resolve host of app repository: {}", e);
+            return repository_unreachable_response();
+        }
+        Ok(Ok(Err(TagLookupError::Repository(e)))) => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: format!(
+                    "The app's repository must be a public https URL, please add the app again: {}",
+                    e
+                ),
+                translation_key: ErrorTranslationKey::SubmitAppUpdateRepositoryNotAllowed,
+            });
+        }
+        Ok(Ok(Err(TagLookupError::Git(e)))) => {
+            tracing::warn!("Unable to read tags of app repository: {}", e);
+            return repository_unreachable_response();
+        }
+        Ok(Err(e)) => {
+            tracing::error!("Error looking up tag of submitted version: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+        Err(_) => {
+            tracing::warn!("Timed out reading tags of app repository");
+            return repository_unreachable_response();
+        }
+    }
+
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for app submission: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let record = match create_submission(&mut con, &submission, &user.uuid).await {
+        Ok(s) => s,
+        Err(Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _)) => {
+            return app_not_found_response()
+        }
+        Err(e) => {
+            tracing::error!("Error recording app submission: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
     let task = SubmitAppUpdate::new(
         submission.app_id.to_owned(),
         submission.version_tag.to_owned(),
         user.uuid,
+        record.id,
     );
 
-    if background_worker::insert_task(&task).is_err() {
+    if let Err(e) = background_worker::insert_task(&task) {
+        tracing::error!("Error queueing app submission: {}", e);
+
+        if let Err(e) = mark_submission_failed(&mut con, &record.id, "Unable to queue update").await
+        {
+            tracing::error!("Error updating submission state: {}", e);
+        }
+
         return HttpResponse::InternalServerError().finish();
     }
 
-    HttpResponse::Ok().finish()
+    audit::record(
+        &mut con,
+        NewAuditEvent::new(&user.uuid, AuditAction::UpdateSubmitted)
+            .app(&submission.app_id)
+            .details(json!({
+                "submission_id": record.id,
+                "version_tag": submission.version_tag,
+            })),
+    )
+    .await;
+
+    HttpResponse::Ok().json(record)
+}
+
+enum TagLookupError {
+    Repository(PublicUrlError),
+    Git(anyhow::Error),
+}
+
+fn app_not_found_response() -> HttpResponse {
+    HttpResponse::NotFound().json(ErrorResponse {
+        error: "Specified app ID was not found".into(),
+        translation_key: ErrorTranslationKey::AppNotFound,
+    })
+}
+
+fn repository_unreachable_response() -> HttpResponse {
+    HttpResponse::BadGateway().json(ErrorResponse {
+        error: "The app's repository couldn't be reached. Please try again later.".into(),
+        translation_key: ErrorTranslationKey::SubmitAppUpdateRepositoryUnreachable,
+    })
+}
+
+/// Checks the submitted tag is a version newer than the last one submitted. Tags that aren't
+/// semantic versions, like `1.2`, can't be compared, so they're accepted as they are.
+fn check_version_order(tag: &str, last_version: Option<&str>) -> Result<(), ErrorResponse> {
+    match last_version {
+        Some(last) if !is_newer_version(tag, Some(last)) => Err(ErrorResponse {
+            error: format!(
+                "The version {} isn't newer than the last submitted version {}",
+                tag, last
+            ),
+            translation_key: ErrorTranslationKey::SubmitAppUpdateVersionNotNewer,
+        }),
+        _ => Ok(()),
+    }
+}
+
+async fn get_app_versions(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    app_id: &str,
+) -> Result<Option<(String, Option<String>)>, Error> {
+    use common::schema::apps;
+
+    apps::table
+        .filter(apps::id.eq(app_id))
+        .select((apps::repository, apps::last_submitted_version))
+        .get_result::<(String, Option<String>)>(con)
+        .await
+        .optional()
+}
+
+async fn create_submission(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    submission: &AppUpdateSubmission,
+    uuid: &Uuid,
+) -> Result<Submission, Error> {
+    use common::schema::submissions;
+
+    diesel::insert_into(submissions::table)
+        .values(NewSubmission {
+            app_id: &submission.app_id,
+            user_id: Some(uuid),
+            version_tag: &submission.version_tag,
+            state: SubmissionState::Queued,
+        })
+        .get_result::<Submission>(con)
+        .await
+}
+
+async fn mark_submission_failed(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    submission_id: &Uuid,
+    message: &str,
+) -> Result<usize, Error> {
+    use common::schema::submissions::dsl::*;
+
+    diesel::update(submissions.filter(id.eq(submission_id)))
+        .set((state.eq(SubmissionState::Failed), error.eq(message)))
+        .execute(con)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{
+        http::{header, StatusCode},
+        test::TestRequest,
+        App,
+    };
+    use common::models::{AppOwnerRole, TokenScope};
+    use diesel_async::AsyncConnection;
+
+    use crate::utils::db_test::{
+        create_access_token, create_app, create_user, db_pool, test_transaction_pool,
+    };
+
+    use super::*;
+
+    fn rejection(tag: &str, last_version: Option<&str>) -> Option<ErrorTranslationKey> {
+        check_version_order(tag, last_version)
+            .err()
+            .map(|e| e.translation_key)
+    }
+
+    #[test]
+    fn test_check_version_order() {
+        assert_eq!(rejection("1.0.0", None), None);
+        assert_eq!(rejection("v1.2.0", Some("1.1.9")), None);
+        assert_eq!(rejection("1.10.0", Some("v1.9.0")), None);
+        assert_eq!(rejection("2.0.0", Some("not-a-version")), None);
+        assert_eq!(rejection("latest", None), None);
+        assert_eq!(rejection("1.2", None), None);
+        assert_eq!(rejection("1.2", Some("1.0.0")), None);
+
+        assert_eq!(
+            rejection("1.0.0", Some("1.0.0")),
+            Some(ErrorTranslationKey::SubmitAppUpdateVersionNotNewer)
+        );
+        assert_eq!(
+            rejection("1.0.0-beta.1", Some("1.0.0")),
+            Some(ErrorTranslationKey::SubmitAppUpdateVersionNotNewer)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_app_versions() -> anyhow::Result<()> {
+        use common::schema::apps;
+
+        let db_pool = db_pool().await;
+        let mut con = db_pool.get().await?;
+
+        con.begin_test_transaction().await?;
+
+        let owner = create_user(&mut con, true).await?;
+        let app = create_app(&mut con, Some(&owner), None).await?;
+
+        diesel::update(apps::table.filter(apps::id.eq(&app)))
+            .set(apps::last_submitted_version.eq("1.2.3"))
+            .execute(&mut con)
+            .await?;
+
+        let (_, last_version) = get_app_versions(&mut con, &app).await?.unwrap();
+        assert_eq!(last_version.as_deref(), Some("1.2.3"));
+        assert_eq!(
+            get_app_versions(&mut con, "com.example.missing").await?,
+            None
+        );
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_submit_rejections() -> anyhow::Result<()> {
+        use common::schema::{app_owners, apps};
+
+        let pool = test_transaction_pool().await;
+        let server = actix_web::test::init_service(
+            App::new()
+                .app_data(Data::new(pool.clone()))
+                .configure(crate::routes::dashboard_routes_config),
+        )
+        .await;
+
+        let (app, owner, finance, stranger) = {
+            let mut con = pool.get().await?;
+
+            let owner = create_user(&mut con, true).await?;
+            let finance = create_user(&mut con, true).await?;
+            let stranger = create_user(&mut con, true).await?;
+            let app = create_app(&mut con, Some(&owner), None).await?;
+
+            diesel::update(apps::table.filter(apps::id.eq(&app)))
+                .set((
+                    apps::repository.eq("file:///srv/git/app.git"),
+                    apps::last_submitted_version.eq("1.2.3"),
+                ))
+                .execute(&mut con)
+                .await?;
+
+            diesel::insert_into(app_owners::table)
+                .values((
+                    app_owners::user_id.eq(finance),
+                    app_owners::app_id.eq(&app),
+                    app_owners::verified_owner.eq(true),
+                    app_owners::role.eq(AppOwnerRole::Finance),
+                ))
+                .execute(&mut con)
+                .await?;
+
+            (
+                app,
+                create_access_token(&mut con, &owner, &[TokenScope::Submit]).await?,
+                create_access_token(&mut con, &finance, &[TokenScope::Submit]).await?,
+                create_access_token(&mut con, &stranger, &[TokenScope::Submit]).await?,
+            )
+        };
+
+        let submission = |token: &str, version_tag: &str| {
+            TestRequest::post()
+                .uri("/api/dashboard/submit_app_update")
+                .insert_header((header::AUTHORIZATION, format!("Bearer {}", token)))
+                .set_json(json!({ "app_id": app, "version_tag": version_tag }))
+                .to_request()
+        };
+
+        let cases = [
+            (
+                &stranger,
+                "2.0.0",
+                StatusCode::FORBIDDEN,
+                "generic.app-not-owned",
+            ),
+            (
+                &finance,
+                "2.0.0",
+                StatusCode::FORBIDDEN,
+                "generic.insufficient-app-role",
+            ),
+            (
+                &owner,
+                "1.2.3",
+                StatusCode::BAD_REQUEST,
+                "submit-app-update.version-not-newer",
+            ),
+            (
+                &owner,
+                "2.0.0",
+                StatusCode::BAD_REQUEST,
+                "submit-app-update.repository-not-allowed",
+            ),
+        ];
+
+        for (token, version_tag, status, translation_key) in cases {
+            let response =
+                actix_web::test::call_service(&server, submission(token, version_tag)).await;
+            assert_eq!(response.status(), status, "submitting {}", version_tag);
+
+            let body: serde_json::Value = actix_web::test::read_body_json(response).await;
+            assert_eq!(body["translation_key"], translation_key);
+        }
+
+        let cases = [
+            ("http://127.0.0.1:9/missing.git", StatusCode::BAD_REQUEST),
+            ("https://169.254.169.254/latest", StatusCode::BAD_REQUEST),
+            ("https://localhost/missing.git", StatusCode::BAD_REQUEST),
+            // Failing to reach the repository isn't the submitter's fault
+            (
+                "https://repository.invalid/missing.git",
+                StatusCode::BAD_GATEWAY,
+            ),
+        ];
+
+        for (repository, status) in cases {
+            {
+                let mut con = pool.get().await?;
+                diesel::update(apps::table.filter(apps::id.eq(&app)))
+                    .set(apps::repository.eq(repository))
+                    .execute(&mut con)
+                    .await?;
+            }
+
+            let response =
+                actix_web::test::call_service(&server, submission(&owner, "2.0.0")).await;
+            assert_eq!(response.status(), status, "fetching {}", repository);
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,5 @@
+pub mod webhook;
+
+pub fn github_routes_config(cfg: &mut actix_web::web::ServiceConfig) {
+    cfg.service(actix_web::web::scope("/api/github").service(webhook::webhook));
+}
@@ -0,0 +1,547 @@
+use actix_web::{
+    post,
+    web::{Bytes, Data},
+    HttpRequest, HttpResponse,
+};
+use common::models::SubmissionState;
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::{
+    pooled_connection::bb8::{Pool, PooledConnection},
+    AsyncPgConnection, RunQueryDsl,
+};
+use secrecy::ExposeSecret;
+use serde::Deserialize;
+
+use crate::{
+    types::{ErrorResponse, ErrorTranslationKey},
+    utils::{versions::is_newer_version, webhooks::verify_github_signature},
+};
+
+const GITHUB_EVENT_HEADER: &str = "X-GitHub-Event";
+const GITHUB_SIGNATURE_HEADER: &str = "X-Hub-Signature-256";
+
+/// The fields shared by `pull_request` and `pull_request_review` events that we need to
+/// find and update a submission
+#[derive(Deserialize)]
+struct PullRequestEvent {
+    action: String,
+    pull_request: PullRequest,
+    repository: Repository,
+    /// Only sent with `pull_request_review` events
+    review: Option<Review>,
+}
+
+#[derive(Deserialize)]
+struct PullRequest {
+    number: i32,
+    #[serde(default)]
+    merged: bool,
+    head: PullRequestHead,
+}
+
+#[derive(Deserialize)]
+struct PullRequestHead {
+    #[serde(rename = "ref")]
+    branch: String,
+}
+
+#[derive(Deserialize)]
+struct Repository {
+    clone_url: String,
+}
+
+#[derive(Deserialize)]
+struct Review {
+    state: String,
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/github/webhook",
+    operation_id = "github_webhook",
+    request_body(content = String, description = "GitHub event, as signed in the `X-Hub-Signature-256` header"),
+    params(
+        ("X-GitHub-Event" = String, Header, description = "Type of the event"),
+        ("X-Hub-Signature-256" = String, Header, description = "Signature of the payload using the webhook secret"),
+    ),
+    responses(
+        (status = 200, description = "The event was processed or ignored"),
+        (status = 400, body = ErrorResponse),
+        (status = 500, description = "The event couldn't be processed"),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Handling a GitHub webhook", skip(req, body, pool))
+)]
+#[post("/webhook")]
+pub async fn webhook(
+    req: HttpRequest,
+    body: Bytes,
+    pool: Data<Pool<AsyncPgConnection>>,
+) -> HttpResponse {
+    let settings = common::settings::get_settings().expect("Failed to read settings.");
+
+    if settings.github.webhook_secret.expose_secret().is_empty() {
+        tracing::error!("Received a GitHub webhook, but no webhook secret is configured");
+        return HttpResponse::InternalServerError().finish();
+    }
+
+    let header = |name| {
+        req.headers()
+            .get(name)
+            .and_then(|h| h.to_str().ok())
+            .unwrap_or_default()
+    };
+
+    if let Err(e) = verify_github_signature(
+        &body,
+        header(GITHUB_SIGNATURE_HEADER),
+        settings.github.webhook_secret.expose_secret(),
+    ) {
+        tracing::warn!("Rejected GitHub webhook: {}", e);
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "The webhook signature could not be verified".into(),
+            translation_key: ErrorTranslationKey::InvalidWebhookSignature,
+        });
+    }
+
+    let kind = header(GITHUB_EVENT_HEADER);
+    if kind != "pull_request" && kind != "pull_request_review" {
+        tracing::debug!("Ignoring GitHub event of type {}", kind);
+        return HttpResponse::Ok().finish();
+    }
+
+    let event = match serde_json::from_slice::<PullRequestEvent>(&body) {
+        Ok(e) => e,
+        Err(e) => {
+            tracing::error!("Unable to deserialize GitHub event: {}", e);
+            return HttpResponse::BadRequest().finish();
+        }
+    };
+
+    if !same_repository(&event.repository.clone_url, &settings.github.reviews_url) {
+        tracing::debug!(
+            "Ignoring GitHub event for repository {}",
+            event.repository.clone_url
+        );
+        return HttpResponse::Ok().finish();
+    }
+
+    let Some((state, from)) = submission_transition(kind, &event) else {
+        return HttpResponse::Ok().finish();
+    };
+
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for GitHub webhook: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    match update_submissions(&mut con, &event.pull_request, state, from).await {
+        Ok(_) => HttpResponse::Ok().finish(),
+        Err(e) => {
+            tracing::error!(
+                "Error updating submissions for PR #{}: {}",
+                event.pull_request.number,
+                e
+            );
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+/// Compares repository URLs, ignoring the optional `.git` suffix of clone URLs
+fn same_repository(a: &str, b: &str) -> bool {
+    let normalize = |url: &str| {
+        url.trim_end_matches('/')
+            .trim_end_matches(".git")
+            .to_lowercase()
+    };
+    normalize(a) == normalize(b)
+}
+
+/// Works out which state an event moves a submission to, and which states it can be moved from
+fn submission_transition(
+    kind: &str,
+    event: &PullRequestEvent,
+) -> Option<(SubmissionState, &'static [SubmissionState])> {
+    use SubmissionState::*;
+
+    // Merging or closing a PR only moves on submissions whose PR is open, never failed ones
+    const OPEN: &[SubmissionState] = &[PrOpen, ChangesRequested];
+
+    match (kind, event.action.as_str()) {
+        ("pull_request", "closed") if event.pull_request.merged => Some((Merged, OPEN)),
+        ("pull_request", "closed") => Some((Closed, OPEN)),
+        ("pull_request", "reopened") => Some((PrOpen, &[Closed])),
+        ("pull_request_review", "submitted") => match event.review.as_ref()?.state.as_str() {
+            "changes_requested" => Some((ChangesRequested, OPEN)),
+            "approved" => Some((PrOpen, &[ChangesRequested])),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+async fn update_submissions(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    pull_request: &PullRequest,
+    new_state: SubmissionState,
+    from: &[SubmissionState],
+) -> anyhow::Result<()> {
+    use common::schema::{apps, submissions::dsl::*};
+
+    let updated = diesel::update(
+        submissions
+            .filter(branch_name.eq(&pull_request.head.branch))
+            .filter(pr_number.eq(pull_request.number))
+            .filter(state.eq_any(from)),
+    )
+    .set(state.eq(new_state))
+    .returning((app_id, version_tag))
+    .get_results::<(String, String)>(con)
+    .await?;
+
+    if updated.is_empty() {
+        tracing::debug!(
+            "No submission to update for PR #{} on {}",
+            pull_request.number,
+            pull_request.head.branch
+        );
+    }
+
+    // Publish the version straight away rather than waiting for the next poll of the repo
+    if new_state == SubmissionState::Merged {
+        for (app, version) in updated {
+            let last_version = apps::table
+                .filter(apps::id.eq(&app))
+                .select(apps::last_submitted_version)
+                .get_result::<Option<String>>(con)
+                .await?;
+
+            // An older version can be merged after a newer one, e.g. as a backport
+            if !is_newer_version(&version, last_version.as_deref()) {
+                continue;
+            }
+
+            diesel::update(apps::table.filter(apps::id.eq(app)))
+                .set((
+                    apps::last_submitted_version.eq(version),
+                    apps::last_update.eq(time::OffsetDateTime::now_utc()),
+                ))
+                .execute(con)
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{test::TestRequest, App};
+    use common::models::{NewSubmission, Submission};
+
+    use crate::utils::{
+        db_test::{create_app, db_pool, test_transaction_pool},
+        webhooks::github_signature_header,
+    };
+
+    use super::*;
+
+    const PULL_REQUEST_CLOSED: &str = include_str!("fixtures/pull_request_closed.json");
+    const PULL_REQUEST_REVIEW_SUBMITTED: &str =
+        include_str!("fixtures/pull_request_review_submitted.json");
+
+    fn fixture(raw: &str, branch: &str, number: i32) -> serde_json::Value {
+        let settings = common::settings::get_settings().expect("Failed to read settings.");
+
+        let mut event: serde_json::Value = serde_json::from_str(raw).expect("Invalid fixture");
+        event["number"] = number.into();
+        event["pull_request"]["number"] = number.into();
+        event["pull_request"]["head"]["ref"] = branch.into();
+        event["repository"]["clone_url"] = settings.github.reviews_url.into();
+
+        event
+    }
+
+    fn signed_request(kind: &str, payload: &serde_json::Value, secret: &str) -> TestRequest {
+        let payload = serde_json::to_vec(payload).expect("Unable to serialize fixture");
+
+        TestRequest::post()
+            .uri("/webhook")
+            .insert_header((GITHUB_EVENT_HEADER, kind))
+            .insert_header((
+                GITHUB_SIGNATURE_HEADER,
+                github_signature_header(&payload, secret),
+            ))
+            .set_payload(payload)
+    }
+
+    async fn submission_state(
+        pool: &Pool<AsyncPgConnection>,
+        submission: &uuid::Uuid,
+    ) -> Option<SubmissionState> {
+        use common::schema::submissions::dsl::*;
+
+        let mut con = pool.get().await.expect("Unable to get pool connection");
+        submissions
+            .filter(id.eq(submission))
+            .select(state)
+            .get_result::<SubmissionState>(&mut con)
+            .await
+            .ok()
+    }
+
+    #[test]
+    fn test_same_repository() {
+        assert!(same_repository(
+            "https://github.com/elementary/appcenter-reviews.git",
+            "https://github.com/elementary/appcenter-reviews"
+        ));
+        assert!(same_repository(
+            "https://github.com/Elementary/AppCenter-Reviews.git",
+            "https://github.com/elementary/appcenter-reviews.git"
+        ));
+        assert!(!same_repository(
+            "https://github.com/elementary/appcenter-reviews.git",
+            "https://github.com/someone/appcenter-reviews.git"
+        ));
+    }
+
+    #[actix_web::test]
+    async fn test_review_and_merge() -> Result<(), actix_web::Error> {
+        use common::schema::{apps, submissions};
+
+        let settings = common::settings::get_settings().expect("Failed to read settings.");
+        let secret = settings.github.webhook_secret.expose_secret();
+
+        let pool = test_transaction_pool().await;
+        let mut server = actix_web::test::init_service(
+            App::new()
+                .service(webhook)
+                .app_data(Data::new(pool.clone())),
+        )
+        .await;
+
+        let (app, branch, submission, failed) = {
+            let mut con = pool.get().await.expect("Unable to get pool connection");
+            let app = create_app(&mut con, None, None)
+                .await
+                .expect("Unable to create test app");
+            let branch = format!("appcenter-website/{}-1.0.0", app);
+
+            let mut created = vec![];
+            for initial_state in [SubmissionState::PrOpen, SubmissionState::Failed] {
+                let submission = diesel::insert_into(submissions::table)
+                    .values(NewSubmission {
+                        app_id: &app,
+                        user_id: None,
+                        version_tag: "1.0.0",
+                        state: initial_state,
+                    })
+                    .get_result::<Submission>(&mut con)
+                    .await
+                    .expect("Unable to create test submission");
+
+                diesel::update(submissions::table.filter(submissions::id.eq(submission.id)))
+                    .set((
+                        submissions::branch_name.eq(&branch),
+                        submissions::pr_number.eq(1234),
+                    ))
+                    .execute(&mut con)
+                    .await
+                    .expect("Unable to update test submission");
+
+                created.push(submission.id);
+            }
+
+            (app, branch, created[0], created[1])
+        };
+
+        // A PR with another number on the same branch is from an earlier submission
+        let response = actix_web::test::call_service(
+            &mut server,
+            signed_request(
+                "pull_request_review",
+                &fixture(PULL_REQUEST_REVIEW_SUBMITTED, &branch, 1000),
+                secret,
+            )
+            .to_request(),
+        )
+        .await;
+        assert!(response.status().is_success());
+        assert_eq!(
+            submission_state(&pool, &submission).await,
+            Some(SubmissionState::PrOpen)
+        );
+
+        let response = actix_web::test::call_service(
+            &mut server,
+            signed_request(
+                "pull_request_review",
+                &fixture(PULL_REQUEST_REVIEW_SUBMITTED, &branch, 1234),
+                secret,
+            )
+            .to_request(),
+        )
+        .await;
+        assert!(response.status().is_success());
+        assert_eq!(
+            submission_state(&pool, &submission).await,
+            Some(SubmissionState::ChangesRequested)
+        );
+
+        let response = actix_web::test::call_service(
+            &mut server,
+            signed_request(
+                "pull_request",
+                &fixture(PULL_REQUEST_CLOSED, &branch, 1234),
+                secret,
+            )
+            .to_request(),
+        )
+        .await;
+        assert!(response.status().is_success());
+        assert_eq!(
+            submission_state(&pool, &submission).await,
+            Some(SubmissionState::Merged)
+        );
+        assert_eq!(
+            submission_state(&pool, &failed).await,
+            Some(SubmissionState::Failed)
+        );
+
+        let version = {
+            let mut con = pool.get().await.expect("Unable to get pool connection");
+            apps::table
+                .filter(apps::id.eq(&app))
+                .select(apps::last_submitted_version)
+                .get_result::<Option<String>>(&mut con)
+                .await
+                .expect("App disappeared")
+        };
+        assert_eq!(version.as_deref(), Some("1.0.0"));
+
+        // Reviews arriving late mustn't move a merged submission back
+        let response = actix_web::test::call_service(
+            &mut server,
+            signed_request(
+                "pull_request_review",
+                &fixture(PULL_REQUEST_REVIEW_SUBMITTED, &branch, 1234),
+                secret,
+            )
+            .to_request(),
+        )
+        .await;
+        assert!(response.status().is_success());
+        assert_eq!(
+            submission_state(&pool, &submission).await,
+            Some(SubmissionState::Merged)
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_merging_older_version() -> anyhow::Result<()> {
+        use common::schema::{apps, submissions};
+        use diesel_async::AsyncConnection;
+
+        let db_pool = db_pool().await;
+        let mut con = db_pool.get().await?;
+
+        con.begin_test_transaction().await?;
+
+        let app = create_app(&mut con, None, None).await?;
+        let branch = format!("appcenter-website/{}-1.0.1", app);
+
+        diesel::update(apps::table.filter(apps::id.eq(&app)))
+            .set(apps::last_submitted_version.eq("1.1.0"))
+            .execute(&mut con)
+            .await?;
+
+        let submission = diesel::insert_into(submissions::table)
+            .values(NewSubmission {
+                app_id: &app,
+                user_id: None,
+                version_tag: "1.0.1",
+                state: SubmissionState::PrOpen,
+            })
+            .get_result::<Submission>(&mut con)
+            .await?;
+
+        diesel::update(submissions::table.filter(submissions::id.eq(submission.id)))
+            .set((
+                submissions::branch_name.eq(&branch),
+                submissions::pr_number.eq(1234),
+            ))
+            .execute(&mut con)
+            .await?;
+
+        let pull_request = PullRequest {
+            number: 1234,
+            merged: true,
+            head: PullRequestHead { branch },
+        };
+
+        update_submissions(
+            &mut con,
+            &pull_request,
+            SubmissionState::Merged,
+            &[SubmissionState::PrOpen],
+        )
+        .await?;
+
+        let (state, version) = submissions::table
+            .inner_join(apps::table)
+            .filter(submissions::id.eq(submission.id))
+            .select((submissions::state, apps::last_submitted_version))
+            .get_result::<(SubmissionState, Option<String>)>(&mut con)
+            .await?;
+
+        assert_eq!(state, SubmissionState::Merged);
+        assert_eq!(version.as_deref(), Some("1.1.0"));
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_invalid_signature() -> Result<(), actix_web::Error> {
+        let pool = db_pool().await;
+        let mut server = actix_web::test::init_service(
+            App::new()
+                .service(webhook)
+                .app_data(Data::new(pool.clone())),
+        )
+        .await;
+
+        let event = fixture(
+            PULL_REQUEST_CLOSED,
+            "appcenter-website/com.example.app-1.0.0",
+            1,
+        );
+
+        let response = actix_web::test::call_service(
+            &mut server,
+            signed_request("pull_request", &event, "wrong").to_request(),
+        )
+        .await;
+        assert_eq!(response.status(), actix_web::http::StatusCode::BAD_REQUEST);
+
+        let response = actix_web::test::call_service(
+            &mut server,
+            TestRequest::post()
+                .uri("/webhook")
+                .insert_header((GITHUB_EVENT_HEADER, "pull_request"))
+                .set_payload(PULL_REQUEST_CLOSED)
+                .to_request(),
+        )
+        .await;
+        assert_eq!(response.status(), actix_web::http::StatusCode::BAD_REQUEST);
+
+        Ok(())
+    }
+}
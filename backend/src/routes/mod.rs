@@ -1,11 +1,15 @@
+pub mod admin;
 mod apps;
 pub mod dashboard;
+pub mod github;
 mod health;
 pub mod payments;
 pub mod users;
 
+pub use admin::admin_routes_config;
 pub use apps::apps_routes_config;
 pub use dashboard::dashboard_routes_config;
+pub use github::github_routes_config;
 pub use health::health_check;
 pub use payments::payments_routes_config;
 pub use users::auth_routes_config;
@@ -16,34 +20,114 @@ pub use users::auth_routes_config;
     openapi(
         paths(
             users::test_auth::test_auth,
+            admin::app_fee::set_app_fee,
+            admin::apps::publish_app,
+            admin::apps::set_app_verification,
+            admin::apps::set_owner_verification,
+            admin::apps::unpublish_app,
+            admin::audit_events::list_audit_events,
+            admin::users::deactivate_user,
+            admin::users::list_users,
+            admin::users::reactivate_user,
             apps::all_ids::all_ids,
+            apps::categories::categories,
+            apps::categories::category,
+            apps::details::details,
             apps::get::get,
             apps::recently_added::recently_added,
             apps::recently_updated::recently_updated,
+            apps::search::search,
+            dashboard::access_tokens::create_access_token,
+            dashboard::access_tokens::get_access_tokens,
+            dashboard::access_tokens::revoke_access_token,
+            dashboard::account::delete_account,
+            dashboard::account::export_account,
+            dashboard::app_invitations::accept_app_invitation,
+            dashboard::app_invitations::invite_app_owner,
+            dashboard::app_owners::get_app_owners,
+            dashboard::app_owners::remove_app_owner,
+            dashboard::app_owners::transfer_app_ownership,
+            dashboard::app_pricing::set_app_pricing,
             dashboard::apps::add_app,
             dashboard::apps::get_apps,
             dashboard::create_stripe_account::create,
+            dashboard::domain_challenge::check_domain_challenge,
+            dashboard::domain_challenge::issue_domain_challenge,
+            dashboard::earnings::get_earnings,
             dashboard::enable_app_payments::enable_app_payments,
             dashboard::link_stripe_account::link,
             dashboard::stripe_account::get_stripe_account,
+            dashboard::submissions::get_submissions,
             dashboard::submit_app_update::submit,
+            github::webhook::webhook,
+            payments::session::session,
             payments::start::start,
+            payments::webhook::webhook,
             users::confirm_registration::confirm,
+            users::forge_callback::forge_callback,
+            users::forge_login::forge_login,
             users::github_callback::github_callback,
             users::github_login::github_login,
             users::login::login_user,
+            users::password_reset::reset_password,
+            users::password_reset_request::request_password_reset,
+            users::resend_verification::resend_verification,
         ),
         components(schemas(
+            common::models::AccessToken,
             common::models::App,
+            common::models::AppOwnerRole,
+            common::models::AuditAction,
+            common::models::AuditEvent,
             common::models::ComponentSummary,
+            common::models::CategorySummary,
+            common::models::CategoryApps,
+            common::models::AppDetails,
+            common::models::AppUrl,
+            common::models::UrlKind,
+            common::models::ContentRating,
+            common::models::Release,
+            common::models::Screenshot,
+            common::models::ScreenshotImage,
             common::models::TranslatableString,
             common::models::Icon,
             common::models::StripeAccount,
+            common::models::Submission,
+            common::models::SubmissionState,
+            common::models::TokenScope,
             crate::types::general::ErrorResponse,
             crate::types::general::ErrorTranslationKey,
+            crate::types::pagination::AppIdPage,
+            crate::types::pagination::AuditEventPage,
+            crate::types::pagination::ComponentSummaryPage,
+            crate::types::pagination::UserPage,
+            crate::types::admin::Verification,
+            crate::types::UserVisible,
+            crate::types::dashboard::AcceptAppInvitation,
+            crate::types::dashboard::AppCoOwner,
+            crate::types::dashboard::AppEarnings,
+            crate::types::dashboard::AppFeeOverride,
+            crate::types::dashboard::AppOwnerInvitation,
+            crate::types::dashboard::AppOwnershipTransfer,
+            crate::types::dashboard::AppPricing,
+            crate::types::dashboard::CreateAccessToken,
             crate::types::dashboard::CreateApp,
+            crate::types::dashboard::CreatedAccessToken,
+            crate::types::dashboard::DomainChallenge,
+            dashboard::account::AccountExport,
+            dashboard::account::ExportedAppOwnership,
+            dashboard::account::ExportedDomainChallenge,
+            dashboard::account::ExportedForgeAuth,
+            dashboard::account::ExportedGithubAuth,
+            dashboard::account::ExportedInvitation,
+            dashboard::account::ExportedProfile,
+            dashboard::account::ExportedUser,
             crate::types::dashboard::AppUpdateSubmission,
+            crate::types::payments::PaymentReceipt,
             users::login::LoginUser,
+            users::password_reset::PasswordReset,
+            users::password_reset_request::PasswordResetRequest,
+            users::resend_verification::ResendVerificationRequest,
         ))
     )
 )]
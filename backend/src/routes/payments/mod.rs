@@ -1,5 +1,12 @@
+pub mod session;
 pub mod start;
+pub mod webhook;
 
 pub fn payments_routes_config(cfg: &mut actix_web::web::ServiceConfig) {
-    cfg.service(actix_web::web::scope("/api/payments").service(start::start));
+    cfg.service(
+        actix_web::web::scope("/api/payments")
+            .service(session::session)
+            .service(start::start)
+            .service(webhook::webhook),
+    );
 }
@@ -0,0 +1,212 @@
+use actix_web::{
+    get,
+    web::{Data, Path},
+    HttpResponse,
+};
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl};
+use diesel_async::{
+    pooled_connection::bb8::{Pool, PooledConnection},
+    AsyncPgConnection, RunQueryDsl,
+};
+use stripe::{CheckoutSession, CheckoutSessionId, CheckoutSessionPaymentStatus, Client};
+
+use common::models::Payment;
+
+use crate::types::{payments::PaymentReceipt, ErrorResponse, ErrorTranslationKey};
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/payments/session/{id}",
+    params(
+        ("id" = String, Path, description = "ID of the Stripe checkout session, as passed to the success URL"),
+    ),
+    responses(
+        (status = 200, description = "Receipt for the checkout session", body = PaymentReceipt),
+        (status = 404, body = ErrorResponse),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Getting a payment receipt", skip(pool, stripe_client))
+)]
+#[get("/session/{id}")]
+pub async fn session(
+    path: Path<(String,)>,
+    pool: Data<Pool<AsyncPgConnection>>,
+    stripe_client: Data<Client>,
+) -> HttpResponse {
+    let session_id = path.into_inner().0;
+
+    let not_found = || {
+        HttpResponse::NotFound().json(ErrorResponse {
+            error: "Specified payment was not found".into(),
+            translation_key: ErrorTranslationKey::PaymentNotFound,
+        })
+    };
+
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for payment receipt: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    match get_payment(&mut con, &session_id).await {
+        Ok(Some(payment)) => return HttpResponse::Ok().json(receipt_from_payment(payment)),
+        Ok(None) => {}
+        Err(e) => {
+            tracing::error!("Error getting payment from db: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    // The buyer is usually redirected before the webhook has recorded the payment,
+    // so fall back to asking Stripe about the session
+    let stripe_session_id = match session_id.parse::<CheckoutSessionId>() {
+        Ok(i) => i,
+        Err(_) => return not_found(),
+    };
+
+    match CheckoutSession::retrieve(&stripe_client, &stripe_session_id, &[]).await {
+        Ok(s) => HttpResponse::Ok().json(receipt_from_session(s)),
+        Err(stripe::StripeError::Stripe(e)) if e.http_status == 404 => not_found(),
+        Err(e) => {
+            tracing::error!("Error retrieving checkout session from Stripe: {}", e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+async fn get_payment(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    session_id: &str,
+) -> Result<Option<Payment>, diesel::result::Error> {
+    use common::schema::payments::dsl::*;
+
+    payments
+        .filter(checkout_session_id.eq(session_id))
+        .first::<Payment>(con)
+        .await
+        .optional()
+}
+
+fn receipt_from_payment(payment: Payment) -> PaymentReceipt {
+    PaymentReceipt {
+        checkout_session_id: payment.checkout_session_id,
+        app_id: payment.app_id,
+        amount: payment.amount,
+        currency: payment.currency,
+        amount_refunded: payment.amount_refunded,
+        status: payment.status,
+    }
+}
+
+fn receipt_from_session(checkout: CheckoutSession) -> PaymentReceipt {
+    PaymentReceipt {
+        checkout_session_id: checkout.id.to_string(),
+        app_id: checkout
+            .metadata
+            .and_then(|m| m.get("app_id").map(|a| a.to_owned())),
+        amount: checkout.amount_total.unwrap_or_default(),
+        currency: checkout
+            .currency
+            .map(|c| c.to_string())
+            .unwrap_or_else(|| "usd".into()),
+        amount_refunded: 0,
+        status: match checkout.payment_status {
+            CheckoutSessionPaymentStatus::Paid => "paid",
+            _ => "pending",
+        }
+        .into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{test::TestRequest, App};
+    use common::models::NewPayment;
+
+    use crate::utils::{
+        db_test::{create_app, db_pool},
+        stripe_test,
+    };
+
+    use super::*;
+
+    #[actix_web::test]
+    async fn test_recorded_receipt() -> Result<(), actix_web::Error> {
+        use common::schema::{apps, payments};
+
+        let pool = db_pool().await;
+        let mut server = actix_web::test::init_service(
+            App::new()
+                .service(session)
+                .app_data(Data::new(stripe_test::stripe_client()))
+                .app_data(Data::new(pool.clone())),
+        )
+        .await;
+
+        let mut con = pool.get().await.expect("Unable to get pool connection");
+        let app = create_app(&mut con, None, Some("acct_1234"))
+            .await
+            .expect("Unable to create test app");
+        let session_id = format!("cs_test_{}", app.replace('.', "_"));
+
+        diesel::insert_into(payments::table)
+            .values(NewPayment {
+                checkout_session_id: &session_id,
+                payment_intent_id: None,
+                app_id: Some(&app),
+                amount: 500,
+                currency: "eur",
+                platform_fee: 150,
+                status: "paid",
+            })
+            .execute(&mut con)
+            .await
+            .expect("Unable to insert test payment");
+
+        let req = TestRequest::get()
+            .uri(&format!("/session/{}", session_id))
+            .to_request();
+        let receipt: serde_json::Value =
+            actix_web::test::call_and_read_body_json(&mut server, req).await;
+
+        diesel::delete(payments::table.filter(payments::checkout_session_id.eq(&session_id)))
+            .execute(&mut con)
+            .await
+            .ok();
+        diesel::delete(apps::table.filter(apps::id.eq(&app)))
+            .execute(&mut con)
+            .await
+            .ok();
+
+        assert_eq!(receipt["app_id"], app);
+        assert_eq!(receipt["amount"], 500);
+        assert_eq!(receipt["currency"], "eur");
+        assert_eq!(receipt["status"], "paid");
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_unknown_receipt() -> Result<(), actix_web::Error> {
+        let pool = db_pool().await;
+        let mut server = actix_web::test::init_service(
+            App::new()
+                .service(session)
+                .app_data(Data::new(stripe_test::stripe_client()))
+                .app_data(Data::new(pool.clone())),
+        )
+        .await;
+
+        let req = TestRequest::get()
+            .uri("/session/not_a_session")
+            .to_request();
+        let response = actix_web::test::call_service(&mut server, req).await;
+
+        assert_eq!(response.status(), actix_web::http::StatusCode::NOT_FOUND);
+
+        Ok(())
+    }
+}
@@ -9,10 +9,16 @@ use stripe::{
     CheckoutSession, CheckoutSessionMode, Client, CreateCheckoutSession,
     CreateCheckoutSessionLineItems, CreateCheckoutSessionLineItemsPriceData,
     CreateCheckoutSessionLineItemsPriceDataProductData, CreateCheckoutSessionPaymentIntentData,
-    CreateCheckoutSessionPaymentIntentDataTransferData, Currency, Metadata,
+    CreateCheckoutSessionPaymentIntentDataTransferData, Metadata,
 };
 
-use crate::types::payments::AppPaymentRequest;
+use crate::{
+    types::{payments::AppPaymentRequest, ErrorResponse, ErrorTranslationKey},
+    utils::{
+        currency::{validate_amount, AmountError},
+        fees::{calculate_fee, fee_policy_for_app},
+    },
+};
 
 #[cfg_attr(feature = "openapi", utoipa::path(
     path = "/payments/start",
@@ -26,6 +32,7 @@ use crate::types::payments::AppPaymentRequest;
                 description = "Stripe checkout URL the user's browser should be redirected to"
             ))
         ),
+        (status = 400, body = ErrorResponse),
     )
 ))]
 #[get("/start")]
@@ -38,6 +45,37 @@ pub async fn start(
     app_information: actix_web::web::Query<AppPaymentRequest>,
     pool: actix_web::web::Data<Pool<AsyncPgConnection>>,
 ) -> HttpResponse {
+    let settings = common::settings::get_settings().expect("Failed to read settings.");
+
+    let currency = match validate_amount(
+        app_information.amount.into(),
+        app_information.currency.as_deref().unwrap_or("usd"),
+        &settings.stripe.minimum_amounts,
+    ) {
+        Ok(c) => c,
+        Err(AmountError::UnsupportedCurrency) => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "Payments in the requested currency aren't supported".into(),
+                translation_key: ErrorTranslationKey::UnsupportedCurrency,
+            });
+        }
+        Err(AmountError::BelowMinimum(minimum)) => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: format!(
+                    "The minimum payment in this currency is {} of its smallest unit",
+                    minimum
+                ),
+                translation_key: ErrorTranslationKey::PaymentAmountTooLow,
+            });
+        }
+        Err(AmountError::AboveMaximum) => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "The requested amount is too large".into(),
+                translation_key: ErrorTranslationKey::PaymentAmountTooHigh,
+            });
+        }
+    };
+
     let mut con = match pool.get().await {
         Ok(c) => c,
         Err(e) => {
@@ -55,14 +93,53 @@ pub async fn start(
             }
         };
 
-    // TODO: Fix these URLs
-    let mut params = CreateCheckoutSession::new("http://test.com/success");
-    params.cancel_url = Some("http://test.com/cancel");
+    let (minimum_price, price_currency, free_allowed) =
+        match get_app_pricing(&mut con, &app_information.app_id).await {
+            Ok(p) => p,
+            Err(e) => {
+                tracing::error!("Error fetching pricing for app: {}", e);
+                return HttpResponse::InternalServerError().finish();
+            }
+        };
+
+    // The minimum price can only be compared against payments in the currency it was set in.
+    // Payments in other currencies are voluntary, so only accepted if the app is free to download
+    if currency.to_string() == price_currency {
+        if i64::from(app_information.amount) < i64::from(minimum_price) {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: format!(
+                    "The developer of this app asks for a payment of at least {} of the smallest unit of {}",
+                    minimum_price, price_currency
+                ),
+                translation_key: ErrorTranslationKey::PaymentBelowAppMinimum,
+            });
+        }
+    } else if !free_allowed {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: format!("Payments for this app must be made in {}", price_currency),
+            translation_key: ErrorTranslationKey::PaymentCurrencyNotAccepted,
+        });
+    }
+
+    let fee_policy =
+        match fee_policy_for_app(&mut con, &app_information.app_id, &settings.stripe.fee).await {
+            Ok(p) => p,
+            Err(e) => {
+                tracing::error!("Error fetching fee policy for app: {}", e);
+                return HttpResponse::InternalServerError().finish();
+            }
+        };
+
+    let success_url = success_url(&settings.frontend_url, &app_information.app_id);
+    let cancel_url = cancel_url(&settings.frontend_url, &app_information.app_id);
+
+    let mut params = CreateCheckoutSession::new(&success_url);
+    params.cancel_url = Some(&cancel_url);
     params.mode = Some(CheckoutSessionMode::Payment);
     params.line_items = Some(vec![CreateCheckoutSessionLineItems {
         quantity: Some(1),
         price_data: Some(CreateCheckoutSessionLineItemsPriceData {
-            currency: Currency::USD,
+            currency,
             unit_amount: Some(app_information.amount.into()),
             product_data: Some(CreateCheckoutSessionLineItemsPriceDataProductData {
                 name: app_information.app_name.to_owned(),
@@ -72,12 +149,13 @@ pub async fn start(
         }),
         ..Default::default()
     }]);
-    params.metadata = Some(Metadata::from([(
-        "app_id".into(),
-        app_information.app_id.to_owned(),
-    )]));
+    let fee = calculate_fee(app_information.amount.into(), currency, &fee_policy);
+    params.metadata = Some(Metadata::from([
+        ("app_id".into(), app_information.app_id.to_owned()),
+        ("platform_fee".into(), fee.to_string()),
+    ]));
     params.payment_intent_data = Some(CreateCheckoutSessionPaymentIntentData {
-        application_fee_amount: Some(calculate_fee(app_information.amount).into()),
+        application_fee_amount: Some(fee),
         transfer_data: Some(CreateCheckoutSessionPaymentIntentDataTransferData {
             destination: stripe_account_id.to_owned(),
             ..Default::default()
@@ -102,13 +180,18 @@ pub async fn start(
         .finish()
 }
 
-fn calculate_fee(amount: u32) -> u32 {
-    let fee = ((amount as f64) * 0.3).round() as u32;
-    if fee >= 50 {
-        fee
-    } else {
-        50
-    }
+/// Page the buyer lands on after paying, Stripe fills in the `{CHECKOUT_SESSION_ID}` placeholder
+fn success_url(frontend_url: &str, app_id: &str) -> String {
+    format!(
+        "{}/app/{}/receipt/{{CHECKOUT_SESSION_ID}}",
+        frontend_url.trim_end_matches('/'),
+        app_id
+    )
+}
+
+/// Page the buyer returns to if they back out of the checkout
+fn cancel_url(frontend_url: &str, app_id: &str) -> String {
+    format!("{}/app/{}", frontend_url.trim_end_matches('/'), app_id)
 }
 
 async fn get_stripe_account_for_app(
@@ -124,6 +207,19 @@ async fn get_stripe_account_for_app(
         .ok_or(anyhow!("Couldn't find app"))
 }
 
+/// Gets the minimum price, the currency it's in and whether the app can be downloaded for free
+async fn get_app_pricing(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    app_id: &str,
+) -> Result<(i32, String, bool), diesel::result::Error> {
+    use common::schema::apps::dsl::*;
+
+    apps.filter(id.eq(app_id))
+        .select((minimum_price, price_currency, free_allowed))
+        .get_result::<(i32, String, bool)>(con)
+        .await
+}
+
 #[cfg(test)]
 mod tests {
     use crate::utils::{
@@ -135,12 +231,18 @@ mod tests {
     use actix_web::{test::TestRequest, App};
 
     #[test]
-    fn test_fee_calculations() {
-        assert_eq!(50, calculate_fee(100));
-        assert_eq!(50, calculate_fee(150));
-        assert_eq!(51, calculate_fee(170));
-        assert_eq!(60, calculate_fee(200));
-        assert_eq!(150, calculate_fee(500));
+    fn test_redirect_urls() {
+        assert_eq!(
+            success_url("http://localhost:3000/", "com.github.davidmhewitt.torrential"),
+            "http://localhost:3000/app/com.github.davidmhewitt.torrential/receipt/{CHECKOUT_SESSION_ID}"
+        );
+        assert_eq!(
+            cancel_url(
+                "http://localhost:3000",
+                "com.github.davidmhewitt.torrential"
+            ),
+            "http://localhost:3000/app/com.github.davidmhewitt.torrential"
+        );
     }
 
     #[actix_web::test]
@@ -186,4 +288,64 @@ mod tests {
 
         Ok(())
     }
+
+    #[actix_web::test]
+    async fn test_rejected_payments() -> Result<(), actix_web::Error> {
+        use common::schema::apps;
+
+        let pool = db_pool().await;
+        let stripe_client = actix_web::web::Data::new(stripe_test::stripe_client());
+
+        let mut server = actix_web::test::init_service(
+            App::new()
+                .service(start)
+                .app_data(stripe_client)
+                .app_data(actix_web::web::Data::new(pool.clone())),
+        )
+        .await;
+
+        for query in [
+            "app_id=com.example.app&app_name=Example&amount=300&currency=xyz",
+            "app_id=com.example.app&app_name=Example&amount=99",
+            "app_id=com.example.app&app_name=Example&amount=149&currency=jpy",
+        ] {
+            let req = TestRequest::get()
+                .uri(&format!("/start?{}", query))
+                .to_request();
+            let response = actix_web::test::call_service(&mut server, req).await;
+
+            assert_eq!(response.status(), actix_web::http::StatusCode::BAD_REQUEST);
+        }
+
+        let mut con = pool.get().await.expect("Unable to get pool connection");
+        let app = create_app(&mut con, None, Some("acct_1234"))
+            .await
+            .expect("Unable to create test app");
+        diesel::update(apps::table.filter(apps::id.eq(&app)))
+            .set((apps::minimum_price.eq(500), apps::free_allowed.eq(false)))
+            .execute(&mut con)
+            .await
+            .expect("Unable to set minimum price");
+
+        let mut statuses = vec![];
+        for query in ["amount=300", "amount=300&currency=jpy"] {
+            let req = TestRequest::get()
+                .uri(&format!("/start?app_id={}&app_name=Example&{}", app, query))
+                .to_request();
+            statuses.push(
+                actix_web::test::call_service(&mut server, req)
+                    .await
+                    .status(),
+            );
+        }
+
+        diesel::delete(apps::table.filter(apps::id.eq(&app)))
+            .execute(&mut con)
+            .await
+            .ok();
+
+        assert_eq!(statuses, vec![actix_web::http::StatusCode::BAD_REQUEST; 2]);
+
+        Ok(())
+    }
 }
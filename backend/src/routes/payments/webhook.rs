@@ -0,0 +1,436 @@
+use std::collections::HashMap;
+
+use actix_web::{
+    post,
+    web::{Bytes, Data},
+    HttpRequest, HttpResponse,
+};
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl};
+use diesel_async::{
+    pooled_connection::bb8::{Pool, PooledConnection},
+    AsyncPgConnection, RunQueryDsl,
+};
+use secrecy::ExposeSecret;
+use serde::Deserialize;
+
+use common::models::NewPayment;
+
+use crate::{
+    types::{ErrorResponse, ErrorTranslationKey},
+    utils::webhooks::verify_stripe_signature,
+};
+
+const STRIPE_SIGNATURE_HEADER: &str = "Stripe-Signature";
+
+#[derive(Deserialize)]
+struct StripeEvent {
+    id: String,
+    #[serde(rename = "type")]
+    kind: String,
+    data: StripeEventData,
+}
+
+#[derive(Deserialize)]
+struct StripeEventData {
+    object: serde_json::Value,
+}
+
+/// The fields of a Stripe checkout session object we need to record a payment
+#[derive(Deserialize)]
+struct CompletedCheckoutSession {
+    id: String,
+    payment_intent: Option<String>,
+    payment_status: String,
+    amount_total: Option<i64>,
+    currency: Option<String>,
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+}
+
+/// The fields of a Stripe charge object we need to record a refund
+#[derive(Deserialize)]
+struct RefundedCharge {
+    payment_intent: Option<String>,
+    amount_refunded: i64,
+    refunded: bool,
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/payments/webhook",
+    request_body(content = String, description = "Stripe event, as signed in the `Stripe-Signature` header"),
+    params(
+        ("Stripe-Signature" = String, Header, description = "Signature of the payload using the webhook signing secret"),
+    ),
+    responses(
+        (status = 200, description = "The event was processed or ignored"),
+        (status = 400, body = ErrorResponse),
+        (status = 500, description = "The event couldn't be processed yet, so Stripe should retry it"),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Handling a Stripe webhook", skip(req, body, pool))
+)]
+#[post("/webhook")]
+pub async fn webhook(
+    req: HttpRequest,
+    body: Bytes,
+    pool: Data<Pool<AsyncPgConnection>>,
+) -> HttpResponse {
+    let settings = common::settings::get_settings().expect("Failed to read settings.");
+
+    if settings.stripe.webhook_secret.expose_secret().is_empty() {
+        tracing::error!("Received a Stripe webhook, but no webhook secret is configured");
+        return HttpResponse::InternalServerError().finish();
+    }
+
+    let signature = req
+        .headers()
+        .get(STRIPE_SIGNATURE_HEADER)
+        .and_then(|h| h.to_str().ok())
+        .unwrap_or_default();
+
+    if let Err(e) = verify_stripe_signature(
+        &body,
+        signature,
+        settings.stripe.webhook_secret.expose_secret(),
+        time::OffsetDateTime::now_utc().unix_timestamp(),
+    ) {
+        tracing::warn!("Rejected Stripe webhook: {}", e);
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            error: "The webhook signature could not be verified".into(),
+            translation_key: ErrorTranslationKey::InvalidWebhookSignature,
+        });
+    }
+
+    let event = match serde_json::from_slice::<StripeEvent>(&body) {
+        Ok(e) => e,
+        Err(e) => {
+            tracing::error!("Unable to deserialize Stripe event: {}", e);
+            return HttpResponse::BadRequest().finish();
+        }
+    };
+
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for Stripe webhook: {}", e);
+            return HttpResponse::InternalServerError().finish();
+        }
+    };
+
+    let result = match event.kind.as_str() {
+        "checkout.session.completed" | "checkout.session.async_payment_succeeded" => {
+            match serde_json::from_value(event.data.object) {
+                Ok(session) => record_payment(&mut con, session).await,
+                Err(e) => Err(e.into()),
+            }
+        }
+        "charge.refunded" => match serde_json::from_value(event.data.object) {
+            Ok(charge) => record_refund(&mut con, charge).await,
+            Err(e) => Err(e.into()),
+        },
+        _ => {
+            tracing::debug!("Ignoring Stripe event {} of type {}", event.id, event.kind);
+            Ok(())
+        }
+    };
+
+    match result {
+        Ok(_) => HttpResponse::Ok().finish(),
+        Err(e) => {
+            tracing::error!("Error processing Stripe event {}: {}", event.id, e);
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
+
+async fn record_payment(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    session: CompletedCheckoutSession,
+) -> anyhow::Result<()> {
+    use common::schema::{apps, payments};
+
+    // Asynchronous payment methods complete the session before the money arrives,
+    // those payments are recorded by the `async_payment_succeeded` event instead
+    if session.payment_status != "paid" {
+        return Ok(());
+    }
+
+    // Keep the payment even if the app has since been removed
+    let app_id = match session.metadata.get("app_id") {
+        Some(requested) => apps::table
+            .filter(apps::id.eq(requested))
+            .select(apps::id)
+            .get_result::<String>(con)
+            .await
+            .optional()?,
+        None => None,
+    };
+
+    let platform_fee = session
+        .metadata
+        .get("platform_fee")
+        .and_then(|f| f.parse::<i64>().ok())
+        .unwrap_or_default();
+
+    diesel::insert_into(payments::table)
+        .values(NewPayment {
+            checkout_session_id: &session.id,
+            payment_intent_id: session.payment_intent.as_deref(),
+            app_id: app_id.as_deref(),
+            amount: session.amount_total.unwrap_or_default(),
+            currency: session.currency.as_deref().unwrap_or("usd"),
+            platform_fee,
+            status: "paid",
+        })
+        .on_conflict(payments::checkout_session_id)
+        .do_nothing()
+        .execute(con)
+        .await?;
+
+    Ok(())
+}
+
+/// Records a refund against its payment. Stripe doesn't order events, so a refund can arrive
+/// before the payment it refunds, in which case it fails and is retried by Stripe later.
+async fn record_refund(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    charge: RefundedCharge,
+) -> anyhow::Result<()> {
+    use common::schema::payments::dsl::*;
+
+    let Some(intent) = charge.payment_intent else {
+        return Ok(());
+    };
+
+    let updated = diesel::update(payments.filter(payment_intent_id.eq(&intent)))
+        .set((
+            amount_refunded.eq(charge.amount_refunded),
+            status.eq(if charge.refunded {
+                "refunded"
+            } else {
+                "partially_refunded"
+            }),
+        ))
+        .execute(con)
+        .await?;
+
+    if updated == 0 {
+        return Err(anyhow::anyhow!(
+            "Received refund for unknown payment intent {}",
+            intent
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{test::TestRequest, App};
+    use argon2::password_hash::rand_core::{OsRng, RngCore};
+    use common::models::Payment;
+
+    use crate::utils::{
+        db_test::{create_app, db_pool, test_transaction_pool},
+        webhooks::stripe_signature_header,
+    };
+
+    use super::*;
+
+    const CHECKOUT_SESSION_COMPLETED: &str =
+        include_str!("fixtures/checkout_session_completed.json");
+    const CHARGE_REFUNDED: &str = include_str!("fixtures/charge_refunded.json");
+
+    fn random_suffix() -> String {
+        let mut buff = [0_u8; 8];
+        OsRng.fill_bytes(&mut buff);
+        hex::encode(buff)
+    }
+
+    fn signed_request(payload: &serde_json::Value, secret: &str) -> TestRequest {
+        let payload = serde_json::to_vec(payload).expect("Unable to serialize fixture");
+        let header = stripe_signature_header(
+            &payload,
+            secret,
+            time::OffsetDateTime::now_utc().unix_timestamp(),
+        );
+
+        TestRequest::post()
+            .uri("/webhook")
+            .insert_header((STRIPE_SIGNATURE_HEADER, header))
+            .set_payload(payload)
+    }
+
+    async fn get_payment(pool: &Pool<AsyncPgConnection>, session_id: &str) -> Option<Payment> {
+        use common::schema::payments;
+
+        let mut con = pool.get().await.expect("Unable to get pool connection");
+        payments::table
+            .filter(payments::checkout_session_id.eq(session_id))
+            .first::<Payment>(&mut con)
+            .await
+            .optional()
+            .expect("Unable to get payment")
+    }
+
+    #[actix_web::test]
+    async fn test_payment_and_refund() -> Result<(), actix_web::Error> {
+        let settings = common::settings::get_settings().expect("Failed to read settings.");
+        let secret = settings.stripe.webhook_secret.expose_secret();
+
+        let pool = test_transaction_pool().await;
+        let mut server = actix_web::test::init_service(
+            App::new()
+                .service(webhook)
+                .app_data(Data::new(pool.clone())),
+        )
+        .await;
+
+        let app = {
+            let mut con = pool.get().await.expect("Unable to get pool connection");
+            create_app(&mut con, None, Some("acct_1234"))
+                .await
+                .expect("Unable to create test app")
+        };
+
+        let session_id = format!("cs_test_{}", random_suffix());
+        let intent_id = format!("pi_{}", random_suffix());
+
+        let mut completed: serde_json::Value =
+            serde_json::from_str(CHECKOUT_SESSION_COMPLETED).expect("Invalid fixture");
+        completed["data"]["object"]["id"] = session_id.clone().into();
+        completed["data"]["object"]["payment_intent"] = intent_id.clone().into();
+        completed["data"]["object"]["metadata"]["app_id"] = app.clone().into();
+
+        let mut refunded: serde_json::Value =
+            serde_json::from_str(CHARGE_REFUNDED).expect("Invalid fixture");
+        refunded["data"]["object"]["payment_intent"] = intent_id.clone().into();
+
+        // Stripe retries deliveries, so the same event may arrive more than once
+        for _ in 0..2 {
+            let response = actix_web::test::call_service(
+                &mut server,
+                signed_request(&completed, secret).to_request(),
+            )
+            .await;
+            assert!(response.status().is_success());
+        }
+
+        let payment = get_payment(&pool, &session_id)
+            .await
+            .expect("Payment wasn't recorded");
+
+        assert_eq!(payment.app_id.as_deref(), Some(app.as_str()));
+        assert_eq!(
+            payment.payment_intent_id.as_deref(),
+            Some(intent_id.as_str())
+        );
+        assert_eq!(payment.amount, 300);
+        assert_eq!(payment.currency, "usd");
+        assert_eq!(payment.platform_fee, 90);
+        assert_eq!(payment.status, "paid");
+
+        let response = actix_web::test::call_service(
+            &mut server,
+            signed_request(&refunded, secret).to_request(),
+        )
+        .await;
+        assert!(response.status().is_success());
+
+        let payment = get_payment(&pool, &session_id)
+            .await
+            .expect("Payment disappeared");
+
+        assert_eq!(payment.amount_refunded, 100);
+        assert_eq!(payment.status, "partially_refunded");
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_refund_before_payment() -> Result<(), actix_web::Error> {
+        let settings = common::settings::get_settings().expect("Failed to read settings.");
+        let secret = settings.stripe.webhook_secret.expose_secret();
+
+        let pool = test_transaction_pool().await;
+        let mut server = actix_web::test::init_service(
+            App::new()
+                .service(webhook)
+                .app_data(Data::new(pool.clone())),
+        )
+        .await;
+
+        let session_id = format!("cs_test_{}", random_suffix());
+        let intent_id = format!("pi_{}", random_suffix());
+
+        let mut completed: serde_json::Value =
+            serde_json::from_str(CHECKOUT_SESSION_COMPLETED).expect("Invalid fixture");
+        completed["data"]["object"]["id"] = session_id.clone().into();
+        completed["data"]["object"]["payment_intent"] = intent_id.clone().into();
+
+        let mut refunded: serde_json::Value =
+            serde_json::from_str(CHARGE_REFUNDED).expect("Invalid fixture");
+        refunded["data"]["object"]["payment_intent"] = intent_id.clone().into();
+
+        // Failing makes Stripe deliver the refund again later
+        let response = actix_web::test::call_service(
+            &mut server,
+            signed_request(&refunded, secret).to_request(),
+        )
+        .await;
+        assert!(response.status().is_server_error());
+
+        for event in [&completed, &refunded] {
+            let response = actix_web::test::call_service(
+                &mut server,
+                signed_request(event, secret).to_request(),
+            )
+            .await;
+            assert!(response.status().is_success());
+        }
+
+        let payment = get_payment(&pool, &session_id)
+            .await
+            .expect("Payment wasn't recorded");
+
+        assert_eq!(payment.amount_refunded, 100);
+        assert_eq!(payment.status, "partially_refunded");
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_invalid_signature() -> Result<(), actix_web::Error> {
+        let pool = db_pool().await;
+        let mut server = actix_web::test::init_service(
+            App::new()
+                .service(webhook)
+                .app_data(Data::new(pool.clone())),
+        )
+        .await;
+
+        let completed: serde_json::Value =
+            serde_json::from_str(CHECKOUT_SESSION_COMPLETED).expect("Invalid fixture");
+
+        let response = actix_web::test::call_service(
+            &mut server,
+            signed_request(&completed, "whsec_wrong").to_request(),
+        )
+        .await;
+        assert_eq!(response.status(), actix_web::http::StatusCode::BAD_REQUEST);
+
+        let response = actix_web::test::call_service(
+            &mut server,
+            TestRequest::post()
+                .uri("/webhook")
+                .set_payload(CHECKOUT_SESSION_COMPLETED)
+                .to_request(),
+        )
+        .await;
+        assert_eq!(response.status(), actix_web::http::StatusCode::BAD_REQUEST);
+
+        Ok(())
+    }
+}
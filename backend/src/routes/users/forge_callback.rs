@@ -0,0 +1,346 @@
+use actix_session::Session;
+use actix_web::{
+    get,
+    web::{Data, Path, Query},
+    HttpResponse,
+};
+use anyhow::Result;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl};
+use diesel_async::{
+    pooled_connection::bb8::{Pool, PooledConnection},
+    scoped_futures::ScopedFutureExt,
+    AsyncConnection, AsyncPgConnection, RunQueryDsl,
+};
+use oauth2::{reqwest::async_http_client, AuthorizationCode, TokenResponse};
+use secrecy::{ExposeSecret, SecretString};
+use uuid::Uuid;
+
+use common::models::{AuditAction, NewAuditEvent, NewForgeAuth, NewUser};
+use serde_json::json;
+
+use super::{
+    forge_login::{csrf_session_key, oauth_client},
+    github_callback::{error_redirect, CodeResponse},
+};
+use crate::{
+    types::ErrorTranslationKey,
+    utils::{audit, forges::fetch_user},
+};
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/users/{forge}/callback",
+    params(
+        ("forge" = String, Path, description = "Identifier of the forge being signed in with, e.g. `gitlab`"),
+        CodeResponse,
+    ),
+    responses(
+        (status = 303),
+        (status = 404, description = "The forge doesn't exist or isn't configured"),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Forge Callback", skip(pool, session, response))
+)]
+#[get("/{forge}/callback")]
+pub async fn forge_callback(
+    pool: Data<Pool<AsyncPgConnection>>,
+    session: Session,
+    path: Path<(String,)>,
+    response: Query<CodeResponse>,
+) -> HttpResponse {
+    let settings = common::settings::get_settings().expect("Failed to read settings.");
+
+    let Some((forge, client)) = oauth_client(&path.0, &settings) else {
+        return HttpResponse::NotFound().finish();
+    };
+
+    let csrf_key = csrf_session_key(forge);
+    match session.get::<String>(&csrf_key) {
+        Ok(Some(c)) if c == response.state => {}
+        _ => {
+            return error_redirect(
+                &settings.frontend_url,
+                ErrorTranslationKey::GenericRegistrationProblem,
+            )
+        }
+    }
+
+    let token = match client
+        .exchange_code(AuthorizationCode::new(
+            response.code.expose_secret().to_owned(),
+        ))
+        .request_async(async_http_client)
+        .await
+    {
+        Ok(t) => t,
+        Err(e) => {
+            tracing::error!("Error exchanging {} OAuth code: {}", forge.name(), e);
+            return error_redirect(
+                &settings.frontend_url,
+                ErrorTranslationKey::GenericRegistrationProblem,
+            );
+        }
+    };
+
+    let access_token = SecretString::new(token.access_token().secret().to_owned());
+    let forge_user = match fetch_user(forge, forge.api_url(), &access_token).await {
+        Ok(u) => u,
+        Err(e) => {
+            tracing::error!("Error getting {} user: {}", forge.name(), e);
+            return error_redirect(
+                &settings.frontend_url,
+                ErrorTranslationKey::GenericRegistrationProblem,
+            );
+        }
+    };
+
+    let Some(email) = forge_user.email else {
+        return error_redirect(
+            &settings.frontend_url,
+            ErrorTranslationKey::RegistrationNoEmailPermission,
+        );
+    };
+
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(_) => {
+            return error_redirect(
+                &settings.frontend_url,
+                ErrorTranslationKey::GenericRegistrationProblem,
+            )
+        }
+    };
+
+    let user_id = match sign_in_forge_user(
+        &mut con,
+        &email,
+        forge.id(),
+        &forge_user.id,
+        &access_token,
+        token.refresh_token().map(|t| t.secret().as_str()),
+    )
+    .await
+    {
+        Ok(Some(u)) => u,
+        Ok(None) => {
+            return error_redirect(
+                &settings.frontend_url,
+                ErrorTranslationKey::AccountDeactivated,
+            )
+        }
+        Err(e) => {
+            tracing::error!("Error signing in {} user: {}", forge.name(), e);
+            return error_redirect(
+                &settings.frontend_url,
+                ErrorTranslationKey::GenericRegistrationProblem,
+            );
+        }
+    };
+
+    session.remove(&csrf_key);
+    session.renew();
+    session
+        .insert(crate::types::USER_ID_KEY, user_id)
+        .expect("`user_id` cannot be inserted into session");
+    session
+        .insert(crate::types::USER_EMAIL_KEY, &email)
+        .expect("`user_email` cannot be inserted into session");
+    session
+        .insert(
+            crate::types::USER_LOGGED_IN_AT_KEY,
+            crate::utils::auth::session_timestamp(time::OffsetDateTime::now_utc()),
+        )
+        .expect("`logged_in_at` cannot be inserted into session");
+
+    audit::record(
+        &mut con,
+        NewAuditEvent::new(&user_id, AuditAction::Login).details(json!({ "method": forge.id() })),
+    )
+    .await;
+
+    HttpResponse::SeeOther()
+        .insert_header((actix_web::http::header::LOCATION, settings.frontend_url))
+        .finish()
+}
+
+/// Finds the active user the forge account is linked to, or the one with its email address,
+/// registering them if there isn't one, and stores the forge account's latest tokens.
+/// Returns `None` if an administrator has deactivated that user
+async fn sign_in_forge_user(
+    con: &mut PooledConnection<'_, AsyncPgConnection>,
+    user_email: &str,
+    forge_id: &str,
+    forge_user: &str,
+    access: &SecretString,
+    refresh: Option<&str>,
+) -> Result<Option<Uuid>> {
+    use common::schema::{forge_auth, user_profile, users};
+
+    let user_email = user_email.to_owned();
+    let forge_id = forge_id.to_owned();
+    let forge_user = forge_user.to_owned();
+    let access = access.expose_secret().to_owned();
+    let refresh = refresh.map(|r| r.to_owned());
+
+    let user_id = con
+        .transaction::<_, diesel::result::Error, _>(|transaction| {
+            async move {
+                let linked = forge_auth::table
+                    .inner_join(users::table)
+                    .filter(forge_auth::forge.eq(&forge_id))
+                    .filter(forge_auth::forge_user_id.eq(&forge_user))
+                    .filter(users::is_active.eq(true))
+                    .select((users::id, users::deactivated_at.is_not_null()))
+                    .get_result::<(Uuid, bool)>(transaction)
+                    .await
+                    .optional()?;
+
+                let existing = match linked {
+                    Some(user) => Some(user),
+                    None => users::table
+                        .filter(users::email.eq(&user_email))
+                        .filter(users::is_active.eq(true))
+                        .select((users::id, users::deactivated_at.is_not_null()))
+                        .get_result::<(Uuid, bool)>(transaction)
+                        .await
+                        .optional()?,
+                };
+
+                let user_id = match existing {
+                    Some((_, true)) => return Ok(None),
+                    Some((id, false)) => id,
+                    None => {
+                        let id = diesel::insert_into(users::table)
+                            .values(NewUser {
+                                email: &user_email,
+                                password: None,
+                                is_active: true,
+                                is_admin: false,
+                            })
+                            .returning(users::id)
+                            .get_result::<Uuid>(transaction)
+                            .await?;
+
+                        diesel::insert_into(user_profile::table)
+                            .values(user_profile::user_id.eq(id))
+                            .execute(transaction)
+                            .await?;
+
+                        id
+                    }
+                };
+
+                diesel::insert_into(forge_auth::table)
+                    .values(NewForgeAuth {
+                        user_id: &user_id,
+                        forge: &forge_id,
+                        forge_user_id: &forge_user,
+                        access_token: Some(&access),
+                        refresh_token: refresh.as_deref(),
+                    })
+                    .on_conflict((forge_auth::user_id, forge_auth::forge))
+                    .do_update()
+                    .set((
+                        forge_auth::forge_user_id.eq(&forge_user),
+                        forge_auth::access_token.eq(&access),
+                        forge_auth::refresh_token.eq(&refresh),
+                    ))
+                    .execute(transaction)
+                    .await?;
+
+                Ok(Some(user_id))
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    Ok(user_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::db_test::{create_user, db_pool};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sign_in_forge_user() -> anyhow::Result<()> {
+        use common::schema::{forge_auth, users};
+
+        let db_pool = db_pool().await;
+        let mut con = db_pool.get().await?;
+
+        con.begin_test_transaction().await?;
+
+        let existing = create_user(&mut con, true).await?;
+        let existing_email = users::table
+            .filter(users::id.eq(existing))
+            .select(users::email)
+            .get_result::<String>(&mut con)
+            .await?;
+
+        let token = SecretString::new("first".into());
+        let signed_in =
+            sign_in_forge_user(&mut con, &existing_email, "gitlab", "1234", &token, None).await?;
+        assert_eq!(signed_in, Some(existing));
+
+        // Signing in again replaces the tokens
+        let token = SecretString::new("second".into());
+        sign_in_forge_user(&mut con, &existing_email, "gitlab", "1234", &token, None).await?;
+
+        let stored = forge_auth::table
+            .filter(forge_auth::user_id.eq(existing))
+            .select(forge_auth::access_token)
+            .load::<Option<String>>(&mut con)
+            .await?;
+        assert_eq!(stored, vec![Some("second".to_owned())]);
+
+        // The linked account is found even once the forge account's email address changes
+        let renamed = sign_in_forge_user(
+            &mut con,
+            "renamed@example.com",
+            "gitlab",
+            "1234",
+            &token,
+            None,
+        )
+        .await?;
+        assert_eq!(renamed, Some(existing));
+
+        let registered = sign_in_forge_user(
+            &mut con,
+            "new-forge-user@example.com",
+            "codeberg",
+            "5678",
+            &token,
+            Some("refresh"),
+        )
+        .await?;
+        assert!(registered.is_some_and(|r| r != existing));
+
+        // Deactivated users can't sign in through a linked forge account or their email address
+        diesel::update(users::table.filter(users::id.eq(existing)))
+            .set(users::deactivated_at.eq(time::OffsetDateTime::now_utc()))
+            .execute(&mut con)
+            .await?;
+
+        let linked =
+            sign_in_forge_user(&mut con, &existing_email, "gitlab", "1234", &token, None).await?;
+        assert_eq!(linked, None);
+
+        let by_email =
+            sign_in_forge_user(&mut con, &existing_email, "codeberg", "9999", &token, None).await?;
+        assert_eq!(by_email, None);
+
+        let linked_codeberg = forge_auth::table
+            .filter(forge_auth::user_id.eq(existing))
+            .filter(forge_auth::forge.eq("codeberg"))
+            .count()
+            .get_result::<i64>(&mut con)
+            .await?;
+        assert_eq!(linked_codeberg, 0);
+
+        Ok(())
+    }
+}
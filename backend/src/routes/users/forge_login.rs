@@ -0,0 +1,96 @@
+use actix_session::Session;
+use actix_web::{get, web::Path, HttpResponse};
+use common::settings::Settings;
+use oauth2::{
+    basic::BasicClient, AuthUrl, ClientId, ClientSecret, CsrfToken, RedirectUrl, Scope, TokenUrl,
+};
+use secrecy::ExposeSecret;
+
+use crate::utils::forges::{forge_by_id, Forge};
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/users/{forge}/login",
+    params(
+        ("forge" = String, Path, description = "Identifier of the forge to sign in with, e.g. `gitlab`"),
+    ),
+    responses(
+        (status = 303),
+        (status = 404, description = "The forge doesn't exist or isn't configured"),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Forge Login", skip(session))
+)]
+#[get("/{forge}/login")]
+pub async fn forge_login(session: Session, path: Path<(String,)>) -> HttpResponse {
+    let settings = common::settings::get_settings().expect("Failed to read settings.");
+
+    let Some((forge, client)) = oauth_client(&path.0, &settings) else {
+        return HttpResponse::NotFound().finish();
+    };
+
+    let (authorize_url, csrf_state) = forge
+        .scopes()
+        .iter()
+        .fold(
+            client.authorize_url(CsrfToken::new_random),
+            |request, scope| request.add_scope(Scope::new(scope.to_string())),
+        )
+        .url();
+
+    session
+        .insert(csrf_session_key(forge), csrf_state.secret())
+        .expect("Failed to serialize csrf state");
+
+    HttpResponse::SeeOther()
+        .insert_header((actix_web::http::header::LOCATION, authorize_url.as_str()))
+        .finish()
+}
+
+/// Builds the OAuth client for signing in with a forge, if it supports the generic login and an
+/// OAuth application is configured for it
+pub(crate) fn oauth_client(
+    forge_id: &str,
+    settings: &Settings,
+) -> Option<(&'static dyn Forge, BasicClient)> {
+    let forge = forge_by_id(forge_id).filter(|f| f.generic_login())?;
+    let application = settings
+        .forges
+        .get(forge.id())
+        .filter(|a| !a.client_id.is_empty())?;
+
+    let web_address = if settings.debug {
+        format!(
+            "{}:{}",
+            settings.application.base_url, settings.application.port
+        )
+    } else {
+        settings.application.base_url.to_owned()
+    };
+
+    let redirect_url =
+        match RedirectUrl::new(format!("{}/api/users/{}/callback", web_address, forge.id())) {
+            Ok(u) => u,
+            Err(e) => {
+                tracing::error!("Invalid {} callback URL: {}", forge.name(), e);
+                return None;
+            }
+        };
+
+    let client = BasicClient::new(
+        ClientId::new(application.client_id.to_owned()),
+        Some(ClientSecret::new(
+            application.client_secret.expose_secret().to_owned(),
+        )),
+        AuthUrl::new(forge.authorize_url().into()).expect("Invalid authorization endpoint URL"),
+        Some(TokenUrl::new(forge.token_url().into()).expect("Invalid token endpoint URL")),
+    )
+    .set_redirect_uri(redirect_url);
+
+    Some((forge, client))
+}
+
+pub(crate) fn csrf_session_key(forge: &dyn Forge) -> String {
+    format!("_{}_oauth_csrf", forge.id())
+}
@@ -14,12 +14,13 @@ use oauth2::{
 };
 use secrecy::{ExposeSecret, SecretString};
 use serde::Deserialize;
-use serde_json::Number;
+use serde_json::{json, Number};
 use serde_variant::to_variant_name;
 
 use crate::routes::users::register::insert_user_into_db;
 use crate::types::ErrorTranslationKey;
-use common::models::{NewGithubAuth, NewUser, User};
+use crate::utils::audit;
+use common::models::{AuditAction, NewAuditEvent, NewGithubAuth, NewUser, User};
 
 #[cfg(feature = "openapi")]
 use utoipa::IntoParams;
@@ -28,8 +29,8 @@ use utoipa::IntoParams;
 #[cfg_attr(feature = "openapi", derive(IntoParams))]
 pub struct CodeResponse {
     #[cfg_attr(feature = "openapi", param(value_type = String))]
-    code: SecretString,
-    state: String,
+    pub(crate) code: SecretString,
+    pub(crate) state: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -208,6 +209,26 @@ pub async fn github_callback(
         };
 
         if let Ok(user) = get_user_who_is_active(&mut con, primary).await {
+            if user.deactivated_at.is_some() {
+                return error_redirect(
+                    &settings.frontend_url,
+                    ErrorTranslationKey::AccountDeactivated,
+                );
+            }
+
+            // Keep the latest token, as it may have been granted more scopes since the last login
+            if let Err(e) = update_github_tokens(
+                &mut con,
+                &user.id,
+                &user_info.id.to_string(),
+                token.access_token().secret(),
+                token.refresh_token().map(|t| t.secret().as_str()),
+            )
+            .await
+            {
+                tracing::error!("Error updating GitHub tokens: {}", e);
+            }
+
             session.remove("_github_oauth_csrf");
             session.renew();
             session
@@ -216,6 +237,19 @@ pub async fn github_callback(
             session
                 .insert(crate::types::USER_EMAIL_KEY, primary)
                 .expect("`user_email` cannot be inserted into session");
+            session
+                .insert(
+                    crate::types::USER_LOGGED_IN_AT_KEY,
+                    crate::utils::auth::session_timestamp(time::OffsetDateTime::now_utc()),
+                )
+                .expect("`logged_in_at` cannot be inserted into session");
+
+            audit::record(
+                &mut con,
+                NewAuditEvent::new(&user.id, AuditAction::Login)
+                    .details(json!({ "method": "github" })),
+            )
+            .await;
 
             return actix_web::HttpResponse::SeeOther()
                 .insert_header((actix_web::http::header::LOCATION, settings.frontend_url))
@@ -267,6 +301,19 @@ pub async fn github_callback(
         session
             .insert(crate::types::USER_EMAIL_KEY, primary)
             .expect("`user_email` cannot be inserted into session");
+        session
+            .insert(
+                crate::types::USER_LOGGED_IN_AT_KEY,
+                crate::utils::auth::session_timestamp(time::OffsetDateTime::now_utc()),
+            )
+            .expect("`logged_in_at` cannot be inserted into session");
+
+        audit::record(
+            &mut connection,
+            NewAuditEvent::new(&user_id, AuditAction::Login)
+                .details(json!({ "method": "github", "registered": true })),
+        )
+        .await;
     }
 
     actix_web::HttpResponse::SeeOther()
@@ -274,7 +321,7 @@ pub async fn github_callback(
         .finish()
 }
 
-fn error_redirect(
+pub(crate) fn error_redirect(
     frontend_url: &str,
     translation_key: ErrorTranslationKey,
 ) -> actix_web::HttpResponse {
@@ -290,6 +337,37 @@ fn error_redirect(
         .finish()
 }
 
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(
+        name = "Updating GitHub tokens in DB.",
+        skip(con, access_token, refresh_token)
+    )
+)]
+async fn update_github_tokens(
+    con: &mut AsyncPgConnection,
+    user: &uuid::Uuid,
+    github_user: &str,
+    access_token: &str,
+    refresh_token: Option<&str>,
+) -> Result<()> {
+    use common::schema::github_auth::dsl::*;
+
+    diesel::update(
+        github_auth
+            .filter(user_id.eq(user))
+            .filter(github_user_id.eq(github_user)),
+    )
+    .set((
+        github_access_token.eq(access_token),
+        github_refresh_token.eq(refresh_token),
+    ))
+    .execute(con)
+    .await?;
+
+    Ok(())
+}
+
 #[cfg_attr(
     not(coverage),
     tracing::instrument(name = "Getting a user from DB.", skip(con))
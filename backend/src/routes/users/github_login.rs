@@ -36,6 +36,8 @@ pub async fn github_login(session: Session) -> actix_web::HttpResponse {
     let (authorize_url, csrf_state) = client
         .authorize_url(CsrfToken::new_random)
         .add_scope(Scope::new("user:email".into()))
+        // Needed to check the user is an admin of organisations that own their apps
+        .add_scope(Scope::new("read:org".into()))
         .url();
 
     session
@@ -8,9 +8,10 @@ use diesel::{ExpressionMethods, QueryDsl};
 use diesel_async::{pooled_connection::bb8::Pool, AsyncPgConnection, RunQueryDsl};
 use secrecy::SecretString;
 
-use common::models::User;
+use common::models::{AuditAction, NewAuditEvent, User};
+use serde_json::json;
 
-use crate::types::ErrorTranslationKey;
+use crate::{types::ErrorTranslationKey, utils::audit};
 
 #[cfg(feature = "openapi")]
 use utoipa::ToSchema;
@@ -63,6 +64,19 @@ async fn login_user(
                 session
                     .insert(crate::types::USER_EMAIL_KEY, &loggedin_user.email)
                     .expect("`user_email` cannot be inserted into session");
+                session
+                    .insert(
+                        crate::types::USER_LOGGED_IN_AT_KEY,
+                        crate::utils::auth::session_timestamp(time::OffsetDateTime::now_utc()),
+                    )
+                    .expect("`logged_in_at` cannot be inserted into session");
+
+                audit::record(
+                    &mut con,
+                    NewAuditEvent::new(&loggedin_user.id, AuditAction::Login)
+                        .details(json!({ "method": "password" })),
+                )
+                .await;
 
                 actix_web::HttpResponse::SeeOther()
                     .insert_header((actix_web::http::header::LOCATION, settings.frontend_url))
@@ -99,6 +113,7 @@ pub(crate) async fn get_user_who_is_active_with_password(
     Ok(users
         .filter(email.eq(user_email))
         .filter(is_active.eq(true))
+        .filter(deactivated_at.is_null())
         .filter(password.is_not_null())
         .get_result::<User>(con)
         .await?)
@@ -169,6 +184,17 @@ mod tests {
             .await
             .expect("Unable to insert user");
 
+        diesel::insert_into(users)
+            .values((
+                email.eq("test103@example.com"),
+                password.eq(hash("Password123!")),
+                is_active.eq(true),
+                deactivated_at.eq(time::OffsetDateTime::now_utc()),
+            ))
+            .execute(&mut con)
+            .await
+            .expect("Unable to insert user");
+
         let user1 = get_user_who_is_active_with_password(&mut con, "test100@example.com")
             .await
             .expect("Unable to get user 1");
@@ -178,6 +204,9 @@ mod tests {
         let _user3 = get_user_who_is_active_with_password(&mut con, "test102@example.com")
             .await
             .expect_err("Shouldn't have returned a passwordless user");
+        let _user4 = get_user_who_is_active_with_password(&mut con, "test103@example.com")
+            .await
+            .expect_err("Shouldn't have returned a deactivated user");
 
         assert_eq!(user1.email, "test100@example.com");
         assert_eq!(user2.email, "test101@example.com");
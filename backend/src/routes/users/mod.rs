@@ -1,9 +1,14 @@
 pub mod confirm_registration;
+pub mod forge_callback;
+pub mod forge_login;
 pub mod github_callback;
 pub mod github_login;
 pub mod login;
 pub mod logout;
+pub mod password_reset;
+pub mod password_reset_request;
 pub mod register;
+pub mod resend_verification;
 pub(crate) mod test_auth;
 
 pub fn auth_routes_config(cfg: &mut actix_web::web::ServiceConfig) {
@@ -12,9 +17,15 @@ pub fn auth_routes_config(cfg: &mut actix_web::web::ServiceConfig) {
             .service(confirm_registration::confirm)
             .service(github_callback::github_callback)
             .service(github_login::github_login)
+            // Registered after the GitHub routes, which have their own handlers
+            .service(forge_callback::forge_callback)
+            .service(forge_login::forge_login)
             .service(login::login_user)
             .service(logout::log_out)
+            .service(password_reset::reset_password)
+            .service(password_reset_request::request_password_reset)
             .service(register::register_user)
+            .service(resend_verification::resend_verification)
             .service(test_auth::test_auth),
     );
 }
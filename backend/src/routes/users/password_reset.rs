@@ -0,0 +1,279 @@
+use actix_web::{
+    post,
+    web::{Data, Json},
+    HttpResponse,
+};
+use diesel::ExpressionMethods;
+use diesel_async::{pooled_connection::bb8::Pool, AsyncPgConnection, RunQueryDsl};
+use secrecy::{ExposeSecret, SecretString};
+use uuid::Uuid;
+
+use crate::types::{ErrorResponse, ErrorTranslationKey, SuccessResponse};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+#[derive(serde::Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct PasswordReset {
+    /// The PASETO token from the password reset email
+    token: String,
+    #[cfg_attr(feature = "openapi", schema(format = Password, value_type = String))]
+    password: SecretString,
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/users/password/reset",
+    request_body = PasswordReset,
+    responses(
+        (status = 200, description = "The password was changed"),
+        (status = 400, body = ErrorResponse),
+    )
+))]
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Resetting a password", skip(pool, reset, redis_pool))
+)]
+#[post("/password/reset")]
+pub async fn reset_password(
+    pool: Data<Pool<AsyncPgConnection>>,
+    reset: Json<PasswordReset>,
+    redis_pool: Data<deadpool_redis::Pool>,
+) -> HttpResponse {
+    let reset = reset.into_inner();
+
+    if let Err(e) = crate::utils::auth::password::validate(reset.password.expose_secret()) {
+        return HttpResponse::BadRequest().json(e);
+    }
+
+    let mut redis_con = match redis_pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get redis connection for password reset: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+                translation_key: ErrorTranslationKey::GenericServerProblem,
+            });
+        }
+    };
+
+    let confirmation_token = match crate::utils::auth::tokens::verify_confirmation_token_pasetor(
+        reset.token,
+        &mut redis_con,
+        true,
+    )
+    .await
+    {
+        Ok(t) => t,
+        Err(e) => {
+            tracing::warn!("Invalid password reset token: {:#?}", e);
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "This password reset link has expired or was already used. Kindly request a new one".to_string(),
+                translation_key: ErrorTranslationKey::PasswordResetTokenInvalid,
+            });
+        }
+    };
+
+    let hashed_password = tokio::task::spawn_blocking(move || {
+        crate::utils::auth::password::hash(reset.password.expose_secret())
+    })
+    .await
+    .expect("Unable to unwrap JoinError.");
+
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for password reset: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+                translation_key: ErrorTranslationKey::GenericServerProblem,
+            });
+        }
+    };
+
+    if let Err(e) = set_password(&mut con, &confirmation_token.user_id, &hashed_password).await {
+        tracing::error!("Error updating password: {}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+            translation_key: ErrorTranslationKey::GenericServerProblem,
+        });
+    }
+
+    tracing::info!("Password was reset successfully.");
+    HttpResponse::Ok().json(SuccessResponse {
+        message: "Your password has been changed. You can now log in".to_string(),
+    })
+}
+
+async fn set_password(
+    con: &mut AsyncPgConnection,
+    user_id: &Uuid,
+    hashed_password: &str,
+) -> Result<(), diesel::result::Error> {
+    use common::schema::users::dsl::*;
+
+    diesel::update(users)
+        .filter(id.eq(user_id))
+        .set((
+            password.eq(hashed_password),
+            password_changed_at.eq(time::OffsetDateTime::now_utc()),
+        ))
+        .execute(con)
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::QueryDsl;
+    use diesel_async::AsyncConnection;
+
+    use actix_web::{http::StatusCode, test::TestRequest, App};
+    use serde_json::json;
+
+    use crate::utils::{
+        auth::{password::verify_password, tokens::issue_confirmation_token_pasetors},
+        db_test::{create_user, db_pool, redis_pool},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_set_password() -> anyhow::Result<()> {
+        use common::schema::users::dsl::*;
+
+        let db_pool = db_pool().await;
+        let mut con = db_pool.get().await?;
+
+        con.begin_test_transaction().await?;
+
+        let user = create_user(&mut con, true).await?;
+
+        set_password(
+            &mut con,
+            &user,
+            &crate::utils::auth::password::hash("NewPassword123!"),
+        )
+        .await?;
+
+        let stored = users
+            .filter(id.eq(user))
+            .select(password)
+            .get_result::<Option<String>>(&mut con)
+            .await?
+            .expect("Password wasn't set");
+
+        verify_password(&stored, &SecretString::new("NewPassword123!".into()))
+            .expect("New password wasn't stored");
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_reset_rejects_short_password() {
+        let mut server = actix_web::test::init_service(
+            App::new()
+                .service(reset_password)
+                .app_data(Data::new(db_pool().await))
+                .app_data(Data::new(redis_pool())),
+        )
+        .await;
+
+        let req = TestRequest::post()
+            .uri("/password/reset")
+            .set_json(json!({ "token": "unused", "password": "12345" }))
+            .to_request();
+        let response = actix_web::test::call_service(&mut server, req).await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_reset_rejects_invalid_token() {
+        let mut server = actix_web::test::init_service(
+            App::new()
+                .service(reset_password)
+                .app_data(Data::new(db_pool().await))
+                .app_data(Data::new(redis_pool())),
+        )
+        .await;
+
+        let req = TestRequest::post()
+            .uri("/password/reset")
+            .set_json(json!({ "token": "v4.local.invalid", "password": "NewPassword123!" }))
+            .to_request();
+        let response = actix_web::test::call_service(&mut server, req).await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_reset_password() -> anyhow::Result<()> {
+        use common::schema::users::dsl::*;
+
+        let settings = common::settings::get_settings().expect("Failed to read settings.");
+        let pool = db_pool().await;
+        let redis_pool = redis_pool();
+
+        let mut server = actix_web::test::init_service(
+            App::new()
+                .service(reset_password)
+                .app_data(Data::new(pool.clone()))
+                .app_data(Data::new(redis_pool.clone())),
+        )
+        .await;
+
+        let mut con = pool.get().await?;
+        let user = create_user(&mut con, true).await?;
+
+        let mut redis_con = redis_pool.get().await?;
+        let token =
+            issue_confirmation_token_pasetors(user, &mut redis_con, true, &settings.secret).await?;
+        // A registration confirmation token can't be used to reset the password
+        let confirmation_token =
+            issue_confirmation_token_pasetors(user, &mut redis_con, false, &settings.secret)
+                .await?;
+
+        let mut statuses = vec![];
+        for t in [&confirmation_token, &token, &token] {
+            let req = TestRequest::post()
+                .uri("/password/reset")
+                .set_json(json!({ "token": t, "password": "NewPassword123!" }))
+                .to_request();
+            statuses.push(
+                actix_web::test::call_service(&mut server, req)
+                    .await
+                    .status(),
+            );
+        }
+
+        let (stored, changed_at) = users
+            .filter(id.eq(user))
+            .select((password, password_changed_at))
+            .get_result::<(Option<String>, Option<time::OffsetDateTime>)>(&mut con)
+            .await?;
+
+        diesel::delete(users.filter(id.eq(user)))
+            .execute(&mut con)
+            .await?;
+
+        // Each token can only be used once
+        assert_eq!(
+            statuses,
+            vec![
+                StatusCode::BAD_REQUEST,
+                StatusCode::OK,
+                StatusCode::BAD_REQUEST
+            ]
+        );
+        verify_password(
+            &stored.expect("Password wasn't set"),
+            &SecretString::new("NewPassword123!".into()),
+        )
+        .expect("New password wasn't stored");
+        assert!(changed_at.is_some());
+
+        Ok(())
+    }
+}
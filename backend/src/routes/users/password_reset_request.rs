@@ -0,0 +1,239 @@
+use actix_web::{
+    post,
+    web::{Data, Json},
+    HttpRequest, HttpResponse,
+};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::{pooled_connection::bb8::Pool, AsyncPgConnection, RunQueryDsl};
+use uuid::Uuid;
+
+use crate::{
+    types::{ErrorResponse, ErrorTranslationKey, SuccessResponse},
+    utils::{sql::lower, throttle},
+};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+/// Reset emails allowed per client IP address and per email address
+const LIMITS: throttle::EmailLimits = throttle::EmailLimits {
+    ip: 10,
+    email: 3,
+    window_seconds: 60 * 60,
+};
+
+#[derive(serde::Deserialize, Debug)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct PasswordResetRequest {
+    #[cfg_attr(feature = "openapi", schema(example = "developer@example.com"))]
+    email: String,
+}
+
+#[cfg_attr(feature = "openapi", utoipa::path(
+    path = "/users/password/reset-request",
+    request_body = PasswordResetRequest,
+    responses(
+        (
+            status = 200,
+            description = "A password reset link was sent, if an active account exists for the email address",
+        ),
+        (status = 429, body = ErrorResponse),
+    )
+))]
+#[cfg_attr(not(coverage), tracing::instrument(name = "Requesting a password reset",
+skip(req, pool, request, redis_pool),
+fields(
+    user_email = %request.email,
+)))]
+#[post("/password/reset-request")]
+pub async fn request_password_reset(
+    req: HttpRequest,
+    pool: Data<Pool<AsyncPgConnection>>,
+    request: Json<PasswordResetRequest>,
+    redis_pool: Data<deadpool_redis::Pool>,
+) -> HttpResponse {
+    let mut redis_con = match redis_pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get redis connection for password reset: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+                translation_key: ErrorTranslationKey::GenericServerProblem,
+            });
+        }
+    };
+
+    let email = request.email.trim().to_lowercase();
+    let settings = common::settings::get_settings().expect("Failed to read settings.");
+    let ip = throttle::client_ip(&req, &settings.application.trusted_proxies);
+
+    let allowed =
+        throttle::check_ip_and_email(&mut redis_con, "password_reset", &ip, &email, &LIMITS).await;
+
+    match allowed {
+        Ok(true) => {}
+        Ok(false) => {
+            return HttpResponse::TooManyRequests().json(ErrorResponse {
+                error: "Too many password resets have been requested. Kindly try again later"
+                    .to_string(),
+                translation_key: ErrorTranslationKey::PasswordResetThrottled,
+            });
+        }
+        Err(e) => {
+            tracing::error!("Error checking password reset throttle: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+                translation_key: ErrorTranslationKey::GenericServerProblem,
+            });
+        }
+    }
+
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for password reset: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+                translation_key: ErrorTranslationKey::GenericServerProblem,
+            });
+        }
+    };
+
+    // Always respond the same way, so this can't be used to find out who has an account
+    let success = HttpResponse::Ok().json(SuccessResponse {
+        message: "If an account exists for that email address, we just sent it a link to reset your password".to_string(),
+    });
+
+    let (user_id, user_email) = match get_active_user_by_email(&mut con, &email).await {
+        Ok(Some(u)) => u,
+        Ok(None) => return success,
+        Err(e) => {
+            tracing::error!("Error getting user for password reset: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "Something unexpected happened. Kindly try again.".to_string(),
+                translation_key: ErrorTranslationKey::GenericServerProblem,
+            });
+        }
+    };
+
+    if let Err(e) = crate::utils::emails::send_multipart_email(
+        "Reset your password".to_string(),
+        user_id,
+        Some(String::from("accounts@elementary.io")),
+        user_email,
+        "password_reset_email.html",
+        &mut redis_con,
+    )
+    .await
+    {
+        tracing::error!("Error sending password reset email: {}", e);
+        return HttpResponse::InternalServerError().json(ErrorResponse {
+            error: "Something unexpected happened. Kindly try again.".to_string(),
+            translation_key: ErrorTranslationKey::GenericServerProblem,
+        });
+    }
+
+    success
+}
+
+/// Looks up an active user by their email address, ignoring case
+async fn get_active_user_by_email(
+    con: &mut AsyncPgConnection,
+    user_email: &str,
+) -> Result<Option<(Uuid, String)>, diesel::result::Error> {
+    use common::schema::users::dsl::*;
+    use diesel::OptionalExtension;
+
+    users
+        .filter(lower(email).eq(user_email))
+        .filter(is_active.eq(true))
+        .filter(deactivated_at.is_null())
+        .select((id, email))
+        .get_result::<(Uuid, String)>(con)
+        .await
+        .optional()
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http::StatusCode, test::TestRequest, App};
+    use diesel_async::AsyncConnection;
+    use serde_json::json;
+
+    use crate::utils::db_test::{create_user, db_pool, redis_pool};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn users_are_found_ignoring_case() -> anyhow::Result<()> {
+        use common::schema::users::dsl::*;
+
+        let db_pool = db_pool().await;
+        let mut con = db_pool.get().await?;
+
+        con.begin_test_transaction().await?;
+
+        let active = create_user(&mut con, true).await?;
+        let inactive = create_user(&mut con, false).await?;
+
+        let email_for = |user: Uuid| users.filter(id.eq(user)).select(email);
+        let active_email = email_for(active).get_result::<String>(&mut con).await?;
+        let inactive_email = email_for(inactive).get_result::<String>(&mut con).await?;
+
+        diesel::update(users.filter(id.eq(active)))
+            .set(email.eq(active_email.to_uppercase()))
+            .execute(&mut con)
+            .await?;
+
+        assert_eq!(
+            get_active_user_by_email(&mut con, &active_email).await?,
+            Some((active, active_email.to_uppercase()))
+        );
+        assert_eq!(
+            get_active_user_by_email(&mut con, &inactive_email).await?,
+            None
+        );
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_reset_requests_are_throttled() {
+        let mut server = actix_web::test::init_service(
+            App::new()
+                .service(request_password_reset)
+                .app_data(Data::new(db_pool().await))
+                .app_data(Data::new(redis_pool())),
+        )
+        .await;
+
+        let address = format!("{}@example.com", Uuid::new_v4());
+        // A fresh client address each run, so earlier runs don't count towards the IP limit
+        let peer = format!("[2001:db8::{:x}]:1234", Uuid::new_v4().as_u128() as u16);
+
+        let mut statuses = vec![];
+        // Changing the case of the address doesn't get around the limit
+        for e in [&address, &address, &address.to_uppercase(), &address] {
+            let req = TestRequest::post()
+                .uri("/password/reset-request")
+                .peer_addr(peer.parse().unwrap())
+                .set_json(json!({ "email": e }))
+                .to_request();
+            statuses.push(
+                actix_web::test::call_service(&mut server, req)
+                    .await
+                    .status(),
+            );
+        }
+
+        assert_eq!(
+            statuses,
+            vec![
+                StatusCode::OK,
+                StatusCode::OK,
+                StatusCode::OK,
+                StatusCode::TOO_MANY_REQUESTS
+            ]
+        );
+    }
+}
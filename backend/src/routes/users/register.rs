@@ -42,6 +42,10 @@ pub async fn register_user(
         }
     };
 
+    if let Err(e) = crate::utils::auth::password::validate(&new_user.password) {
+        return actix_web::HttpResponse::BadRequest().json(e);
+    }
+
     let hashed_password = crate::utils::auth::password::hash(&new_user.0.password);
 
     let user = NewUser {
@@ -97,9 +97,11 @@ async fn run(
             )
             .wrap(cors)
             .service(crate::routes::health_check)
+            .configure(crate::routes::admin_routes_config)
             .configure(crate::routes::auth_routes_config)
             .configure(crate::routes::apps_routes_config)
             .configure(crate::routes::dashboard_routes_config)
+            .configure(crate::routes::github_routes_config)
             .configure(crate::routes::payments_routes_config)
             .service(fs::Files::new("/static/apps", "_apps"))
             .app_data(pool.clone())
@@ -120,9 +122,11 @@ async fn run(
                 .build(),
             )
             .service(crate::routes::health_check)
+            .configure(crate::routes::admin_routes_config)
             .configure(crate::routes::auth_routes_config)
             .configure(crate::routes::apps_routes_config)
             .configure(crate::routes::dashboard_routes_config)
+            .configure(crate::routes::github_routes_config)
             .configure(crate::routes::payments_routes_config)
             .service(fs::Files::new("/static/apps", "_apps"))
             .app_data(pool.clone())
@@ -0,0 +1,57 @@
+use common::models::AuditAction;
+use serde::Deserialize;
+#[cfg(feature = "openapi")]
+use utoipa::{IntoParams, ToSchema};
+
+use uuid::Uuid;
+
+use super::pagination::{DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE};
+
+#[derive(Deserialize, Debug)]
+#[cfg_attr(feature = "openapi", derive(IntoParams))]
+pub struct UserSearchParameters {
+    /// Only return users whose email address contains this text, ignoring case
+    pub q: Option<String>,
+    /// The `next` cursor returned with the previous page
+    pub after: Option<String>,
+    /// The number of users to return, between 1 and 100. Defaults to 20
+    pub limit: Option<i64>,
+}
+
+impl UserSearchParameters {
+    pub fn page_size(&self) -> i64 {
+        self.limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct Verification {
+    #[cfg_attr(feature = "openapi", schema(example = true))]
+    pub verified: bool,
+}
+
+#[derive(Deserialize, Debug)]
+#[cfg_attr(feature = "openapi", derive(IntoParams))]
+pub struct AuditEventParameters {
+    /// Only return events caused by this user
+    pub actor_id: Option<Uuid>,
+    /// Only return events about this app
+    pub app_id: Option<String>,
+    /// Only return events of this kind
+    pub action: Option<AuditAction>,
+    /// The `next` cursor returned with the previous page
+    pub after: Option<String>,
+    /// The number of events to return, between 1 and 100. Defaults to 20
+    pub limit: Option<i64>,
+}
+
+impl AuditEventParameters {
+    pub fn page_size(&self) -> i64 {
+        self.limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE)
+    }
+}
@@ -1,6 +1,8 @@
-use serde::Deserialize;
+use common::models::{AccessToken, AppOwnerRole, TokenScope};
+use serde::{Deserialize, Serialize};
 #[cfg(feature = "openapi")]
 use utoipa::ToSchema;
+use uuid::Uuid;
 
 #[derive(Debug, Deserialize)]
 #[cfg_attr(feature = "openapi", derive(ToSchema))]
@@ -30,3 +32,177 @@ pub struct AppUpdateSubmission {
     #[cfg_attr(feature = "openapi", schema(example = "3.0.0"))]
     pub version_tag: String,
 }
+
+#[derive(Debug, PartialEq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AppEarnings {
+    #[cfg_attr(
+        feature = "openapi",
+        schema(example = "com.github.davidmhewitt.torrential")
+    )]
+    pub app_id: String,
+    /// Calendar month (UTC) the payments were made in
+    #[cfg_attr(feature = "openapi", schema(example = "2023-08"))]
+    pub month: String,
+    #[cfg_attr(feature = "openapi", schema(example = "usd"))]
+    pub currency: String,
+    /// Number of payments that weren't fully refunded
+    #[cfg_attr(feature = "openapi", schema(example = 3))]
+    pub payments: i64,
+    /// Amount paid by buyers minus refunds, in the smallest unit of `currency`
+    #[cfg_attr(feature = "openapi", schema(example = 900))]
+    pub gross: i64,
+    #[cfg_attr(feature = "openapi", schema(example = 270))]
+    pub platform_fee: i64,
+    #[cfg_attr(feature = "openapi", schema(example = 630))]
+    pub net: i64,
+}
+
+/// Overrides of the platform fee policy for a single app, unset fields use the global policy
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AppFeeOverride {
+    /// Share of each payment, from 0 to 100
+    #[cfg_attr(feature = "openapi", schema(example = 10.0))]
+    pub percentage: Option<f64>,
+    /// Smallest fee charged, in whole units of the payment's currency
+    #[cfg_attr(feature = "openapi", schema(example = 0.0))]
+    pub minimum: Option<f64>,
+    /// Largest fee charged, in whole units of the payment's currency
+    pub maximum: Option<f64>,
+}
+
+impl AppFeeOverride {
+    pub fn is_valid(&self) -> bool {
+        let non_negative = [self.percentage, self.minimum, self.maximum]
+            .iter()
+            .flatten()
+            .all(|v| v.is_finite() && *v >= 0.0);
+
+        non_negative
+            && self.percentage.unwrap_or_default() <= 100.0
+            && match (self.minimum, self.maximum) {
+                (Some(min), Some(max)) => min <= max,
+                _ => true,
+            }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AppPricing {
+    /// Price shown to buyers by default, in the smallest unit of `currency`
+    #[cfg_attr(feature = "openapi", schema(example = 300))]
+    pub suggested_price: Option<i32>,
+    /// Smallest payment accepted, in the smallest unit of `currency`
+    #[cfg_attr(feature = "openapi", schema(example = 100))]
+    pub minimum_price: i32,
+    /// ISO code of the currency the prices are given in
+    #[cfg_attr(feature = "openapi", schema(example = "usd"))]
+    pub currency: String,
+    /// Whether buyers can download the app without paying
+    #[cfg_attr(feature = "openapi", schema(example = true))]
+    pub free_allowed: bool,
+}
+
+impl AppPricing {
+    pub fn is_valid(&self) -> bool {
+        self.minimum_price >= 0
+            && self.suggested_price.unwrap_or(self.minimum_price) >= self.minimum_price
+            && (self.free_allowed || self.minimum_price > 0)
+    }
+}
+
+/// Challenge proving control of the domain an app ID belongs to, by serving `token` at
+/// `well_known_url` or adding a TXT record called `dns_record_name` with `token` as its value
+#[derive(Debug, PartialEq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct DomainChallenge {
+    #[cfg_attr(feature = "openapi", schema(example = "com.example.myapp"))]
+    pub app_id: String,
+    #[cfg_attr(feature = "openapi", schema(example = "example.com"))]
+    pub domain: String,
+    #[cfg_attr(
+        feature = "openapi",
+        schema(example = "appcenter-verification=4f1c9a7e2b3d8c6f0a5e1d7b9c2f4a6e")
+    )]
+    pub token: String,
+    #[cfg_attr(
+        feature = "openapi",
+        schema(example = "https://example.com/.well-known/appcenter-verification")
+    )]
+    pub well_known_url: String,
+    #[cfg_attr(
+        feature = "openapi",
+        schema(example = "_appcenter-verification.example.com")
+    )]
+    pub dns_record_name: String,
+    /// Whether ownership of the domain has been proven
+    #[cfg_attr(feature = "openapi", schema(example = false))]
+    pub verified: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AppOwnerInvitation {
+    #[cfg_attr(feature = "openapi", schema(example = "maintainer@example.com"))]
+    pub email: String,
+    /// Role given to the invitee, primary ownership can only be transferred to existing owners
+    #[cfg_attr(feature = "openapi", schema(example = "maintainer"))]
+    pub role: AppOwnerRole,
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AcceptAppInvitation {
+    /// The PASETO token from the invitation email
+    pub token: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AppOwnershipTransfer {
+    /// Verified owner of the app who becomes its primary owner
+    #[cfg_attr(
+        feature = "openapi",
+        schema(example = "9c5b94b1-35ad-49bb-b118-8e8fc24abf80")
+    )]
+    pub user_id: Uuid,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AppCoOwner {
+    #[cfg_attr(
+        feature = "openapi",
+        schema(example = "9c5b94b1-35ad-49bb-b118-8e8fc24abf80")
+    )]
+    pub user_id: Uuid,
+    #[cfg_attr(feature = "openapi", schema(example = "maintainer@example.com"))]
+    pub email: String,
+    #[cfg_attr(feature = "openapi", schema(example = "maintainer"))]
+    pub role: AppOwnerRole,
+    pub verified_owner: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct CreateAccessToken {
+    /// Reminder of what the token is used for
+    #[cfg_attr(feature = "openapi", schema(example = "Release pipeline"))]
+    pub name: String,
+    #[cfg_attr(feature = "openapi", schema(example = json!(["submit"])))]
+    pub scopes: Vec<TokenScope>,
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct CreatedAccessToken {
+    /// The secret to send as `Authorization: Bearer`, which is only ever shown this once
+    #[cfg_attr(
+        feature = "openapi",
+        schema(example = "appcenter_pat_6f1ed002ab5595859014ebf0951522d9")
+    )]
+    pub token: String,
+    pub access_token: AccessToken,
+}
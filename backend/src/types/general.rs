@@ -17,24 +17,98 @@ pub enum ErrorTranslationKey {
     UserAlreadyExists,
     #[serde(rename = "registration.no-email-permission")]
     RegistrationNoEmailPermission,
+    #[serde(rename = "registration.resend-throttled")]
+    ResendVerificationThrottled,
+    #[serde(rename = "password-reset.invalid-token")]
+    PasswordResetTokenInvalid,
+    #[serde(rename = "password-reset.throttled")]
+    PasswordResetThrottled,
+    #[serde(rename = "password.too-short")]
+    PasswordTooShort,
     #[serde(rename = "login.username-password-mismatch")]
     UsernamePasswordMismatch,
     #[serde(rename = "login.user-nonexistent")]
     UserDoesntExist,
+    #[serde(rename = "login.account-deactivated")]
+    AccountDeactivated,
     #[serde(rename = "logout.generic-problem")]
     GenericLogoutProblem,
     #[serde(rename = "add-app.generic-problem")]
     GenericAddAppProblem,
     #[serde(rename = "add-app.non-matching-github-rdnn")]
     AddAppNonMatchingGithubRDNN,
+    #[serde(rename = "add-app.non-matching-forge-rdnn")]
+    AddAppNonMatchingForgeRDNN,
     #[serde(rename = "add-app.invalid-repository-url")]
     AddAppInvalidRepositoryUrl,
     #[serde(rename = "submit-app-update.unable-to-get-url")]
     SubmitAppUpdateCannotGetUrl,
+    #[serde(rename = "submit-app-update.version-not-newer")]
+    SubmitAppUpdateVersionNotNewer,
+    #[serde(rename = "submit-app-update.tag-not-found")]
+    SubmitAppUpdateTagNotFound,
+    #[serde(rename = "submit-app-update.repository-unreachable")]
+    SubmitAppUpdateRepositoryUnreachable,
+    #[serde(rename = "submit-app-update.repository-not-allowed")]
+    SubmitAppUpdateRepositoryNotAllowed,
     #[serde(rename = "stripe-link.no-account")]
     StripeLinkNoAccount,
+    #[serde(rename = "generic.admin-only")]
+    AdminOnly,
+    #[serde(rename = "admin.cannot-deactivate-self")]
+    CannotDeactivateSelf,
+    #[serde(rename = "app-fee.invalid-policy")]
+    InvalidFeePolicy,
+    #[serde(rename = "generic.app-not-owned")]
+    AppNotOwned,
+    #[serde(rename = "app-pricing.invalid")]
+    InvalidAppPricing,
     #[serde(rename = "generic.app-not-found")]
     AppNotFound,
+    #[serde(rename = "generic.category-not-found")]
+    CategoryNotFound,
+    #[serde(rename = "generic.invalid-cursor")]
+    InvalidCursor,
+    #[serde(rename = "payments.unsupported-currency")]
+    UnsupportedCurrency,
+    #[serde(rename = "payments.amount-too-low")]
+    PaymentAmountTooLow,
+    #[serde(rename = "payments.amount-too-high")]
+    PaymentAmountTooHigh,
+    #[serde(rename = "payments.below-app-minimum")]
+    PaymentBelowAppMinimum,
+    #[serde(rename = "payments.currency-not-accepted")]
+    PaymentCurrencyNotAccepted,
+    #[serde(rename = "payments.payment-not-found")]
+    PaymentNotFound,
+    #[serde(rename = "webhook.invalid-signature")]
+    InvalidWebhookSignature,
+    #[serde(rename = "domain-verification.unavailable")]
+    DomainVerificationUnavailable,
+    #[serde(rename = "domain-verification.challenge-not-found")]
+    DomainChallengeNotFound,
+    #[serde(rename = "domain-verification.failed")]
+    DomainVerificationFailed,
+    #[serde(rename = "generic.insufficient-app-role")]
+    InsufficientAppRole,
+    #[serde(rename = "app-owners.already-owner")]
+    AlreadyAppOwner,
+    #[serde(rename = "app-owners.not-found")]
+    AppOwnerNotFound,
+    #[serde(rename = "app-owners.last-owner")]
+    LastAppOwner,
+    #[serde(rename = "app-owners.invalid-role")]
+    InvalidAppOwnerRole,
+    #[serde(rename = "app-invitation.invalid")]
+    AppInvitationInvalid,
+    #[serde(rename = "app-invitation.wrong-account")]
+    AppInvitationWrongAccount,
+    #[serde(rename = "access-tokens.invalid")]
+    InvalidAccessToken,
+    #[serde(rename = "access-tokens.not-found")]
+    AccessTokenNotFound,
+    #[serde(rename = "access-tokens.missing-scope")]
+    AccessTokenMissingScope,
 }
 
 #[derive(serde::Serialize)]
@@ -51,3 +125,5 @@ pub struct SuccessResponse {
 
 pub const USER_ID_KEY: &str = "user_id";
 pub const USER_EMAIL_KEY: &str = "user_email";
+/// When the session was started, in microseconds since the Unix epoch
+pub const USER_LOGGED_IN_AT_KEY: &str = "logged_in_at";
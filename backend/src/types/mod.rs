@@ -1,5 +1,7 @@
+pub mod admin;
 pub mod dashboard;
 pub mod general;
+pub mod pagination;
 pub mod payments;
 mod tokens;
 mod users;
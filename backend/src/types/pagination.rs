@@ -0,0 +1,45 @@
+#[cfg(feature = "openapi")]
+use common::models::{AuditEvent, ComponentSummary};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "openapi")]
+use utoipa::{IntoParams, ToSchema};
+
+#[cfg(feature = "openapi")]
+use super::UserVisible;
+
+pub const DEFAULT_PAGE_SIZE: i64 = 20;
+pub const MAX_PAGE_SIZE: i64 = 100;
+
+#[derive(Deserialize, Debug)]
+#[cfg_attr(feature = "openapi", derive(IntoParams))]
+pub struct PaginationParameters {
+    /// The `next` cursor returned with the previous page
+    pub after: Option<String>,
+    /// The number of items to return, between 1 and 100. Defaults to 20
+    pub limit: Option<i64>,
+}
+
+impl PaginationParameters {
+    pub fn page_size(&self) -> i64 {
+        self.limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE)
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(
+    feature = "openapi",
+    aliases(
+        AppIdPage = Page<String>,
+        AuditEventPage = Page<AuditEvent>,
+        ComponentSummaryPage = Page<ComponentSummary>,
+        UserPage = Page<UserVisible>
+    )
+)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Cursor to pass as `after` to get the next page, or `null` if this is the last page
+    pub next: Option<String>,
+}
@@ -1,12 +1,35 @@
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "openapi")]
-use utoipa::IntoParams;
+use utoipa::{IntoParams, ToSchema};
 
 #[derive(Deserialize, Serialize, Debug)]
 #[cfg_attr(feature = "openapi", derive(IntoParams))]
 pub struct AppPaymentRequest {
     pub app_name: String,
     pub app_id: String,
-    /// The amount to pay for the app in cents USD
+    /// The amount to pay for the app in the smallest unit of `currency`, e.g. cents or yen
     pub amount: u32,
+    /// Lowercase ISO 4217 code of the currency to pay in, defaults to `usd`
+    pub currency: Option<String>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct PaymentReceipt {
+    #[cfg_attr(feature = "openapi", schema(example = "cs_test_a1b2c3d4e5f6g7h8i9j0"))]
+    pub checkout_session_id: String,
+    #[cfg_attr(
+        feature = "openapi",
+        schema(example = "com.github.davidmhewitt.torrential")
+    )]
+    pub app_id: Option<String>,
+    /// The amount paid in the smallest unit of `currency`
+    #[cfg_attr(feature = "openapi", schema(example = 300))]
+    pub amount: i64,
+    #[cfg_attr(feature = "openapi", schema(example = "usd"))]
+    pub currency: String,
+    pub amount_refunded: i64,
+    /// `pending` until Stripe confirms the payment, then `paid`, `partially_refunded` or `refunded`
+    #[cfg_attr(feature = "openapi", schema(example = "paid"))]
+    pub status: String,
 }
@@ -1,9 +1,12 @@
-#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct UserVisible {
     pub id: uuid::Uuid,
     pub email: String,
     pub is_active: bool,
     pub is_admin: bool,
+    /// Whether an administrator has deactivated the account
+    pub is_deactivated: bool,
 }
 
 #[derive(serde::Serialize)]
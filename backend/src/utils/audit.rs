@@ -0,0 +1,61 @@
+use common::models::NewAuditEvent;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+/// Adds an event to the audit log. The action has already happened by the time it's recorded, so
+/// failing to record it is logged instead of failing the request.
+pub async fn record(con: &mut AsyncPgConnection, event: NewAuditEvent<'_>) {
+    use common::schema::audit_events;
+
+    let action = event.action;
+    if let Err(e) = diesel::insert_into(audit_events::table)
+        .values(event)
+        .execute(con)
+        .await
+    {
+        tracing::error!("Error recording {} audit event: {}", action.as_str(), e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::models::{AuditAction, AuditEvent};
+    use diesel::{ExpressionMethods, QueryDsl};
+    use diesel_async::AsyncConnection;
+    use serde_json::json;
+
+    use crate::utils::db_test::{create_user, db_pool};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_record() -> anyhow::Result<()> {
+        use common::schema::audit_events;
+
+        let db_pool = db_pool().await;
+        let mut con = db_pool.get().await?;
+
+        con.begin_test_transaction().await?;
+
+        let user = create_user(&mut con, true).await?;
+
+        record(
+            &mut con,
+            NewAuditEvent::new(&user, AuditAction::AppAdded)
+                .app("com.example.audited")
+                .details(json!({ "verified": false })),
+        )
+        .await;
+
+        let event = audit_events::table
+            .filter(audit_events::actor_id.eq(user))
+            .get_result::<AuditEvent>(&mut con)
+            .await?;
+
+        assert_eq!(event.action, AuditAction::AppAdded);
+        assert_eq!(event.app_id.as_deref(), Some("com.example.audited"));
+        assert_eq!(event.target_user_id, None);
+        assert_eq!(event.details, json!({ "verified": false }));
+
+        Ok(())
+    }
+}
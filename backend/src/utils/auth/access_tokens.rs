@@ -0,0 +1,183 @@
+use actix_web::http::Method;
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use common::models::{TokenScope, User};
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl};
+use diesel_async::{pooled_connection::bb8::Pool, AsyncPgConnection, RunQueryDsl};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// Makes personal access tokens easy to recognise, e.g. by secret scanners
+const TOKEN_PREFIX: &str = "appcenter_pat_";
+
+/// Endpoints that accept personal access tokens, and the scope each one needs. Every other
+/// endpoint, including managing the tokens themselves, is only available to signed in users.
+const SCOPED_ENDPOINTS: &[(Method, &str, TokenScope)] = &[
+    (
+        Method::POST,
+        "/api/dashboard/submit_app_update",
+        TokenScope::Submit,
+    ),
+    (Method::GET, "/api/dashboard/apps", TokenScope::ReadApps),
+    (
+        Method::GET,
+        "/api/dashboard/submissions",
+        TokenScope::ReadApps,
+    ),
+];
+
+pub fn generate_access_token() -> String {
+    let mut buff = [0_u8; 32];
+    OsRng.fill_bytes(&mut buff);
+    format!("{}{}", TOKEN_PREFIX, hex::encode(buff))
+}
+
+/// Tokens are long and random, so a plain SHA-256 is enough to store them safely while still
+/// letting them be looked up by their hash
+pub fn hash_access_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// The scope a token needs to be used for the endpoint matching `pattern`, or `None` if tokens
+/// can't be used for it at all
+pub fn required_scope(method: &Method, pattern: &str) -> Option<TokenScope> {
+    SCOPED_ENDPOINTS
+        .iter()
+        .find(|(m, p, _)| m == method && *p == pattern)
+        .map(|(_, _, scope)| *scope)
+}
+
+/// Finds the active user a token belongs to along with the token's scopes, and notes that the
+/// token was used
+#[cfg_attr(
+    not(coverage),
+    tracing::instrument(name = "Check access token", skip_all)
+)]
+pub async fn check_access_token(
+    pool: &Pool<AsyncPgConnection>,
+    token: &str,
+) -> Option<(User, Vec<TokenScope>)> {
+    let mut con = match pool.get().await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("Unable to get DB connection for access token: {}", e);
+            return None;
+        }
+    };
+
+    match find_token_user(&mut con, &hash_access_token(token)).await {
+        Ok(user) => user,
+        Err(e) => {
+            tracing::error!("Error checking personal access token: {}", e);
+            None
+        }
+    }
+}
+
+async fn find_token_user(
+    con: &mut AsyncPgConnection,
+    token_hash: &str,
+) -> Result<Option<(User, Vec<TokenScope>)>, diesel::result::Error> {
+    use common::schema::{access_tokens, users};
+
+    let Some((user, token_id, scopes)) = access_tokens::table
+        .inner_join(users::table)
+        .filter(access_tokens::token_hash.eq(token_hash))
+        .filter(users::is_active.eq(true))
+        .filter(users::deactivated_at.is_null())
+        .select((users::all_columns, access_tokens::id, access_tokens::scopes))
+        .get_result::<(User, Uuid, Vec<TokenScope>)>(con)
+        .await
+        .optional()?
+    else {
+        return Ok(None);
+    };
+
+    diesel::update(access_tokens::table.filter(access_tokens::id.eq(token_id)))
+        .set(access_tokens::last_used_at.eq(time::OffsetDateTime::now_utc()))
+        .execute(con)
+        .await?;
+
+    Ok(Some((user, scopes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use common::models::NewAccessToken;
+    use diesel_async::AsyncConnection;
+
+    use crate::utils::db_test::{create_user, db_pool};
+
+    use super::*;
+
+    #[test]
+    fn test_generate_and_hash() {
+        let token = generate_access_token();
+        assert!(token.starts_with(TOKEN_PREFIX));
+        assert_ne!(token, generate_access_token());
+
+        assert_eq!(hash_access_token(&token), hash_access_token(&token));
+        assert_eq!(hash_access_token(&token).len(), 64);
+    }
+
+    #[test]
+    fn test_required_scope() {
+        assert_eq!(
+            required_scope(&Method::POST, "/api/dashboard/submit_app_update"),
+            Some(TokenScope::Submit)
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/api/dashboard/apps"),
+            Some(TokenScope::ReadApps)
+        );
+        assert_eq!(required_scope(&Method::POST, "/api/dashboard/apps"), None);
+        assert_eq!(
+            required_scope(&Method::POST, "/api/dashboard/access_tokens"),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_find_token_user() -> anyhow::Result<()> {
+        use common::schema::{access_tokens, users};
+
+        let db_pool = db_pool().await;
+        let mut con = db_pool.get().await?;
+
+        con.begin_test_transaction().await?;
+
+        let user = create_user(&mut con, true).await?;
+        let token_hash = hash_access_token(&generate_access_token());
+
+        diesel::insert_into(access_tokens::table)
+            .values(NewAccessToken {
+                user_id: &user,
+                name: "CI",
+                token_hash: &token_hash,
+                scopes: &[TokenScope::Submit],
+            })
+            .execute(&mut con)
+            .await?;
+
+        let (found, scopes) = find_token_user(&mut con, &token_hash).await?.unwrap();
+        assert_eq!(found.id, user);
+        assert_eq!(scopes, vec![TokenScope::Submit]);
+
+        let last_used = access_tokens::table
+            .filter(access_tokens::user_id.eq(user))
+            .select(access_tokens::last_used_at)
+            .get_result::<Option<time::OffsetDateTime>>(&mut con)
+            .await?;
+        assert!(last_used.is_some());
+
+        let unknown = hash_access_token(&generate_access_token());
+        assert!(find_token_user(&mut con, &unknown).await?.is_none());
+
+        diesel::update(users::table.filter(users::id.eq(user)))
+            .set(users::deactivated_at.eq(time::OffsetDateTime::now_utc()))
+            .execute(&mut con)
+            .await?;
+        assert!(find_token_user(&mut con, &token_hash).await?.is_none());
+
+        Ok(())
+    }
+}
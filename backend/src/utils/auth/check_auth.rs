@@ -26,15 +26,43 @@ pub async fn check_auth(
         Err(_) => return None,
     };
 
+    let logged_in_at = session
+        .get::<i64>(crate::types::USER_LOGGED_IN_AT_KEY)
+        .unwrap_or(None);
+
     if let Ok(user) = get_active_user_by_email_and_id(pool, &id, &email)
         .await
     {
+        if !session_is_current(logged_in_at, user.password_changed_at) {
+            session.purge();
+            return None;
+        }
+
         return Some(user);
     }
 
     None
 }
 
+/// The time a session was started at as stored in it, in microseconds since the Unix epoch.
+/// That's the precision the database keeps `password_changed_at` in, so a session started
+/// just before a password change in the same second doesn't outlive it.
+pub fn session_timestamp(at: time::OffsetDateTime) -> i64 {
+    (at.unix_timestamp_nanos() / 1_000) as i64
+}
+
+/// Sessions started before the password was last changed are no longer valid
+fn session_is_current(
+    logged_in_at: Option<i64>,
+    password_changed_at: Option<time::OffsetDateTime>,
+) -> bool {
+    match (logged_in_at, password_changed_at) {
+        (_, None) => true,
+        (None, Some(_)) => false,
+        (Some(logged_in_at), Some(changed_at)) => logged_in_at >= session_timestamp(changed_at),
+    }
+}
+
 #[cfg_attr(not(coverage), tracing::instrument(name = "Getting a user from DB.", skip(pool, user_email)))]
 async fn get_active_user_by_email_and_id(
     pool: &Pool<AsyncPgConnection>,
@@ -46,6 +74,7 @@ async fn get_active_user_by_email_and_id(
     let mut con = pool.get().await?;
     let result = users
         .filter(is_active.eq(true))
+        .filter(deactivated_at.is_null())
         .filter(id.eq(user_id))
         .filter(email.eq(user_email))
         .get_result::<User>(&mut con)
@@ -53,3 +82,31 @@ async fn get_active_user_by_email_and_id(
 
     Ok(result)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_is_current() {
+        let changed_at = time::macros::datetime!(2023-06-01 12:00:00.5 UTC);
+
+        assert!(session_is_current(None, None));
+        assert!(session_is_current(Some(0), None));
+        assert!(session_is_current(
+            Some(session_timestamp(changed_at)),
+            Some(changed_at)
+        ));
+        assert!(!session_is_current(
+            Some(session_timestamp(changed_at) - 1),
+            Some(changed_at)
+        ));
+        // Started earlier within the same second as the change
+        let same_second = time::macros::datetime!(2023-06-01 12:00:00.1 UTC);
+        assert!(!session_is_current(
+            Some(session_timestamp(same_second)),
+            Some(changed_at)
+        ));
+        assert!(!session_is_current(None, Some(changed_at)));
+    }
+}
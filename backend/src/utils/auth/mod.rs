@@ -1,5 +1,6 @@
+pub mod access_tokens;
 mod check_auth;
 pub mod password;
 pub mod tokens;
 
-pub use check_auth::check_auth;
+pub use check_auth::{check_auth, session_timestamp};
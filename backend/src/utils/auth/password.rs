@@ -4,6 +4,26 @@ use argon2::{
 };
 use secrecy::{ExposeSecret, SecretString};
 
+use crate::types::{ErrorResponse, ErrorTranslationKey};
+
+/// Matches the validation of the registration form in the frontend
+pub const MIN_PASSWORD_LENGTH: usize = 6;
+
+/// Checks a new password, whether chosen when registering or when resetting it
+pub fn validate(password: &str) -> Result<(), ErrorResponse> {
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(ErrorResponse {
+            error: format!(
+                "Passwords must be at least {} characters long",
+                MIN_PASSWORD_LENGTH
+            ),
+            translation_key: ErrorTranslationKey::PasswordTooShort,
+        });
+    }
+
+    Ok(())
+}
+
 pub fn hash(password: &str) -> String {
     let salt = SaltString::generate(&mut OsRng);
     Argon2::default()
@@ -29,4 +49,12 @@ mod tests {
         let hash = hash("password123");
         verify_password(&hash, &SecretString::new("password123".into()))
     }
+
+    #[test]
+    fn password_length() {
+        assert!(validate("").is_err());
+        assert!(validate("12345").is_err());
+        assert!(validate("123456").is_ok());
+        assert!(validate("äöüß€").is_err());
+    }
 }
@@ -93,7 +93,7 @@ pub async fn issue_confirmation_token_pasetors(
     };
 
     redis_connection
-        .set(
+        .set::<_, _, ()>(
             redis_key.clone(),
             // I just want to validate that the key exists to indicate the session is "live".
             String::new(),
@@ -107,7 +107,7 @@ pub async fn issue_confirmation_token_pasetors(
     let time_to_live = time::Duration::minutes(expiration_in_minutes);
 
     redis_connection
-        .expire(
+        .expire::<_, ()>(
             redis_key.clone(),
             time_to_live.whole_seconds().try_into().unwrap(),
         )
@@ -173,6 +173,69 @@ pub fn verify_paseto_token(
     })
 }
 
+/// How long app invitations can be accepted for, longer than confirmation tokens because the
+/// invitee may not have an account yet
+pub const INVITATION_EXPIRATION_DAYS: i64 = 7;
+
+/// Issues the token of an app invitation link. Invitations are single-use because accepting one
+/// deletes the invitation the token refers to.
+pub fn generate_invitation_token(invitation_id: Uuid, settings: &Secret) -> Result<String> {
+    let dt = time::OffsetDateTime::now_utc() + time::Duration::days(INVITATION_EXPIRATION_DAYS);
+
+    let mut claims = Claims::new()?;
+    claims.expiration(&dt.format(&well_known::Iso8601::DEFAULT)?)?;
+    claims.add_additional("invitation_id", serde_json::json!(invitation_id))?;
+
+    let sk = SymmetricKey::<V4>::from(
+        &general_purpose::STANDARD
+            .decode(settings.secret_key.expose_secret())
+            .expect("Unable to decode secret key for PASETO encryption"),
+    )?;
+
+    Ok(local::encrypt(
+        &sk,
+        &claims,
+        None,
+        Some(
+            &general_purpose::STANDARD
+                .decode(settings.hmac_secret.expose_secret())
+                .expect("Unable to decode HMAC secret for PASETO encryption"),
+        ),
+    )?)
+}
+
+pub fn verify_invitation_token(token: &str, settings: &Secret) -> Result<Uuid> {
+    let sk = SymmetricKey::<V4>::from(
+        &general_purpose::STANDARD
+            .decode(settings.secret_key.expose_secret())
+            .expect("Unable to decode secret key for PASETO encryption"),
+    )?;
+
+    let untrusted_token = UntrustedToken::<Local, V4>::try_from(token)?;
+    let trusted_token = local::decrypt(
+        &sk,
+        &untrusted_token,
+        &ClaimsValidationRules::new(),
+        None,
+        Some(
+            &general_purpose::STANDARD
+                .decode(settings.hmac_secret.expose_secret())
+                .expect("Unable to decode HMAC secret for PASETO decryption"),
+        ),
+    )?;
+
+    let claims = trusted_token
+        .payload_claims()
+        .ok_or(anyhow!("Couldn't get claims from token"))?;
+
+    Ok(serde_json::from_value::<Uuid>(
+        claims
+            .get_claim("invitation_id")
+            .ok_or(anyhow!("No invitation_id claim in token"))?
+            .clone(),
+    )?)
+}
+
 /// Verifies and destroys a token. A token is destroyed immediately
 /// it has successfully been verified and all encoded data extracted.
 /// Redis is used for such destruction.
@@ -205,7 +268,7 @@ pub async fn verify_confirmation_token_pasetor(
         return Err(anyhow!("Token has been used or expired."));
     }
     redis_connection
-        .del(redis_key.clone())
+        .del::<_, ()>(redis_key.clone())
         .await?;
 
     Ok(crate::types::ConfirmationToken { user_id: result.uuid })
@@ -297,4 +360,36 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_invitation_token() -> Result<()> {
+        let secret_key: String = {
+            let mut buff = [0_u8; 32];
+            OsRng.fill_bytes(&mut buff);
+            general_purpose::STANDARD.encode(buff)
+        };
+
+        let hmac_secret: String = {
+            let mut buff = [0_u8; 64];
+            OsRng.fill_bytes(&mut buff);
+            general_purpose::STANDARD.encode(buff)
+        };
+
+        let settings = Secret {
+            secret_key: SecretString::new(secret_key),
+            token_expiration: 30,
+            hmac_secret: SecretString::new(hmac_secret),
+        };
+
+        let invitation = uuid::Uuid::new_v4();
+        let token = generate_invitation_token(invitation, &settings)?;
+
+        assert_eq!(verify_invitation_token(&token, &settings)?, invitation);
+
+        // Other PASETO tokens can't be used to accept invitations
+        let session = generate_paseto_token(invitation, &settings)?;
+        assert!(verify_invitation_token(&session.token, &settings).is_err());
+
+        Ok(())
+    }
 }
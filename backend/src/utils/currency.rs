@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use stripe::Currency;
+
+/// Currencies Stripe expects amounts for in whole units rather than hundredths,
+/// see <https://stripe.com/docs/currencies#zero-decimal>
+const ZERO_DECIMAL_CURRENCIES: &[Currency] = &[
+    Currency::BIF,
+    Currency::CLP,
+    Currency::DJF,
+    Currency::GNF,
+    Currency::JPY,
+    Currency::KMF,
+    Currency::KRW,
+    Currency::MGA,
+    Currency::PYG,
+    Currency::RWF,
+    Currency::UGX,
+    Currency::VND,
+    Currency::VUV,
+    Currency::XAF,
+    Currency::XOF,
+    Currency::XPF,
+];
+
+/// Stripe doesn't accept amounts with more than eight digits in any currency
+const MAXIMUM_AMOUNT: i64 = 99_999_999;
+
+#[derive(Debug, PartialEq)]
+pub enum AmountError {
+    UnsupportedCurrency,
+    /// The amount was below the minimum, which is given in the smallest unit of the currency
+    BelowMinimum(i64),
+    AboveMaximum,
+}
+
+pub fn is_zero_decimal(currency: Currency) -> bool {
+    ZERO_DECIMAL_CURRENCIES.contains(&currency)
+}
+
+/// Converts an amount in whole units of `currency` to the smallest unit Stripe expects, rounding up
+pub fn to_smallest_unit(amount: f64, currency: Currency) -> i64 {
+    if is_zero_decimal(currency) {
+        amount.ceil() as i64
+    } else {
+        (amount * 100.0).ceil() as i64
+    }
+}
+
+/// Checks a payment of `amount` (in the smallest unit of `currency`) against the configured
+/// minimum amounts, which also act as the list of currencies accepted at checkout
+pub fn validate_amount(
+    amount: i64,
+    currency: &str,
+    minimum_amounts: &HashMap<String, f64>,
+) -> Result<Currency, AmountError> {
+    let code = currency.to_lowercase();
+    let minimum = minimum_amounts
+        .get(&code)
+        .ok_or(AmountError::UnsupportedCurrency)?;
+    let currency = code
+        .parse::<Currency>()
+        .map_err(|_| AmountError::UnsupportedCurrency)?;
+
+    let minimum = to_smallest_unit(*minimum, currency);
+    if amount < minimum {
+        return Err(AmountError::BelowMinimum(minimum));
+    }
+
+    if amount > MAXIMUM_AMOUNT {
+        return Err(AmountError::AboveMaximum);
+    }
+
+    Ok(currency)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_smallest_units() {
+        assert_eq!(to_smallest_unit(1.0, Currency::USD), 100);
+        assert_eq!(to_smallest_unit(0.5, Currency::EUR), 50);
+        assert_eq!(to_smallest_unit(150.0, Currency::JPY), 150);
+        assert_eq!(to_smallest_unit(0.5, Currency::JPY), 1);
+    }
+
+    #[test]
+    fn test_amount_validation() {
+        let minimums = HashMap::from([("usd".to_string(), 1.0), ("jpy".to_string(), 150.0)]);
+
+        assert_eq!(validate_amount(300, "usd", &minimums), Ok(Currency::USD));
+        assert_eq!(validate_amount(300, "USD", &minimums), Ok(Currency::USD));
+        assert_eq!(validate_amount(150, "jpy", &minimums), Ok(Currency::JPY));
+        assert_eq!(
+            validate_amount(99, "usd", &minimums),
+            Err(AmountError::BelowMinimum(100))
+        );
+        assert_eq!(
+            validate_amount(149, "jpy", &minimums),
+            Err(AmountError::BelowMinimum(150))
+        );
+        assert_eq!(
+            validate_amount(100_000_000, "usd", &minimums),
+            Err(AmountError::AboveMaximum)
+        );
+        assert_eq!(
+            validate_amount(300, "eur", &minimums),
+            Err(AmountError::UnsupportedCurrency)
+        );
+    }
+}
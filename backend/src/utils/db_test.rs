@@ -1,23 +1,65 @@
 use argon2::password_hash::rand_core::{OsRng, RngCore};
 use diesel::{Connection, ExpressionMethods, PgConnection};
-use diesel_async::{pooled_connection::bb8::Pool, AsyncPgConnection, RunQueryDsl};
+use diesel_async::{
+    pooled_connection::{bb8::Pool, AsyncDieselConnectionManager},
+    AsyncConnection, AsyncPgConnection, RunQueryDsl,
+};
 use diesel_migrations::MigrationHarness;
 
-use crate::startup::{async_connection_pool, MIGRATIONS};
-
-#[inline]
-pub async fn db_pool() -> Pool<AsyncPgConnection> {
-    let settings = common::settings::get_settings().expect("Failed to read settings.");
+use crate::{
+    startup::{async_connection_pool, MIGRATIONS},
+    utils::auth::access_tokens::{generate_access_token, hash_access_token},
+};
 
+fn run_migrations(settings: &common::settings::Settings) {
     let mut connection = PgConnection::establish(&settings.database.url)
         .expect("Unable to connect to database to run migrations");
     connection
         .run_pending_migrations(MIGRATIONS)
         .expect("Unable to run database migrations");
+}
+
+#[inline]
+pub async fn db_pool() -> Pool<AsyncPgConnection> {
+    let settings = common::settings::get_settings().expect("Failed to read settings.");
+
+    run_migrations(&settings);
 
     async_connection_pool(&settings.database).await
 }
 
+/// A pool with a single connection, which is inside a test transaction that's never committed.
+/// Handlers given this pool see each other's changes, but nothing is left in the database.
+pub async fn test_transaction_pool() -> Pool<AsyncPgConnection> {
+    let settings = common::settings::get_settings().expect("Failed to read settings.");
+
+    run_migrations(&settings);
+
+    let manager = AsyncDieselConnectionManager::<AsyncPgConnection>::new(&settings.database.url);
+    let pool = Pool::builder()
+        .max_size(1)
+        .build(manager)
+        .await
+        .expect("Unable to build database pool");
+
+    pool.get()
+        .await
+        .expect("Unable to get pool connection")
+        .begin_test_transaction()
+        .await
+        .expect("Unable to begin test transaction");
+
+    pool
+}
+
+pub fn redis_pool() -> deadpool_redis::Pool {
+    let settings = common::settings::get_settings().expect("Failed to read settings.");
+
+    deadpool_redis::Config::from_url(settings.redis.uri)
+        .create_pool(Some(deadpool_redis::Runtime::Tokio1))
+        .expect("Cannot create deadpool redis")
+}
+
 pub async fn create_user(con: &mut AsyncPgConnection, active: bool) -> anyhow::Result<uuid::Uuid> {
     use common::schema::users::dsl::*;
 
@@ -80,3 +122,24 @@ pub async fn create_app(
 
     Ok(random_id)
 }
+
+/// Creates a personal access token for the user, returning the token to send as a Bearer token
+pub async fn create_access_token(
+    con: &mut AsyncPgConnection,
+    user: &uuid::Uuid,
+    scopes: &[common::models::TokenScope],
+) -> anyhow::Result<String> {
+    let token = generate_access_token();
+
+    diesel::insert_into(common::schema::access_tokens::table)
+        .values(common::models::NewAccessToken {
+            user_id: user,
+            name: "Test",
+            token_hash: &hash_access_token(&token),
+            scopes,
+        })
+        .execute(con)
+        .await?;
+
+    Ok(token)
+}
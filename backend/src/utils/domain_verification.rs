@@ -0,0 +1,288 @@
+use std::{
+    net::{IpAddr, SocketAddr},
+    time::Duration,
+};
+
+use anyhow::{anyhow, Result};
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use common::network::check_public_addresses;
+use reqwest::{
+    header::{ACCEPT, USER_AGENT},
+    redirect,
+};
+use serde::Deserialize;
+
+/// Path the challenge token is served at on the app's domain
+pub const WELL_KNOWN_PATH: &str = "/.well-known/appcenter-verification";
+
+/// Subdomain of the app's domain holding the challenge in a TXT record
+pub const DNS_RECORD_PREFIX: &str = "_appcenter-verification";
+
+/// DNS over HTTPS resolver used to look up TXT records, with the JSON API described in
+/// <https://developers.cloudflare.com/1.1.1.1/encryption/dns-over-https/make-api-requests/dns-json/>
+pub const DNS_OVER_HTTPS_URL: &str = "https://cloudflare-dns.com/dns-query";
+
+/// The DNS record type number of TXT records
+const TXT_RECORD_TYPE: u16 = 16;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Largest well-known file read, a token file is a single short line
+const MAX_WELL_KNOWN_SIZE: usize = 4096;
+
+/// Works out the domain an app ID belongs to, e.g. `example.com` for `com.example.myapp`
+pub fn domain_for_app_id(app_id: &str) -> Option<String> {
+    let parts = app_id.split('.').collect::<Vec<_>>();
+    if parts.len() < 3 || parts.iter().any(|p| p.is_empty()) {
+        return None;
+    }
+
+    Some(
+        parts[..parts.len() - 1]
+            .iter()
+            .rev()
+            .copied()
+            .collect::<Vec<_>>()
+            .join(".")
+            .to_lowercase(),
+    )
+}
+
+pub fn new_challenge_token() -> String {
+    let mut buff = [0_u8; 16];
+    OsRng.fill_bytes(&mut buff);
+    format!("appcenter-verification={}", hex::encode(buff))
+}
+
+fn client() -> Result<reqwest::Client> {
+    Ok(reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .user_agent("elementary AppCenter Website")
+        .build()?)
+}
+
+/// Resolves `domain`, refusing IP literals and domains pointing at anything but public addresses
+async fn resolve_public_domain(domain: &str) -> Result<Vec<SocketAddr>> {
+    if domain
+        .trim_matches(|c| c == '[' || c == ']')
+        .parse::<IpAddr>()
+        .is_ok()
+    {
+        return Err(anyhow!("{} is an IP address rather than a domain", domain));
+    }
+
+    let addrs = tokio::net::lookup_host((domain, 443)).await?;
+
+    Ok(check_public_addresses(domain, addrs)?)
+}
+
+/// Checks whether the website of `domain` serves the token at the well-known path.
+/// The request only goes to the public addresses the domain was checked to resolve to,
+/// and redirects aren't followed, so the check can't be pointed at internal services
+pub async fn check_well_known(domain: &str, token: &str) -> Result<bool> {
+    let addrs = resolve_public_domain(domain).await?;
+
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .user_agent("elementary AppCenter Website")
+        .redirect(redirect::Policy::none())
+        .resolve_to_addrs(domain, &addrs)
+        .build()?;
+
+    serves_token(
+        &client,
+        &format!("https://{}{}", domain, WELL_KNOWN_PATH),
+        token,
+    )
+    .await
+}
+
+/// Fetches `url` and looks for the token on a line of its own, giving up on large responses
+async fn serves_token(client: &reqwest::Client, url: &str, token: &str) -> Result<bool> {
+    let mut response = client.get(url).send().await?;
+
+    if !response.status().is_success() {
+        return Ok(false);
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > MAX_WELL_KNOWN_SIZE {
+            return Err(anyhow!("Response from {} is too large", url));
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(String::from_utf8_lossy(&body)
+        .lines()
+        .any(|l| l.trim() == token))
+}
+
+#[derive(Deserialize)]
+struct DnsResponse {
+    #[serde(rename = "Answer", default)]
+    answer: Vec<DnsAnswer>,
+}
+
+#[derive(Deserialize)]
+struct DnsAnswer {
+    #[serde(rename = "type")]
+    kind: u16,
+    data: String,
+}
+
+/// Checks whether one of the TXT records of the domain's verification subdomain is the token
+pub async fn check_dns_txt(resolver_url: &str, domain: &str, token: &str) -> Result<bool> {
+    let response = client()?
+        .get(resolver_url)
+        .query(&[
+            ("name", format!("{}.{}", DNS_RECORD_PREFIX, domain)),
+            ("type", "TXT".into()),
+        ])
+        .header(ACCEPT, "application/dns-json")
+        .header(USER_AGENT, "elementary AppCenter Website")
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<DnsResponse>()
+        .await?;
+
+    // Resolvers return TXT data quoted, and split into strings of at most 255 characters
+    Ok(response
+        .answer
+        .iter()
+        .filter(|a| a.kind == TXT_RECORD_TYPE)
+        .any(|a| a.data.replace("\" \"", "").trim_matches('"') == token))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+
+    const TOKEN: &str = "appcenter-verification=0123456789abcdef";
+
+    #[test]
+    fn test_domain_for_app_id() {
+        assert_eq!(
+            domain_for_app_id("com.example.myapp"),
+            Some("example.com".into())
+        );
+        assert_eq!(
+            domain_for_app_id("io.Elementary.apps.Camera"),
+            Some("apps.elementary.io".into())
+        );
+        assert_eq!(domain_for_app_id("example.myapp"), None);
+        assert_eq!(domain_for_app_id("com..myapp"), None);
+    }
+
+    #[test]
+    fn test_new_challenge_token() {
+        let token = new_challenge_token();
+
+        assert!(token.starts_with("appcenter-verification="));
+        assert_ne!(token, new_challenge_token());
+    }
+
+    #[tokio::test]
+    async fn test_check_well_known_refuses_internal_hosts() {
+        for domain in ["127.0.0.1", "169.254.169.254", "[::1]", "localhost"] {
+            assert!(check_well_known(domain, TOKEN).await.is_err(), "{}", domain);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_serves_token() -> Result<()> {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path(WELL_KNOWN_PATH))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(format!("other-token\n{}\n", TOKEN)),
+            )
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/redirect"))
+            .respond_with(ResponseTemplate::new(302).insert_header("Location", WELL_KNOWN_PATH))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/large"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                "{}\n{}",
+                "a".repeat(MAX_WELL_KNOWN_SIZE),
+                TOKEN
+            )))
+            .mount(&mock_server)
+            .await;
+
+        let client = reqwest::Client::builder()
+            .redirect(redirect::Policy::none())
+            .build()?;
+        let url = |p: &str| format!("{}{}", mock_server.uri(), p);
+
+        assert!(serves_token(&client, &url(WELL_KNOWN_PATH), TOKEN).await?);
+        assert!(
+            !serves_token(
+                &client,
+                &url(WELL_KNOWN_PATH),
+                "appcenter-verification=other"
+            )
+            .await?
+        );
+        assert!(!serves_token(&client, &url("/redirect"), TOKEN).await?);
+        assert!(serves_token(&client, &url("/large"), TOKEN).await.is_err());
+
+        let empty_server = MockServer::start().await;
+        assert!(
+            !serves_token(
+                &client,
+                &format!("{}{}", empty_server.uri(), WELL_KNOWN_PATH),
+                TOKEN
+            )
+            .await?
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_check_dns_txt() -> Result<()> {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/dns-query"))
+            .and(query_param("name", "_appcenter-verification.example.com"))
+            .and(query_param("type", "TXT"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "Status": 0,
+                "Answer": [
+                    { "name": "_appcenter-verification.example.com", "type": 16, "TTL": 300, "data": "\"v=spf1 -all\"" },
+                    { "name": "_appcenter-verification.example.com", "type": 16, "TTL": 300, "data": format!("\"{}\"", TOKEN) },
+                ],
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/dns-query"))
+            .and(query_param("name", "_appcenter-verification.example.org"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "Status": 3 })))
+            .mount(&mock_server)
+            .await;
+
+        let resolver = format!("{}/dns-query", mock_server.uri());
+
+        assert!(check_dns_txt(&resolver, "example.com", TOKEN).await?);
+        assert!(!check_dns_txt(&resolver, "example.com", "appcenter-verification=other").await?);
+        assert!(!check_dns_txt(&resolver, "example.org", TOKEN).await?);
+
+        Ok(())
+    }
+}
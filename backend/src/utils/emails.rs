@@ -132,9 +132,10 @@ pub async fn send_multipart_email(
     };
     let confirmation_link = {
         if is_for_password_change {
+            // The frontend collects the new password and posts it with the token
             format!(
-                "{}/api/users/password/confirm/change_password?token={}",
-                web_address, issued_token,
+                "{}/password/reset?token={}",
+                settings.frontend_url, issued_token,
             )
         } else {
             format!(
@@ -153,13 +154,23 @@ pub async fn send_multipart_email(
     };
     let html_text = template.render(ctx).unwrap();
 
-    let text = format!(
-        r#"
+    let text = if is_for_password_change {
+        format!(
+            r#"
+        Tap the link below to reset your password.
+        {}
+        "#,
+            confirmation_link
+        )
+    } else {
+        format!(
+            r#"
         Tap the link below to confirm your email address.
         {}
         "#,
-        confirmation_link
-    );
+            confirmation_link
+        )
+    };
     tokio::spawn(send_email(
         sender_email,
         recipient_email,
@@ -0,0 +1,128 @@
+use common::settings::FeeSettings;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use stripe::Currency;
+
+use super::currency::to_smallest_unit;
+
+/// Calculates the platform fee for a payment of `amount`, given in the smallest unit of `currency`
+pub fn calculate_fee(amount: i64, currency: Currency, policy: &FeeSettings) -> i64 {
+    let mut fee = ((amount as f64) * policy.percentage / 100.0).round() as i64;
+
+    fee = fee.max(to_smallest_unit(policy.minimum, currency));
+    if let Some(maximum) = policy.maximum {
+        fee = fee.min(to_smallest_unit(maximum, currency));
+    }
+
+    // Stripe refuses application fees larger than the payment itself
+    fee.min(amount)
+}
+
+/// Gets the fee policy for an app, which is the global policy with any of the app's overrides applied
+pub async fn fee_policy_for_app(
+    con: &mut AsyncPgConnection,
+    app_id: &str,
+    global: &FeeSettings,
+) -> Result<FeeSettings, diesel::result::Error> {
+    use common::schema::apps::dsl::*;
+
+    let overrides = apps
+        .filter(id.eq(app_id))
+        .select((fee_percentage, fee_minimum, fee_maximum))
+        .get_result::<(Option<f64>, Option<f64>, Option<f64>)>(con)
+        .await
+        .optional()?;
+
+    Ok(match overrides {
+        Some((percentage, minimum, maximum)) => FeeSettings {
+            percentage: percentage.unwrap_or(global.percentage),
+            minimum: minimum.unwrap_or(global.minimum),
+            maximum: maximum.or(global.maximum),
+        },
+        None => global.to_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel_async::AsyncConnection;
+
+    use crate::utils::db_test::{create_app, db_pool};
+
+    use super::*;
+
+    const DEFAULT_POLICY: FeeSettings = FeeSettings {
+        percentage: 30.0,
+        minimum: 0.5,
+        maximum: None,
+    };
+
+    #[test]
+    fn test_fee_calculations() {
+        assert_eq!(50, calculate_fee(100, Currency::USD, &DEFAULT_POLICY));
+        assert_eq!(50, calculate_fee(150, Currency::USD, &DEFAULT_POLICY));
+        assert_eq!(51, calculate_fee(170, Currency::USD, &DEFAULT_POLICY));
+        assert_eq!(60, calculate_fee(200, Currency::USD, &DEFAULT_POLICY));
+        assert_eq!(150, calculate_fee(500, Currency::USD, &DEFAULT_POLICY));
+        assert_eq!(150, calculate_fee(500, Currency::EUR, &DEFAULT_POLICY));
+        assert_eq!(30, calculate_fee(30, Currency::USD, &DEFAULT_POLICY));
+    }
+
+    #[test]
+    fn test_zero_decimal_fee_calculations() {
+        assert_eq!(1, calculate_fee(1, Currency::JPY, &DEFAULT_POLICY));
+        assert_eq!(45, calculate_fee(150, Currency::JPY, &DEFAULT_POLICY));
+        assert_eq!(300, calculate_fee(1000, Currency::JPY, &DEFAULT_POLICY));
+    }
+
+    #[test]
+    fn test_capped_fee_calculations() {
+        let policy = FeeSettings {
+            percentage: 10.0,
+            minimum: 0.0,
+            maximum: Some(2.0),
+        };
+
+        assert_eq!(0, calculate_fee(4, Currency::USD, &policy));
+        assert_eq!(100, calculate_fee(1000, Currency::USD, &policy));
+        assert_eq!(200, calculate_fee(5000, Currency::USD, &policy));
+        assert_eq!(2, calculate_fee(5000, Currency::JPY, &policy));
+    }
+
+    #[tokio::test]
+    async fn test_app_fee_policy() -> anyhow::Result<()> {
+        use common::schema::apps;
+
+        let db_pool = db_pool().await;
+        let mut con = db_pool.get().await?;
+
+        con.begin_test_transaction().await?;
+
+        let app = create_app(&mut con, None, None).await?;
+
+        assert_eq!(
+            fee_policy_for_app(&mut con, &app, &DEFAULT_POLICY).await?,
+            DEFAULT_POLICY
+        );
+        assert_eq!(
+            fee_policy_for_app(&mut con, "com.example.missing", &DEFAULT_POLICY).await?,
+            DEFAULT_POLICY
+        );
+
+        diesel::update(apps::table.filter(apps::id.eq(&app)))
+            .set((apps::fee_percentage.eq(10.0), apps::fee_minimum.eq(0.0)))
+            .execute(&mut con)
+            .await?;
+
+        assert_eq!(
+            fee_policy_for_app(&mut con, &app, &DEFAULT_POLICY).await?,
+            FeeSettings {
+                percentage: 10.0,
+                minimum: 0.0,
+                maximum: None,
+            }
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,686 @@
+use anyhow::{anyhow, Result};
+use reqwest::header::{ACCEPT, USER_AGENT};
+use secrecy::{ExposeSecret, SecretString};
+use serde::Deserialize;
+use serde_json::Value;
+use url::Url;
+
+use crate::types::ErrorTranslationKey;
+
+/// GitLab's access level for maintainers, who can manage a project's releases and settings
+const GITLAB_MAINTAINER_ACCESS: u64 = 40;
+
+/// A user's account on a forge, as described by its API
+#[derive(Debug, PartialEq)]
+pub struct ForgeUser {
+    pub id: String,
+    /// The user's primary email address, only set if the forge has verified it
+    pub email: Option<String>,
+}
+
+/// A code forge hosting app repositories. Apps with a reverse domain name on the forge can be
+/// verified by signing in with an account that administers the matching repository.
+pub trait Forge: Sync {
+    /// Identifier used in URLs and stored with the user's tokens, e.g. `gitlab`
+    fn id(&self) -> &'static str;
+    /// Name shown to users
+    fn name(&self) -> &'static str;
+    /// Host repositories are served from
+    fn host(&self) -> &'static str;
+    /// Reverse domain name prefixes of apps hosted on the forge
+    fn rdnn_prefixes(&self) -> &'static [&'static str];
+    fn api_url(&self) -> &'static str;
+    fn authorize_url(&self) -> &'static str;
+    fn token_url(&self) -> &'static str;
+    /// Endpoint revoking access tokens, for forges that support RFC 7009 token revocation
+    fn revoke_url(&self) -> Option<&'static str> {
+        None
+    }
+    /// OAuth scopes needed to read the user's email address and repository permissions
+    fn scopes(&self) -> &'static [&'static str];
+    /// Whether users sign in with the generic forge login routes, GitHub has its own
+    fn generic_login(&self) -> bool {
+        true
+    }
+    /// Error returned when an app's reverse domain name doesn't match its repository
+    fn non_matching_rdnn_error(&self) -> ErrorTranslationKey {
+        ErrorTranslationKey::AddAppNonMatchingForgeRDNN
+    }
+    /// Path of the API endpoint describing the signed in user
+    fn user_path(&self) -> &'static str;
+    /// Path of the API endpoint listing the signed in user's email addresses, for forges that
+    /// don't say whether the address in the user's profile is verified
+    fn emails_path(&self) -> Option<&'static str> {
+        None
+    }
+    /// Path of the API endpoint describing a repository
+    fn repo_path(&self, owner: &str, repo: &str) -> String;
+    fn parse_user(&self, user: &Value) -> Option<ForgeUser>;
+    /// Whether the signed in user can administer a repository, given the API's description of it
+    fn can_administer(&self, repo: &Value) -> bool;
+}
+
+pub struct GitHub;
+
+impl Forge for GitHub {
+    fn id(&self) -> &'static str {
+        "github"
+    }
+
+    fn name(&self) -> &'static str {
+        "GitHub"
+    }
+
+    fn host(&self) -> &'static str {
+        "github.com"
+    }
+
+    fn rdnn_prefixes(&self) -> &'static [&'static str] {
+        &["com.github.", "io.github."]
+    }
+
+    fn api_url(&self) -> &'static str {
+        "https://api.github.com"
+    }
+
+    fn authorize_url(&self) -> &'static str {
+        "https://github.com/login/oauth/authorize"
+    }
+
+    fn token_url(&self) -> &'static str {
+        "https://github.com/login/oauth/access_token"
+    }
+
+    fn scopes(&self) -> &'static [&'static str] {
+        &["user:email", "read:org"]
+    }
+
+    fn generic_login(&self) -> bool {
+        false
+    }
+
+    fn non_matching_rdnn_error(&self) -> ErrorTranslationKey {
+        ErrorTranslationKey::AddAppNonMatchingGithubRDNN
+    }
+
+    fn user_path(&self) -> &'static str {
+        "/user"
+    }
+
+    fn emails_path(&self) -> Option<&'static str> {
+        Some("/user/emails")
+    }
+
+    fn repo_path(&self, owner: &str, repo: &str) -> String {
+        format!("/repos/{}/{}", owner, repo)
+    }
+
+    fn parse_user(&self, user: &Value) -> Option<ForgeUser> {
+        Some(ForgeUser {
+            id: user["id"].as_u64()?.to_string(),
+            email: None,
+        })
+    }
+
+    fn can_administer(&self, repo: &Value) -> bool {
+        repo["permissions"]["admin"].as_bool().unwrap_or_default()
+    }
+}
+
+pub struct GitLab;
+
+impl Forge for GitLab {
+    fn id(&self) -> &'static str {
+        "gitlab"
+    }
+
+    fn name(&self) -> &'static str {
+        "GitLab"
+    }
+
+    fn host(&self) -> &'static str {
+        "gitlab.com"
+    }
+
+    fn rdnn_prefixes(&self) -> &'static [&'static str] {
+        &["io.gitlab."]
+    }
+
+    fn api_url(&self) -> &'static str {
+        "https://gitlab.com/api/v4"
+    }
+
+    fn authorize_url(&self) -> &'static str {
+        "https://gitlab.com/oauth/authorize"
+    }
+
+    fn token_url(&self) -> &'static str {
+        "https://gitlab.com/oauth/token"
+    }
+
+    fn revoke_url(&self) -> Option<&'static str> {
+        Some("https://gitlab.com/oauth/revoke")
+    }
+
+    fn scopes(&self) -> &'static [&'static str] {
+        &["read_user", "read_api"]
+    }
+
+    fn user_path(&self) -> &'static str {
+        "/user"
+    }
+
+    fn repo_path(&self, owner: &str, repo: &str) -> String {
+        format!("/projects/{}%2F{}", owner, repo)
+    }
+
+    fn parse_user(&self, user: &Value) -> Option<ForgeUser> {
+        let confirmed = !user["confirmed_at"].is_null();
+
+        Some(ForgeUser {
+            id: user["id"].as_u64()?.to_string(),
+            email: user["email"]
+                .as_str()
+                .filter(|_| confirmed)
+                .map(|e| e.to_owned()),
+        })
+    }
+
+    fn can_administer(&self, repo: &Value) -> bool {
+        let permissions = &repo["permissions"];
+
+        ["project_access", "group_access"].iter().any(|access| {
+            permissions[access]["access_level"]
+                .as_u64()
+                .is_some_and(|level| level >= GITLAB_MAINTAINER_ACCESS)
+        })
+    }
+}
+
+pub struct Codeberg;
+
+impl Forge for Codeberg {
+    fn id(&self) -> &'static str {
+        "codeberg"
+    }
+
+    fn name(&self) -> &'static str {
+        "Codeberg"
+    }
+
+    fn host(&self) -> &'static str {
+        "codeberg.org"
+    }
+
+    fn rdnn_prefixes(&self) -> &'static [&'static str] {
+        &["org.codeberg."]
+    }
+
+    fn api_url(&self) -> &'static str {
+        "https://codeberg.org/api/v1"
+    }
+
+    fn authorize_url(&self) -> &'static str {
+        "https://codeberg.org/login/oauth/authorize"
+    }
+
+    fn token_url(&self) -> &'static str {
+        "https://codeberg.org/login/oauth/access_token"
+    }
+
+    fn scopes(&self) -> &'static [&'static str] {
+        &["read:user", "read:repository"]
+    }
+
+    fn user_path(&self) -> &'static str {
+        "/user"
+    }
+
+    fn emails_path(&self) -> Option<&'static str> {
+        Some("/user/emails")
+    }
+
+    fn repo_path(&self, owner: &str, repo: &str) -> String {
+        format!("/repos/{}/{}", owner, repo)
+    }
+
+    fn parse_user(&self, user: &Value) -> Option<ForgeUser> {
+        Some(ForgeUser {
+            id: user["id"].as_u64()?.to_string(),
+            email: None,
+        })
+    }
+
+    fn can_administer(&self, repo: &Value) -> bool {
+        repo["permissions"]["admin"].as_bool().unwrap_or_default()
+    }
+}
+
+/// Every forge apps can be verified on
+pub static FORGES: &[&dyn Forge] = &[&GitHub, &GitLab, &Codeberg];
+
+/// Finds the forge hosting an app, from the prefix of its reverse domain name
+pub fn forge_for_rdnn(rdnn: &str) -> Option<&'static dyn Forge> {
+    FORGES
+        .iter()
+        .find(|f| f.rdnn_prefixes().iter().any(|p| rdnn.starts_with(p)))
+        .copied()
+}
+
+/// Finds a forge from its identifier
+pub fn forge_by_id(id: &str) -> Option<&'static dyn Forge> {
+    FORGES.iter().find(|f| f.id() == id).copied()
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RdnnValidationResult {
+    /// The owner and name of the repository
+    Valid((String, String)),
+    Invalid((String, ErrorTranslationKey)),
+}
+
+/// Checks the repository URL of an app is on its forge, and matches the owner and name in its
+/// reverse domain name
+pub fn validate_forge_url_and_rdnn(
+    forge: &dyn Forge,
+    url: &Url,
+    rdnn: &str,
+) -> RdnnValidationResult {
+    let rdnn_parts = rdnn.split('.').collect::<Vec<&str>>();
+    if rdnn_parts.len() != 4 {
+        return RdnnValidationResult::Invalid((
+            format!(
+                "{} RDNNs must have exactly 4 sections/components",
+                forge.name()
+            ),
+            forge.non_matching_rdnn_error(),
+        ));
+    }
+
+    if url.host_str() != Some(forge.host()) {
+        return RdnnValidationResult::Invalid((
+            format!(
+                "{} RDNN repositories must be served from {}",
+                forge.name(),
+                forge.host()
+            ),
+            forge.non_matching_rdnn_error(),
+        ));
+    }
+
+    let path_segments = match url.path_segments() {
+        Some(s) => s.collect::<Vec<&str>>(),
+        None => Vec::new(),
+    };
+
+    if path_segments.len() != 2 {
+        return RdnnValidationResult::Invalid((
+            format!(
+                "Invalid {} repository URL passed in `git_repo_url`",
+                forge.name()
+            ),
+            ErrorTranslationKey::AddAppInvalidRepositoryUrl,
+        ));
+    }
+
+    let path_repo_name = path_segments[1]
+        .strip_suffix(".git")
+        .unwrap_or(path_segments[1]);
+
+    if rdnn_parts[2] != path_segments[0] {
+        return RdnnValidationResult::Invalid((
+            format!("RDNN owner doesn't match {} URL owner", forge.name()),
+            forge.non_matching_rdnn_error(),
+        ));
+    }
+
+    if rdnn_parts[3] != path_repo_name {
+        return RdnnValidationResult::Invalid((
+            format!("RDNN repo doesn't match {} URL repo", forge.name()),
+            forge.non_matching_rdnn_error(),
+        ));
+    }
+
+    RdnnValidationResult::Valid((path_segments[0].to_owned(), path_repo_name.to_owned()))
+}
+
+#[derive(Deserialize)]
+struct ForgeEmail {
+    email: String,
+    verified: bool,
+    primary: bool,
+}
+
+async fn api_get(url: &str, access_token: &SecretString) -> Result<reqwest::Response> {
+    Ok(reqwest::Client::new()
+        .get(url)
+        .bearer_auth(access_token.expose_secret())
+        .header(ACCEPT, "application/json")
+        .header(USER_AGENT, "elementary AppCenter Website")
+        .send()
+        .await?)
+}
+
+/// Gets the account the access token belongs to, with its verified primary email address
+pub async fn fetch_user(
+    forge: &dyn Forge,
+    api_url: &str,
+    access_token: &SecretString,
+) -> Result<ForgeUser> {
+    let user = api_get(&format!("{}{}", api_url, forge.user_path()), access_token)
+        .await?
+        .error_for_status()?
+        .json::<Value>()
+        .await?;
+
+    let mut user = forge
+        .parse_user(&user)
+        .ok_or(anyhow!("Unexpected user from {} API", forge.name()))?;
+
+    if let Some(emails_path) = forge.emails_path() {
+        user.email = api_get(&format!("{}{}", api_url, emails_path), access_token)
+            .await?
+            .error_for_status()?
+            .json::<Vec<ForgeEmail>>()
+            .await?
+            .into_iter()
+            .find(|e| e.primary && e.verified)
+            .map(|e| e.email);
+    }
+
+    Ok(user)
+}
+
+/// Revokes an access token issued to our OAuth application
+pub async fn revoke_token(
+    forge: &dyn Forge,
+    revoke_url: &str,
+    application: &common::settings::ForgeSettings,
+    access_token: &SecretString,
+) -> Result<()> {
+    let response = reqwest::Client::new()
+        .post(revoke_url)
+        .header(USER_AGENT, "elementary AppCenter Website")
+        .form(&[
+            ("client_id", application.client_id.as_str()),
+            ("client_secret", application.client_secret.expose_secret()),
+            ("token", access_token.expose_secret()),
+        ])
+        .send()
+        .await?;
+
+    // Revoking a token that's already invalid succeeds too
+    if !response.status().is_success() {
+        return Err(anyhow!(
+            "{} returned {} when revoking token",
+            forge.name(),
+            response.status()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks whether the account the access token belongs to can administer a repository
+pub async fn user_administers_repo(
+    forge: &dyn Forge,
+    api_url: &str,
+    access_token: &SecretString,
+    owner: &str,
+    repo: &str,
+) -> Result<bool> {
+    let response = api_get(
+        &format!("{}{}", api_url, forge.repo_path(owner, repo)),
+        access_token,
+    )
+    .await?;
+
+    // Forges hide private repositories the user can't see
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(false);
+    }
+
+    Ok(forge.can_administer(&response.error_for_status()?.json::<Value>().await?))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+
+    #[test]
+    fn test_forge_for_rdnn() {
+        let id = |rdnn| forge_for_rdnn(rdnn).map(|f| f.id());
+
+        assert_eq!(id("com.github.davidmhewitt.torrential"), Some("github"));
+        assert_eq!(id("io.github.elementary.camera"), Some("github"));
+        assert_eq!(id("io.gitlab.someone.app"), Some("gitlab"));
+        assert_eq!(id("org.codeberg.someone.app"), Some("codeberg"));
+        assert_eq!(id("io.elementary.camera"), None);
+    }
+
+    #[test]
+    fn test_rdnn_validation() {
+        let valid = |forge: &dyn Forge, url, rdnn| {
+            validate_forge_url_and_rdnn(forge, &Url::parse(url).expect("Couldn't parse URL"), rdnn)
+        };
+
+        assert_eq!(
+            valid(
+                &GitHub,
+                "https://github.com/davidmhewitt/torrential.git",
+                "com.github.davidmhewitt.torrential"
+            ),
+            RdnnValidationResult::Valid(("davidmhewitt".into(), "torrential".into()))
+        );
+        assert_eq!(
+            valid(
+                &GitLab,
+                "https://gitlab.com/someone/app",
+                "io.gitlab.someone.app"
+            ),
+            RdnnValidationResult::Valid(("someone".into(), "app".into()))
+        );
+        assert_eq!(
+            valid(
+                &Codeberg,
+                "https://codeberg.org/someone/app.git",
+                "org.codeberg.someone.app"
+            ),
+            RdnnValidationResult::Valid(("someone".into(), "app".into()))
+        );
+
+        assert!(matches!(
+            valid(
+                &GitLab,
+                "https://github.com/someone/app",
+                "io.gitlab.someone.app"
+            ),
+            RdnnValidationResult::Invalid(_)
+        ));
+        assert!(matches!(
+            valid(
+                &GitLab,
+                "https://gitlab.com/group/subgroup/app",
+                "io.gitlab.group.app"
+            ),
+            RdnnValidationResult::Invalid(_)
+        ));
+        assert!(matches!(
+            valid(
+                &Codeberg,
+                "https://codeberg.org/someone/other",
+                "org.codeberg.someone.app"
+            ),
+            RdnnValidationResult::Invalid((_, ErrorTranslationKey::AddAppNonMatchingForgeRDNN))
+        ));
+        assert!(matches!(
+            valid(
+                &GitHub,
+                "https://github.com/someone/other",
+                "com.github.someone.app"
+            ),
+            RdnnValidationResult::Invalid((_, ErrorTranslationKey::AddAppNonMatchingGithubRDNN))
+        ));
+        assert!(matches!(
+            valid(
+                &Codeberg,
+                "https://codeberg.org/someone/app",
+                "org.codeberg.someone"
+            ),
+            RdnnValidationResult::Invalid(_)
+        ));
+    }
+
+    #[test]
+    fn test_gitlab_permissions() {
+        let project = |project_access: Value, group_access: Value| {
+            json!({
+                "permissions": {
+                    "project_access": project_access,
+                    "group_access": group_access,
+                }
+            })
+        };
+
+        assert!(GitLab.can_administer(&project(json!({ "access_level": 50 }), Value::Null)));
+        assert!(GitLab.can_administer(&project(
+            json!({ "access_level": 30 }),
+            json!({ "access_level": 40 })
+        )));
+        assert!(!GitLab.can_administer(&project(json!({ "access_level": 30 }), Value::Null)));
+        assert!(!GitLab.can_administer(&json!({})));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_user() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        let token = SecretString::new("token".into());
+
+        Mock::given(method("GET"))
+            .and(path("/user"))
+            .and(header("Authorization", "Bearer token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 1234,
+                "login": "someone",
+                "email": "someone@example.com",
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/user/emails"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                { "email": "old@example.com", "verified": true, "primary": false },
+                { "email": "someone@example.com", "verified": true, "primary": true },
+            ])))
+            .mount(&mock_server)
+            .await;
+
+        assert_eq!(
+            fetch_user(&Codeberg, &mock_server.uri(), &token).await?,
+            ForgeUser {
+                id: "1234".into(),
+                email: Some("someone@example.com".into()),
+            }
+        );
+
+        // GitLab only has the one address, which must be confirmed
+        assert_eq!(
+            fetch_user(&GitLab, &mock_server.uri(), &token).await?,
+            ForgeUser {
+                id: "1234".into(),
+                email: None,
+            }
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_user_administers_repo() -> Result<()> {
+        let mock_server = MockServer::start().await;
+        let token = SecretString::new("token".into());
+
+        Mock::given(method("GET"))
+            .and(path("/repos/someone/app"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "full_name": "someone/app",
+                "permissions": { "admin": true, "push": true, "pull": true },
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/repos/someone/other"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "full_name": "someone/other",
+                "permissions": { "admin": false, "push": false, "pull": true },
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/projects/someone%2Fapp"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "path_with_namespace": "someone/app",
+                "permissions": {
+                    "project_access": { "access_level": 50 },
+                    "group_access": null,
+                },
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let uri = mock_server.uri();
+
+        assert!(user_administers_repo(&Codeberg, &uri, &token, "someone", "app").await?);
+        assert!(!user_administers_repo(&Codeberg, &uri, &token, "someone", "other").await?);
+        assert!(!user_administers_repo(&Codeberg, &uri, &token, "someone", "private").await?);
+        assert!(user_administers_repo(&GitLab, &uri, &token, "someone", "app").await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_revoke_token() -> Result<()> {
+        use wiremock::matchers::body_string_contains;
+
+        let mock_server = MockServer::start().await;
+        let application = common::settings::ForgeSettings {
+            client_id: "client".into(),
+            client_secret: SecretString::new("secret".into()),
+        };
+
+        Mock::given(method("POST"))
+            .and(path("/oauth/revoke"))
+            .and(body_string_contains("client_id=client"))
+            .and(body_string_contains("token=revoked"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+
+        let revoke_url = format!("{}/oauth/revoke", mock_server.uri());
+
+        revoke_token(
+            &GitLab,
+            &revoke_url,
+            &application,
+            &SecretString::new("revoked".into()),
+        )
+        .await?;
+        assert!(revoke_token(
+            &GitLab,
+            &format!("{}/oauth/missing", mock_server.uri()),
+            &application,
+            &SecretString::new("revoked".into()),
+        )
+        .await
+        .is_err());
+
+        Ok(())
+    }
+}
@@ -1,5 +1,15 @@
+pub mod audit;
 pub mod auth;
+pub mod currency;
+pub mod domain_verification;
 #[cfg(test)]
 pub mod db_test;
 pub mod emails;
+pub mod fees;
+pub mod forges;
+pub mod pagination;
+pub mod sql;
 pub mod stripe_test;
+pub mod throttle;
+pub mod versions;
+pub mod webhooks;
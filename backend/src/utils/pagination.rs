@@ -0,0 +1,88 @@
+use actix_web::HttpResponse;
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::types::{pagination::Page, ErrorResponse, ErrorTranslationKey};
+
+/// Position of the last item on a page, handed to clients as an opaque string.
+///
+/// Listings ordered by a timestamp store it alongside the ID so that apps sharing a
+/// timestamp are still paged through in a stable order.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Cursor {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<OffsetDateTime>,
+}
+
+impl Cursor {
+    pub fn encode(&self) -> String {
+        general_purpose::URL_SAFE_NO_PAD
+            .encode(serde_json::to_vec(self).expect("Unable to serialize pagination cursor"))
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let json = general_purpose::URL_SAFE_NO_PAD.decode(cursor).ok()?;
+        serde_json::from_slice(&json).ok()
+    }
+}
+
+/// Builds a page from rows queried with a limit of `page_size + 1`, using the extra row (if
+/// any) to detect whether there is a next page.
+pub fn into_page<T>(mut rows: Vec<T>, page_size: i64, cursor: impl Fn(&T) -> Cursor) -> Page<T> {
+    let page_size = usize::try_from(page_size).unwrap_or_default();
+
+    let next = if rows.len() > page_size {
+        rows.truncate(page_size);
+        rows.last().map(|r| cursor(r).encode())
+    } else {
+        None
+    };
+
+    Page { items: rows, next }
+}
+
+pub fn invalid_cursor_response() -> HttpResponse {
+    HttpResponse::BadRequest().json(ErrorResponse {
+        error: "The pagination cursor is invalid".into(),
+        translation_key: ErrorTranslationKey::InvalidCursor,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    #[test]
+    fn cursor_round_trip() {
+        let cursor = Cursor {
+            id: "com.example.foo".into(),
+            timestamp: Some(datetime!(2023-03-27 17:22:01.123456 UTC)),
+        };
+
+        assert_eq!(Cursor::decode(&cursor.encode()), Some(cursor));
+        assert_eq!(Cursor::decode("not a cursor"), None);
+    }
+
+    #[test]
+    fn paging() {
+        let cursor = |id: &&str| Cursor {
+            id: id.to_string(),
+            timestamp: None,
+        };
+
+        let page = into_page(vec!["a", "b", "c"], 2, cursor);
+        assert_eq!(page.items, vec!["a", "b"]);
+        assert_eq!(
+            page.next.as_deref().and_then(Cursor::decode),
+            Some(cursor(&"b"))
+        );
+
+        let page = into_page(vec!["a", "b"], 2, cursor);
+        assert_eq!(page.items, vec!["a", "b"]);
+        assert_eq!(page.next, None);
+    }
+}
@@ -0,0 +1,6 @@
+//! SQL functions diesel doesn't provide itself
+
+diesel::sql_function!(
+    /// Lowercases text, e.g. to compare email addresses ignoring case
+    fn lower(x: diesel::sql_types::Text) -> diesel::sql_types::Text
+);
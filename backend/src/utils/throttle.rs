@@ -0,0 +1,139 @@
+use std::net::IpAddr;
+
+use actix_web::HttpRequest;
+use deadpool_redis::redis::{AsyncCommands, RedisResult};
+
+const THROTTLE_KEY_PREFIX: &str = "throttle/";
+
+/// Counts an attempt at `action` by `subject` and reports whether it is still within `limit`
+/// attempts per `window_seconds`. The window starts with the first attempt.
+pub async fn allow(
+    redis_connection: &mut deadpool_redis::redis::aio::Connection,
+    action: &str,
+    subject: &str,
+    limit: u32,
+    window_seconds: usize,
+) -> RedisResult<bool> {
+    let key = format!("{}{}/{}", THROTTLE_KEY_PREFIX, action, subject);
+
+    let attempts: u32 = redis_connection.incr(&key, 1).await?;
+    if attempts == 1 {
+        redis_connection
+            .expire::<_, ()>(&key, window_seconds)
+            .await?;
+    }
+
+    Ok(attempts <= limit)
+}
+
+/// Limits on an action taken for an email address, like sending it an email
+pub struct EmailLimits {
+    /// Attempts allowed per client IP address, per window
+    pub ip: u32,
+    /// Attempts allowed per email address, per window
+    pub email: u32,
+    pub window_seconds: usize,
+}
+
+/// Counts an attempt at `action` from `ip` for `email` and reports whether it is within both
+/// `limits`. Attempts over the IP address limit don't count towards the one of the email address.
+pub async fn check_ip_and_email(
+    redis_connection: &mut deadpool_redis::redis::aio::Connection,
+    action: &str,
+    ip: &str,
+    email: &str,
+    limits: &EmailLimits,
+) -> RedisResult<bool> {
+    let ip_action = format!("{}/ip", action);
+    if !allow(
+        redis_connection,
+        &ip_action,
+        ip,
+        limits.ip,
+        limits.window_seconds,
+    )
+    .await?
+    {
+        return Ok(false);
+    }
+
+    let email_action = format!("{}/email", action);
+    allow(
+        redis_connection,
+        &email_action,
+        email,
+        limits.email,
+        limits.window_seconds,
+    )
+    .await
+}
+
+/// The address of the client making the request, for throttling by IP address.
+///
+/// Forwarding headers can be set by anyone, so `X-Forwarded-For` is only used when the request
+/// comes from one of the `trusted_proxies`. In that case, the client is the last address in the
+/// header which isn't itself a trusted proxy.
+pub fn client_ip(req: &HttpRequest, trusted_proxies: &[IpAddr]) -> String {
+    let Some(peer) = req.peer_addr().map(|a| a.ip()) else {
+        return "unknown".to_string();
+    };
+
+    if !trusted_proxies.contains(&peer) {
+        return peer.to_string();
+    }
+
+    let forwarded_for = req
+        .headers()
+        .get_all("X-Forwarded-For")
+        .filter_map(|h| h.to_str().ok())
+        .flat_map(|h| h.split(','))
+        .map(|a| a.trim().parse::<IpAddr>().ok())
+        .collect::<Vec<_>>();
+
+    for address in forwarded_for.into_iter().rev() {
+        match address {
+            Some(a) if trusted_proxies.contains(&a) => continue,
+            Some(a) => return a.to_string(),
+            // Anything left of an entry we can't parse can't be relied on
+            None => break,
+        }
+    }
+
+    peer.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::test::TestRequest;
+
+    use super::*;
+
+    #[test]
+    fn test_client_ip() {
+        let proxy: IpAddr = "10.0.0.1".parse().unwrap();
+        let proxies = [proxy];
+
+        let direct = TestRequest::default()
+            .peer_addr("203.0.113.7:1234".parse().unwrap())
+            .insert_header(("X-Forwarded-For", "198.51.100.1"))
+            .to_http_request();
+        assert_eq!(client_ip(&direct, &proxies), "203.0.113.7");
+        assert_eq!(client_ip(&direct, &[]), "203.0.113.7");
+
+        let proxied = TestRequest::default()
+            .peer_addr("10.0.0.1:1234".parse().unwrap())
+            .insert_header(("X-Forwarded-For", "198.51.100.1, 203.0.113.7, 10.0.0.1"))
+            .to_http_request();
+        assert_eq!(client_ip(&proxied, &proxies), "203.0.113.7");
+
+        let unforwarded = TestRequest::default()
+            .peer_addr("10.0.0.1:1234".parse().unwrap())
+            .to_http_request();
+        assert_eq!(client_ip(&unforwarded, &proxies), "10.0.0.1");
+
+        assert_eq!(
+            client_ip(&TestRequest::default().to_http_request(), &proxies),
+            "unknown"
+        );
+    }
+}
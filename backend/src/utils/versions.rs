@@ -0,0 +1,34 @@
+use semver::Version;
+
+/// Semantic versions may be tagged with a `v` prefix
+pub fn parse_version_tag(tag: &str) -> Option<Version> {
+    Version::parse(tag.strip_prefix('v').unwrap_or(tag)).ok()
+}
+
+/// Whether the version tag `tag` comes after `last`. Tags that aren't semantic versions,
+/// like `1.2`, can't be compared, so they count as newer.
+pub fn is_newer_version(tag: &str, last: Option<&str>) -> bool {
+    match (parse_version_tag(tag), last.and_then(parse_version_tag)) {
+        (Some(version), Some(last)) => version > last,
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_newer_version() {
+        assert!(is_newer_version("1.0.0", None));
+        assert!(is_newer_version("v1.2.0", Some("1.1.9")));
+        assert!(is_newer_version("1.10.0", Some("v1.9.0")));
+        assert!(is_newer_version("2.0.0", Some("not-a-version")));
+        assert!(is_newer_version("1.2", Some("1.0.0")));
+
+        assert!(!is_newer_version("1.0.0", Some("1.0.0")));
+        assert!(!is_newer_version("v1.0.0", Some("1.0.0")));
+        assert!(!is_newer_version("1.0.0-beta.1", Some("1.0.0")));
+        assert!(!is_newer_version("0.9.0", Some("1.0.0")));
+    }
+}
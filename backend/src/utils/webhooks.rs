@@ -0,0 +1,149 @@
+use anyhow::{anyhow, Result};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// How old a signed Stripe event can be before it is rejected, to limit replay attacks
+const STRIPE_TOLERANCE_SECONDS: i64 = 5 * 60;
+
+/// Verifies the `Stripe-Signature` header of a webhook request, as described in
+/// <https://stripe.com/docs/webhooks#verify-manually>.
+pub fn verify_stripe_signature(payload: &[u8], header: &str, secret: &str, now: i64) -> Result<()> {
+    // Anyone could sign events with an empty secret
+    if secret.is_empty() {
+        return Err(anyhow!("No Stripe webhook secret is configured"));
+    }
+
+    let mut timestamp = None;
+    let mut signatures = Vec::new();
+
+    for (key, value) in header.split(',').filter_map(|p| p.trim().split_once('=')) {
+        match key {
+            "t" => timestamp = value.parse::<i64>().ok(),
+            "v1" => signatures.push(value),
+            _ => {}
+        }
+    }
+
+    let timestamp = timestamp.ok_or(anyhow!("No timestamp in Stripe signature header"))?;
+    if (now - timestamp).abs() > STRIPE_TOLERANCE_SECONDS {
+        return Err(anyhow!(
+            "Stripe signature timestamp is outside the tolerance"
+        ));
+    }
+
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())?;
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(payload);
+
+    let matches = signatures
+        .into_iter()
+        .any(|signature| match hex::decode(signature) {
+            Ok(signature) => mac.clone().verify_slice(&signature).is_ok(),
+            Err(_) => false,
+        });
+
+    if matches {
+        Ok(())
+    } else {
+        Err(anyhow!("No matching Stripe signature"))
+    }
+}
+
+/// Verifies the `X-Hub-Signature-256` header of a GitHub webhook request, as described in
+/// <https://docs.github.com/en/webhooks/using-webhooks/validating-webhook-deliveries>.
+pub fn verify_github_signature(payload: &[u8], header: &str, secret: &str) -> Result<()> {
+    // Anyone could sign events with an empty secret
+    if secret.is_empty() {
+        return Err(anyhow!("No GitHub webhook secret is configured"));
+    }
+
+    let signature = header.strip_prefix("sha256=").ok_or(anyhow!(
+        "GitHub signature header is not a SHA-256 signature"
+    ))?;
+
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())?;
+    mac.update(payload);
+
+    mac.verify_slice(&hex::decode(signature)?)
+        .map_err(|_| anyhow!("GitHub signature doesn't match"))
+}
+
+/// Signs a payload the way Stripe does, for building webhook requests in tests.
+#[cfg(test)]
+pub fn stripe_signature_header(payload: &[u8], secret: &str, timestamp: i64) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("Invalid HMAC key");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(payload);
+
+    format!(
+        "t={},v1={}",
+        timestamp,
+        hex::encode(mac.finalize().into_bytes())
+    )
+}
+
+/// Signs a payload the way GitHub does, for building webhook requests in tests.
+#[cfg(test)]
+pub fn github_signature_header(payload: &[u8], secret: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("Invalid HMAC key");
+    mac.update(payload);
+
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "whsec_test";
+    const PAYLOAD: &[u8] = br#"{"id":"evt_test"}"#;
+
+    #[test]
+    fn valid_signature() {
+        let header = stripe_signature_header(PAYLOAD, SECRET, 1_700_000_000);
+
+        assert!(verify_stripe_signature(PAYLOAD, &header, SECRET, 1_700_000_060).is_ok());
+        assert!(verify_stripe_signature(
+            PAYLOAD,
+            &format!("{},v1=deadbeef,v0=1234", header),
+            SECRET,
+            1_700_000_000
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn invalid_signatures() {
+        let header = stripe_signature_header(PAYLOAD, SECRET, 1_700_000_000);
+
+        assert!(verify_stripe_signature(PAYLOAD, &header, "whsec_other", 1_700_000_000).is_err());
+        assert!(verify_stripe_signature(b"{}", &header, SECRET, 1_700_000_000).is_err());
+        assert!(verify_stripe_signature(PAYLOAD, &header, SECRET, 1_700_001_000).is_err());
+        assert!(verify_stripe_signature(PAYLOAD, "v1=deadbeef", SECRET, 1_700_000_000).is_err());
+        assert!(verify_stripe_signature(PAYLOAD, "", SECRET, 1_700_000_000).is_err());
+
+        let unsigned = stripe_signature_header(PAYLOAD, "", 1_700_000_000);
+        assert!(verify_stripe_signature(PAYLOAD, &unsigned, "", 1_700_000_000).is_err());
+    }
+
+    #[test]
+    fn github_signatures() {
+        let header = github_signature_header(PAYLOAD, SECRET);
+
+        assert!(verify_github_signature(PAYLOAD, &header, SECRET).is_ok());
+        assert!(verify_github_signature(PAYLOAD, &header, "other").is_err());
+        assert!(verify_github_signature(b"{}", &header, SECRET).is_err());
+        assert!(
+            verify_github_signature(PAYLOAD, &header.replace("sha256=", "sha1="), SECRET).is_err()
+        );
+        assert!(verify_github_signature(PAYLOAD, "sha256=zz", SECRET).is_err());
+        assert!(verify_github_signature(PAYLOAD, "", SECRET).is_err());
+
+        let unsigned = github_signature_header(PAYLOAD, "");
+        assert!(verify_github_signature(PAYLOAD, &unsigned, "").is_err());
+    }
+}
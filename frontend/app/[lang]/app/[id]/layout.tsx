@@ -1,8 +1,21 @@
 export async function generateStaticParams() {
-  const ids = await fetch(
-    `${process.env.SERVER_SIDE_API_URL}/api/apps/all_ids`,
-    { next: { revalidate: 300 } }
-  ).then((res) => res.json())
+  const ids: string[] = []
+  let after: string | null = null
+
+  do {
+    const params = new URLSearchParams({ limit: '100' })
+    if (after) {
+      params.set('after', after)
+    }
+
+    const page: { items: string[]; next: string | null } = await fetch(
+      `${process.env.SERVER_SIDE_API_URL}/api/apps/all_ids?${params}`,
+      { next: { revalidate: 300 } }
+    ).then((res) => res.json())
+
+    ids.push(...page.items)
+    after = page.next
+  } while (after)
 
   return ids.map((id: string) => ({
     id,
@@ -0,0 +1,97 @@
+import { Inter } from 'next/font/google'
+import Link from 'next/link'
+
+interface PaymentReceipt {
+  checkout_session_id: string
+  app_id: string | null
+  amount: number
+  currency: string
+  amount_refunded: number
+  status: string
+}
+
+const inter = Inter({ subsets: ['latin'] })
+
+async function getReceipt(
+  session: string
+): Promise<PaymentReceipt | undefined> {
+  try {
+    const res = await fetch(
+      `${process.env.SERVER_SIDE_API_URL}/api/payments/session/${encodeURIComponent(
+        session
+      )}`,
+      { cache: 'no-store' }
+    )
+
+    if (!res.ok) {
+      throw new Error(`Failed to fetch receipt for ${session}`)
+    }
+
+    return res.json()
+  } catch (e) {
+    console.log(e)
+  }
+}
+
+function formatAmount(lang: string, amount: number, currency: string): string {
+  return new Intl.NumberFormat(lang, {
+    style: 'currency',
+    currency: currency.toUpperCase(),
+  }).format(amount / 100)
+}
+
+export default async function Page({
+  params: { lang, id, session },
+}: {
+  params: { lang: string; id: string; session: string }
+}) {
+  const receipt = await getReceipt(session)
+
+  return (
+    <main
+      className={`${inter.className} my-3 lg:mx-auto lg:max-w-7xl px-2 sm:px-6 lg:px-8`}
+    >
+      <h2 className="text-2xl font-bold leading-7 text-gray-900 sm:truncate sm:text-3xl sm:tracking-tight">
+        {receipt?.status === 'pending'
+          ? 'Your payment is being processed'
+          : 'Thank you for your purchase'}
+      </h2>
+
+      {receipt ? (
+        <dl className="mt-5 divide-y divide-gray-100 text-sm">
+          <div className="py-3 sm:grid sm:grid-cols-3">
+            <dt className="font-medium text-gray-900">Amount</dt>
+            <dd className="text-gray-700 sm:col-span-2">
+              {formatAmount(lang, receipt.amount, receipt.currency)}
+            </dd>
+          </div>
+          {receipt.amount_refunded > 0 && (
+            <div className="py-3 sm:grid sm:grid-cols-3">
+              <dt className="font-medium text-gray-900">Refunded</dt>
+              <dd className="text-gray-700 sm:col-span-2">
+                {formatAmount(lang, receipt.amount_refunded, receipt.currency)}
+              </dd>
+            </div>
+          )}
+          <div className="py-3 sm:grid sm:grid-cols-3">
+            <dt className="font-medium text-gray-900">Reference</dt>
+            <dd className="text-gray-700 sm:col-span-2 break-all">
+              {receipt.checkout_session_id}
+            </dd>
+          </div>
+        </dl>
+      ) : (
+        <p className="mt-5 text-sm text-gray-500">
+          We couldn&apos;t find the details of this payment.
+        </p>
+      )}
+
+      <Link
+        href={`/${lang}/app/${id}`}
+        className="mt-5 inline-flex rounded-md bg-indigo-600 px-3 py-2 text-base font-semibold text-white shadow-sm hover:bg-indigo-500"
+      >
+        Back to the app
+      </Link>
+    </main>
+  )
+}
@@ -0,0 +1,104 @@
+'use client'
+
+import React, { useState } from 'react'
+import Link from 'next/link'
+
+import { useTranslation } from '@/app/i18n/client'
+import { useSearchParams } from 'next/navigation'
+
+export default function AcceptInvitation({
+  params: { lang },
+}: {
+  params: { lang: string }
+}) {
+  const { t } = useTranslation(['invitation', 'server'], lang)
+
+  // Set up translation keys for server errors
+  // t('app-invitation.invalid', {ns: 'server'})
+  // t('app-invitation.wrong-account', {ns: 'server'})
+
+  const searchParams = useSearchParams()
+  const token = searchParams.get('token')
+
+  const [result, setResult] = useState<any>(null)
+  const [isLoading, setLoading] = useState(false)
+
+  async function accept() {
+    const endpoint = `${process.env.NEXT_PUBLIC_API_BASE_URL}/api/dashboard/invitations/accept`
+
+    const options: RequestInit = {
+      method: 'POST',
+      headers: {
+        'Content-Type': 'application/json',
+      },
+      body: JSON.stringify({ token }),
+      credentials: 'include',
+    }
+
+    setLoading(true)
+    const response = await fetch(endpoint, options)
+    setLoading(false)
+
+    if (response.status == 200) {
+      setResult({ accepted: true })
+    } else if (response.status == 401) {
+      setResult({ signInRequired: true })
+    } else {
+      setResult(await response.json().catch(() => ({ error: true })))
+    }
+  }
+
+  return (
+    <>
+      <div className="flex min-h-full flex-col justify-center px-6 py-12 lg:px-8">
+        <div className="sm:mx-auto sm:w-full sm:max-w-md">
+          <h2 className="text-center text-2xl font-bold leading-9 tracking-tight text-gray-900">
+            {t('page-title')}
+          </h2>
+        </div>
+
+        <div className="mt-10 sm:mx-auto sm:w-full sm:max-w-md">
+          {result?.error && (
+            <p className="mb-4 rounded-lg bg-red-500 px-4 py-4 text-base text-white">
+              {result.translation_key
+                ? t(result.translation_key, { ns: 'server' })
+                : t('generic-problem')}
+            </p>
+          )}
+
+          {result?.signInRequired && (
+            <p className="mb-4 text-center text-sm text-gray-500">
+              {t('sign-in-required')}&nbsp;
+              <Link
+                href="/login"
+                className="font-semibold leading-6 text-indigo-600 hover:text-indigo-500"
+              >
+                {t('sign-in-action')}
+              </Link>
+            </p>
+          )}
+
+          {result?.accepted ? (
+            <p className="mb-4 text-center">
+              {t('accepted')}&nbsp;
+              <Link
+                href="/dashboard"
+                className="font-semibold leading-6 text-indigo-600 hover:text-indigo-500"
+              >
+                {t('dashboard-action')}
+              </Link>
+            </p>
+          ) : (
+            <button
+              disabled={isLoading || !token}
+              onClick={accept}
+              className="flex w-full justify-center rounded-md bg-indigo-600 px-3 py-1.5 text-sm font-semibold leading-6 text-white shadow-sm hover:bg-indigo-500 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-indigo-600"
+            >
+              {t('accept-button')}
+            </button>
+          )}
+        </div>
+      </div>
+    </>
+  )
+}
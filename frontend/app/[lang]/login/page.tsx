@@ -64,6 +64,22 @@ export default async function Login({
             {t('github-login', { ns: 'login' })}
           </Link>
 
+          <Link
+            className="mb-3 bg-orange-600 hover:bg-orange-500 flex w-full items-center justify-center rounded-md px-3 py-1.5 text-center text-sm font-normal leading-6 text-white"
+            href={`${process.env.NEXT_PUBLIC_API_BASE_URL}/api/users/gitlab/login`}
+            role="button"
+          >
+            {t('gitlab-login', { ns: 'login' })}
+          </Link>
+
+          <Link
+            className="mb-3 bg-sky-700 hover:bg-sky-600 flex w-full items-center justify-center rounded-md px-3 py-1.5 text-center text-sm font-normal leading-6 text-white"
+            href={`${process.env.NEXT_PUBLIC_API_BASE_URL}/api/users/codeberg/login`}
+            role="button"
+          >
+            {t('codeberg-login', { ns: 'login' })}
+          </Link>
+
           <p className="mt-10 text-center text-sm text-gray-500">
             {t('no-account', { ns: 'login' })}&nbsp;
             <Link
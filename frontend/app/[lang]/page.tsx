@@ -18,6 +18,11 @@ interface ComponentSummary {
   icons: Icon[]
 }
 
+interface Page<T> {
+  items: T[]
+  next: string | null
+}
+
 async function getRecentlyAdded(): Promise<ComponentSummary[] | undefined> {
   try {
     const res = await fetch(
@@ -29,7 +34,8 @@ async function getRecentlyAdded(): Promise<ComponentSummary[] | undefined> {
       throw new Error('Failed to fetch data')
     }
 
-    return res.json()
+    const page: Page<ComponentSummary> = await res.json()
+    return page.items
   } catch (e) {
     console.log(e)
   }
@@ -46,7 +52,8 @@ async function getRecentlyUpdated(): Promise<ComponentSummary[] | undefined> {
       throw new Error('Failed to fetch data')
     }
 
-    return res.json()
+    const page: Page<ComponentSummary> = await res.json()
+    return page.items
   } catch (e) {
     console.log(e)
   }
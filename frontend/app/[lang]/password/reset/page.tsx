@@ -0,0 +1,183 @@
+'use client'
+
+import React, { useState } from 'react'
+import Link from 'next/link'
+import TextBox from '@/components/TextBox'
+
+import { useForm } from 'react-hook-form'
+import { yupResolver } from '@hookform/resolvers/yup'
+import * as Yup from 'yup'
+
+import { useTranslation } from '@/app/i18n/client'
+import { useSearchParams } from 'next/navigation'
+
+export default function ResetPassword({
+  params: { lang },
+}: {
+  params: { lang: string }
+}) {
+  const { t } = useTranslation(['password-reset', 'server'], lang)
+
+  // Set up translation keys for validation errors
+  // t('validation.password-min-6-chars', {ns: 'password-reset'})
+  // t('validation.password-required', {ns: 'password-reset'})
+  // t('validation.passwords-not-matching', {ns: 'password-reset'})
+  // t('validation.confirm-password-required', {ns: 'password-reset'})
+
+  // Set up translation keys for server errors
+  // t('password-reset.invalid-token', {ns: 'server'})
+  // t('password.too-short', {ns: 'server'})
+
+  type FormValues = {
+    password: string
+    confirmPassword: string
+  }
+
+  const validationSchema = Yup.object().shape({
+    password: Yup.string()
+      .min(6, 'validation.password-min-6-chars')
+      .required('validation.password-required'),
+    confirmPassword: Yup.string()
+      .oneOf(
+        [Yup.ref('password'), undefined],
+        'validation.passwords-not-matching'
+      )
+      .required('validation.confirm-password-required'),
+  })
+
+  const searchParams = useSearchParams()
+  const token = searchParams.get('token')
+
+  const [result, setResult] = useState<any>(null)
+
+  const formOptions = { resolver: yupResolver(validationSchema) }
+  const { register, handleSubmit, formState } = useForm<FormValues>(formOptions)
+  const { errors } = formState
+
+  const [isLoading, setLoading] = useState(false)
+  const onSubmit = handleSubmit(async (data) => {
+    const endpoint = `${process.env.NEXT_PUBLIC_API_BASE_URL}/api/users/password/reset`
+
+    const options = {
+      method: 'POST',
+      headers: {
+        'Content-Type': 'application/json',
+      },
+      body: JSON.stringify({ token, password: data.password }),
+    }
+
+    setLoading(true)
+    const response = await fetch(endpoint, options)
+    setLoading(false)
+
+    setResult(await response.json().catch(() => ({ error: true })))
+
+    return false
+  })
+
+  return (
+    <>
+      <div className="flex min-h-full flex-col justify-center px-6 py-12 lg:px-8">
+        <div className="sm:mx-auto sm:w-full sm:max-w-md">
+          <h2 className="text-center text-2xl font-bold leading-9 tracking-tight text-gray-900">
+            {t('page-title')}
+          </h2>
+        </div>
+
+        <div className="mt-10 sm:mx-auto sm:w-full sm:max-w-md">
+          {!token && (
+            <p className="mb-4 rounded-lg bg-red-500 px-4 py-4 text-base text-white">
+              {t('password-reset.invalid-token', { ns: 'server' })}
+            </p>
+          )}
+
+          {result?.error && (
+            <p className="mb-4 rounded-lg bg-red-500 px-4 py-4 text-base text-white">
+              {result.translation_key
+                ? t(result.translation_key, { ns: 'server' })
+                : t('generic-problem')}
+            </p>
+          )}
+
+          {result?.message ? (
+            <p className="mb-4 text-center">
+              {t('password-changed')}&nbsp;
+              <Link
+                href="/login"
+                className="font-semibold leading-6 text-indigo-600 hover:text-indigo-500"
+              >
+                {t('sign-in-action')}
+              </Link>
+            </p>
+          ) : (
+            token && (
+              <form onSubmit={onSubmit}>
+                <div className="flex">
+                  <div className="w-full">
+                    <TextBox
+                      name="password"
+                      type="password"
+                      autoComplete="new-password"
+                      register={register('password')}
+                      title={t('form-labels.password')}
+                      errorMessage={
+                        errors.password?.message
+                          ? (t(errors.password.message) as string)
+                          : undefined
+                      }
+                      inputClassName={`${
+                        errors.password
+                          ? 'bg-red-50 ring-red-500 text-red-900 placeholder-red-700 focus:ring-red-500 dark:bg-red-100 dark:border-red-400'
+                          : ''
+                      }`}
+                      labelClassName={`${
+                        errors.password ? 'text-red-700 dark:text-red-500' : ''
+                      }`}
+                    />
+                  </div>
+                </div>
+
+                <div className="flex">
+                  <div className="w-full">
+                    <TextBox
+                      name="confirmPassword"
+                      type="password"
+                      autoComplete="new-password"
+                      register={register('confirmPassword')}
+                      title={t('form-labels.confirm-password')}
+                      errorMessage={
+                        errors.confirmPassword?.message
+                          ? (t(errors.confirmPassword.message) as string)
+                          : undefined
+                      }
+                      inputClassName={`${
+                        errors.confirmPassword
+                          ? 'bg-red-50 ring-red-500 text-red-900 placeholder-red-700 focus:ring-red-500 dark:bg-red-100 dark:border-red-400'
+                          : ''
+                      }`}
+                      labelClassName={`${
+                        errors.confirmPassword
+                          ? 'text-red-700 dark:text-red-500'
+                          : ''
+                      }`}
+                    />
+                  </div>
+                </div>
+
+                <div>
+                  <button
+                    disabled={isLoading}
+                    type="submit"
+                    className="flex w-full justify-center rounded-md bg-indigo-600 px-3 py-1.5 text-sm font-semibold leading-6 text-white shadow-sm hover:bg-indigo-500 focus-visible:outline focus-visible:outline-2 focus-visible:outline-offset-2 focus-visible:outline-indigo-600"
+                  >
+                    {t('form-labels.reset-button')}
+                  </button>
+                </div>
+              </form>
+            )
+          )}
+        </div>
+      </div>
+    </>
+  )
+}
@@ -3,44 +3,370 @@
  * Do not make direct changes to the file.
  */
 
+/** OneOf type helpers */
+type Without<T, U> = { [P in Exclude<keyof T, keyof U>]?: never }
+type XOR<T, U> = T | U extends object
+  ? (Without<T, U> & U) | (Without<U, T> & T)
+  : T | U
+type OneOf<T extends any[]> = T extends [infer Only]
+  ? Only
+  : T extends [infer A, infer B, ...infer Rest]
+    ? OneOf<[XOR<A, B>, ...Rest]>
+    : never
+
 export interface paths {
+  '/admin/apps/{app_id}/fee': {
+    post: operations['set_app_fee']
+  }
+  '/admin/apps/{app_id}/owners/{user_id}/verification': {
+    post: operations['set_owner_verification']
+  }
+  '/admin/apps/{app_id}/publish': {
+    post: operations['publish_app']
+  }
+  '/admin/apps/{app_id}/unpublish': {
+    post: operations['unpublish_app']
+  }
+  '/admin/apps/{app_id}/verification': {
+    post: operations['set_app_verification']
+  }
+  '/admin/audit_events': {
+    get: operations['list_audit_events']
+  }
+  '/admin/users': {
+    get: operations['list_users']
+  }
+  '/admin/users/{user_id}/deactivate': {
+    post: operations['deactivate_user']
+  }
+  '/admin/users/{user_id}/reactivate': {
+    post: operations['reactivate_user']
+  }
   '/apps/all_ids': {
     get: operations['all_ids']
   }
+  '/apps/categories': {
+    get: operations['categories']
+  }
+  '/apps/categories/{name}': {
+    get: operations['category']
+  }
   '/apps/recently_added': {
     get: operations['recently_added']
   }
   '/apps/recently_updated': {
     get: operations['recently_updated']
   }
+  '/apps/search': {
+    get: operations['search']
+  }
+  '/apps/{id}': {
+    get: operations['get']
+  }
+  '/apps/{id}/details': {
+    get: operations['details']
+  }
+  '/dashboard/access_tokens': {
+    get: operations['get_access_tokens']
+    post: operations['create_access_token']
+  }
+  '/dashboard/access_tokens/{token_id}/revoke': {
+    post: operations['revoke_access_token']
+  }
+  '/dashboard/account/delete': {
+    post: operations['delete_account']
+  }
+  '/dashboard/account/export': {
+    get: operations['export_account']
+  }
+  '/dashboard/app_owners/{app_id}': {
+    get: operations['get_app_owners']
+  }
+  '/dashboard/app_owners/{app_id}/invite': {
+    post: operations['invite_app_owner']
+  }
+  '/dashboard/app_owners/{app_id}/transfer': {
+    post: operations['transfer_app_ownership']
+  }
+  '/dashboard/app_owners/{app_id}/{user_id}/remove': {
+    post: operations['remove_app_owner']
+  }
   '/dashboard/apps': {
     get: operations['get_apps']
     post: operations['add_app']
   }
+  '/dashboard/apps/{app_id}/pricing': {
+    post: operations['set_app_pricing']
+  }
+  '/dashboard/create_stripe_account': {
+    post: operations['create']
+  }
+  '/dashboard/domain_challenge/{app_id}': {
+    post: operations['issue_domain_challenge']
+  }
+  '/dashboard/domain_challenge/{app_id}/check': {
+    post: operations['check_domain_challenge']
+  }
+  '/dashboard/earnings': {
+    get: operations['get_earnings']
+  }
+  '/dashboard/enable_app_payments/{app_id}': {
+    post: operations['enable_app_payments']
+  }
+  '/dashboard/invitations/accept': {
+    post: operations['accept_app_invitation']
+  }
+  '/dashboard/link_stripe_account': {
+    get: operations['link']
+  }
   '/dashboard/stripe_account': {
     get: operations['get_stripe_account']
   }
+  '/dashboard/submissions': {
+    get: operations['get_submissions']
+  }
   '/dashboard/submit_app_update': {
     post: operations['submit']
   }
+  '/github/webhook': {
+    post: operations['github_webhook']
+  }
+  '/payments/session/{id}': {
+    get: operations['session']
+  }
+  '/payments/start': {
+    get: operations['start']
+  }
+  '/payments/webhook': {
+    post: operations['webhook']
+  }
+  '/users/github/callback': {
+    get: operations['github_callback']
+  }
+  '/users/github/login': {
+    get: operations['github_login']
+  }
+  '/users/login': {
+    post: operations['login_user']
+  }
+  '/users/password/reset': {
+    post: operations['reset_password']
+  }
+  '/users/password/reset-request': {
+    post: operations['request_password_reset']
+  }
+  '/users/register/confirm': {
+    get: operations['confirm']
+  }
+  '/users/register/resend': {
+    post: operations['resend_verification']
+  }
   '/users/test_auth': {
     get: operations['test_auth']
   }
+  '/users/{forge}/callback': {
+    get: operations['forge_callback']
+  }
+  '/users/{forge}/login': {
+    get: operations['forge_login']
+  }
 }
 
 export type webhooks = Record<string, never>
 
 export interface components {
   schemas: {
+    AcceptAppInvitation: {
+      /** @description The PASETO token from the invitation email */
+      token: string
+    }
+    /** @description A personal access token, without its secret */
+    AccessToken: {
+      /** Format: date-time */
+      created_at: string
+      /** Format: uuid */
+      id: string
+      /** Format: date-time */
+      last_used_at?: string | null
+      /** @example Release pipeline */
+      name: string
+      scopes: components['schemas']['TokenScope'][]
+    }
+    AccountExport: {
+      access_tokens: components['schemas']['AccessToken'][]
+      apps: components['schemas']['ExportedAppOwnership'][]
+      /** @description Actions taken by or on the user */
+      audit_events: components['schemas']['AuditEvent'][]
+      domain_challenges: components['schemas']['ExportedDomainChallenge'][]
+      forges: components['schemas']['ExportedForgeAuth'][]
+      github?: components['schemas']['ExportedGithubAuth'] | null
+      invitations: components['schemas']['ExportedInvitation'][]
+      profile?: components['schemas']['ExportedProfile'] | null
+      /**
+       * @example [
+       *   "acct_1NCliJPBGjCwUDHc"
+       * ]
+       */
+      stripe_accounts: string[]
+      /** @description App updates submitted by the user */
+      submissions: components['schemas']['Submission'][]
+      user: components['schemas']['ExportedUser']
+    }
     App: {
-      first_seen?: components['schemas']['time.OffsetDateTime'] | null
+      /** Format: date-time */
+      first_seen: string
+      /** @description Whether the app can be downloaded without paying */
+      free_allowed: boolean
       id: string
       is_published: boolean
       is_verified: boolean
       last_submitted_version?: string | null
-      last_update?: components['schemas']['time.OffsetDateTime'] | null
+      /** Format: date-time */
+      last_update: string
+      /**
+       * @description Smallest payment accepted for the app, in the smallest unit of `price_currency`
+       * Format: int32
+       */
+      minimum_price: number
+      /** @description Lowercase ISO code of the currency the app's prices are set in */
+      price_currency: string
       repository: string
       stripe_connect_id?: string | null
+      /**
+       * @description Price the developer suggests paying, in the smallest unit of `price_currency`
+       * Format: int32
+       */
+      suggested_price?: number | null
+    }
+    AppCoOwner: {
+      /** @example maintainer@example.com */
+      email: string
+      role: components['schemas']['AppOwnerRole']
+      /**
+       * Format: uuid
+       * @example 9c5b94b1-35ad-49bb-b118-8e8fc24abf80
+       */
+      user_id: string
+      verified_owner: boolean
+    }
+    AppDetails: {
+      content_rating?: components['schemas']['ContentRating'] | null
+      description?: components['schemas']['TranslatableString'] | null
+      developer_name?: components['schemas']['TranslatableString'] | null
+      icons: components['schemas']['Icon'][]
+      /** @example com.github.davidmhewitt.torrential */
+      id: string
+      /**
+       * @description SPDX license expression of the project
+       * @example GPL-2.0-or-later
+       */
+      license?: string | null
+      name: components['schemas']['TranslatableString']
+      /** @description Releases in the order they are listed in the AppStream metadata, usually newest first */
+      releases: components['schemas']['Release'][]
+      screenshots: components['schemas']['Screenshot'][]
+      summary?: components['schemas']['TranslatableString'] | null
+      urls: components['schemas']['AppUrl'][]
+    }
+    AppEarnings: {
+      /** @example com.github.davidmhewitt.torrential */
+      app_id: string
+      /** @example usd */
+      currency: string
+      /**
+       * @description Amount paid by buyers minus refunds, in the smallest unit of `currency`
+       * Format: int64
+       * @example 900
+       */
+      gross: number
+      /**
+       * @description Calendar month (UTC) the payments were made in
+       * @example 2023-08
+       */
+      month: string
+      /**
+       * Format: int64
+       * @example 630
+       */
+      net: number
+      /**
+       * @description Number of payments that weren't fully refunded
+       * Format: int64
+       * @example 3
+       */
+      payments: number
+      /**
+       * Format: int64
+       * @example 270
+       */
+      platform_fee: number
+    }
+    /** @description Overrides of the platform fee policy for a single app, unset fields use the global policy */
+    AppFeeOverride: {
+      /**
+       * @description Largest fee charged, in whole units of the payment's currency
+       * Format: double
+       */
+      maximum?: number | null
+      /**
+       * @description Smallest fee charged, in whole units of the payment's currency
+       * Format: double
+       * @example 0.0
+       */
+      minimum?: number | null
+      /**
+       * @description Share of each payment, from 0 to 100
+       * Format: double
+       * @example 10.0
+       */
+      percentage?: number | null
+    }
+    AppIdPage: {
+      items: string[]
+      /** @description Cursor to pass as `after` to get the next page, or `null` if this is the last page */
+      next?: string | null
+    }
+    AppOwnerInvitation: {
+      /** @example maintainer@example.com */
+      email: string
+      role: components['schemas']['AppOwnerRole']
+    }
+    /**
+     * @description What an owner of an app is allowed to do with it
+     * @enum {string}
+     */
+    AppOwnerRole: 'owner' | 'maintainer' | 'finance'
+    AppOwnershipTransfer: {
+      /**
+       * @description Verified owner of the app who becomes its primary owner
+       * Format: uuid
+       * @example 9c5b94b1-35ad-49bb-b118-8e8fc24abf80
+       */
+      user_id: string
+    }
+    AppPricing: {
+      /**
+       * @description ISO code of the currency the prices are given in
+       * @example usd
+       */
+      currency: string
+      /**
+       * @description Whether buyers can download the app without paying
+       * @example true
+       */
+      free_allowed: boolean
+      /**
+       * @description Smallest payment accepted, in the smallest unit of `currency`
+       * Format: int32
+       * @example 100
+       */
+      minimum_price: number
+      /**
+       * @description Price shown to buyers by default, in the smallest unit of `currency`
+       * Format: int32
+       * @example 300
+       */
+      suggested_price?: number | null
     }
     AppUpdateSubmission: {
       /** @example com.github.davidmhewitt.torrential */
@@ -48,6 +374,84 @@ export interface components {
       /** @example 3.0.0 */
       version_tag: string
     }
+    AppUrl: {
+      kind: components['schemas']['UrlKind']
+      /** @example https://example.com */
+      url: string
+    }
+    /**
+     * @description Something a user did that is kept in the audit log
+     * @enum {string}
+     */
+    AuditAction:
+      | 'login'
+      | 'app_added'
+      | 'payments_enabled'
+      | 'stripe_account_created'
+      | 'stripe_account_linked'
+      | 'update_submitted'
+      | 'admin_user_deactivated'
+      | 'admin_user_reactivated'
+      | 'admin_app_published'
+      | 'admin_app_unpublished'
+      | 'admin_app_verification_set'
+      | 'admin_owner_verification_set'
+      | 'admin_app_fee_set'
+      | 'access_token_created'
+      | 'access_token_revoked'
+      | 'app_owner_invited'
+      | 'app_invitation_accepted'
+      | 'app_owner_removed'
+      | 'app_ownership_transferred'
+      | 'app_pricing_set'
+    AuditEvent: {
+      action: components['schemas']['AuditAction']
+      /**
+       * @description The user who did it, unset if their account was deleted
+       * Format: uuid
+       */
+      actor_id?: string | null
+      /**
+       * @description The app acted on, if any
+       * @example com.github.davidmhewitt.torrential
+       */
+      app_id?: string | null
+      /** Format: date-time */
+      created_at: string
+      /** @description Action specific details, like the login method or the new value of a setting */
+      details: Record<string, never>
+      /** Format: uuid */
+      id: string
+      /**
+       * @description The user acted on, if any
+       * Format: uuid
+       */
+      target_user_id?: string | null
+    }
+    AuditEventPage: {
+      items: components['schemas']['AuditEvent'][]
+      /** @description Cursor to pass as `after` to get the next page, or `null` if this is the last page */
+      next?: string | null
+    }
+    CategoryApps: {
+      apps: components['schemas']['ComponentSummary'][]
+      /**
+       * @description The number of published apps in this category
+       * @example 12
+       */
+      count: number
+      /** @example Graphics */
+      name: string
+    }
+    CategorySummary: {
+      /**
+       * @description The number of published apps in this category
+       * @example 12
+       */
+      count: number
+      /** @example Graphics */
+      name: string
+    }
     ComponentSummary: {
       icons: components['schemas']['Icon'][]
       /** @example com.github.davidmhewitt.torrential */
@@ -55,12 +459,71 @@ export interface components {
       name: components['schemas']['TranslatableString']
       summary?: components['schemas']['TranslatableString'] | null
     }
+    ComponentSummaryPage: {
+      items: components['schemas']['ComponentSummary'][]
+      /** @description Cursor to pass as `after` to get the next page, or `null` if this is the last page */
+      next?: string | null
+    }
+    ContentRating: {
+      /**
+       * @description OARS content attributes and their intensity, e.g. `violence-cartoon: mild`
+       * @example {
+       *   "social-chat": "none",
+       *   "violence-cartoon": "mild"
+       * }
+       */
+      attributes: {
+        [key: string]: string | undefined
+      }
+    }
+    CreateAccessToken: {
+      /**
+       * @description Reminder of what the token is used for
+       * @example Release pipeline
+       */
+      name: string
+      /**
+       * @example [
+       *   "submit"
+       * ]
+       */
+      scopes: components['schemas']['TokenScope'][]
+    }
     CreateApp: {
       /** @example com.github.davidmhewitt.torrential */
       app_id: string
       /** @example https://github.com/davidmhewitt/torrential.git */
       repository: string
     }
+    CreatedAccessToken: {
+      access_token: components['schemas']['AccessToken']
+      /**
+       * @description The secret to send as `Authorization: Bearer`, which is only ever shown this once
+       * @example appcenter_pat_6f1ed002ab5595859014ebf0951522d9
+       */
+      token: string
+    }
+    /**
+     * @description Challenge proving control of the domain an app ID belongs to, by serving `token` at
+     * `well_known_url` or adding a TXT record called `dns_record_name` with `token` as its value
+     */
+    DomainChallenge: {
+      /** @example com.example.myapp */
+      app_id: string
+      /** @example _appcenter-verification.example.com */
+      dns_record_name: string
+      /** @example example.com */
+      domain: string
+      /** @example appcenter-verification=4f1c9a7e2b3d8c6f0a5e1d7b9c2f4a6e */
+      token: string
+      /**
+       * @description Whether ownership of the domain has been proven
+       * @example false
+       */
+      verified: boolean
+      /** @example https://example.com/.well-known/appcenter-verification */
+      well_known_url: string
+    }
     ErrorResponse: {
       error: string
       translation_key: components['schemas']['ErrorTranslationKey']
@@ -73,15 +536,108 @@ export interface components {
       | 'registration.generic-problem'
       | 'registration.user-already-exists'
       | 'registration.no-email-permission'
+      | 'registration.resend-throttled'
+      | 'password-reset.invalid-token'
+      | 'password-reset.throttled'
+      | 'password.too-short'
       | 'login.username-password-mismatch'
       | 'login.user-nonexistent'
+      | 'login.account-deactivated'
       | 'logout.generic-problem'
       | 'add-app.generic-problem'
       | 'add-app.non-matching-github-rdnn'
+      | 'add-app.non-matching-forge-rdnn'
       | 'add-app.invalid-repository-url'
       | 'submit-app-update.unable-to-get-url'
+      | 'submit-app-update.version-not-newer'
+      | 'submit-app-update.tag-not-found'
+      | 'submit-app-update.repository-unreachable'
+      | 'submit-app-update.repository-not-allowed'
       | 'stripe-link.no-account'
+      | 'generic.admin-only'
+      | 'admin.cannot-deactivate-self'
+      | 'app-fee.invalid-policy'
+      | 'generic.app-not-owned'
+      | 'app-pricing.invalid'
       | 'generic.app-not-found'
+      | 'generic.category-not-found'
+      | 'generic.invalid-cursor'
+      | 'payments.unsupported-currency'
+      | 'payments.amount-too-low'
+      | 'payments.amount-too-high'
+      | 'payments.below-app-minimum'
+      | 'payments.currency-not-accepted'
+      | 'payments.payment-not-found'
+      | 'webhook.invalid-signature'
+      | 'domain-verification.unavailable'
+      | 'domain-verification.challenge-not-found'
+      | 'domain-verification.failed'
+      | 'generic.insufficient-app-role'
+      | 'app-owners.already-owner'
+      | 'app-owners.not-found'
+      | 'app-owners.last-owner'
+      | 'app-owners.invalid-role'
+      | 'app-invitation.invalid'
+      | 'app-invitation.wrong-account'
+      | 'access-tokens.invalid'
+      | 'access-tokens.not-found'
+      | 'access-tokens.missing-scope'
+    ExportedAppOwnership: {
+      /** @example com.github.davidmhewitt.torrential */
+      app_id: string
+      role: components['schemas']['AppOwnerRole']
+      verified_owner: boolean
+    }
+    ExportedDomainChallenge: {
+      /** @example com.example.myapp */
+      app_id: string
+      /** Format: date-time */
+      created_at: string
+      /** @example example.com */
+      domain: string
+      token: string
+      /** Format: date-time */
+      verified_at?: string | null
+    }
+    /** @description An account on another forge linked to the user. Tokens themselves are never exported. */
+    ExportedForgeAuth: {
+      /** @example gitlab */
+      forge: string
+      forge_user_id: string
+      has_access_token: boolean
+      has_refresh_token: boolean
+    }
+    /** @description The GitHub account linked to the user. Tokens themselves are never exported. */
+    ExportedGithubAuth: {
+      github_user_id?: string | null
+      has_access_token: boolean
+      has_refresh_token: boolean
+    }
+    /** @description An invitation to co-own an app, either sent by the user or to their email address */
+    ExportedInvitation: {
+      /** @example com.github.davidmhewitt.torrential */
+      app_id: string
+      /** Format: date-time */
+      created_at: string
+      /** @example maintainer@example.com */
+      email: string
+      /** Format: uuid */
+      invited_by: string
+      role: components['schemas']['AppOwnerRole']
+    }
+    ExportedProfile: {
+      github_link?: string | null
+      profile_picture_url?: string | null
+    }
+    ExportedUser: {
+      /** Format: date-time */
+      date_joined: string
+      email: string
+      /** Format: uuid */
+      id: string
+      is_active: boolean
+      is_admin: boolean
+    }
     Icon: {
       /**
        * Format: int32
@@ -96,20 +652,167 @@ export interface components {
        */
       width?: number | null
     }
+    LoginUser: {
+      email: string
+      /** Format: password */
+      password: string
+    }
+    PasswordReset: {
+      /** Format: password */
+      password: string
+      /** @description The PASETO token from the password reset email */
+      token: string
+    }
+    PasswordResetRequest: {
+      /** @example developer@example.com */
+      email: string
+    }
+    PaymentReceipt: {
+      /**
+       * @description The amount paid in the smallest unit of `currency`
+       * Format: int64
+       * @example 300
+       */
+      amount: number
+      /** Format: int64 */
+      amount_refunded: number
+      /** @example com.github.davidmhewitt.torrential */
+      app_id?: string | null
+      /** @example cs_test_a1b2c3d4e5f6g7h8i9j0 */
+      checkout_session_id: string
+      /** @example usd */
+      currency: string
+      /**
+       * @description `pending` until Stripe confirms the payment, then `paid`, `partially_refunded` or `refunded`
+       * @example paid
+       */
+      status: string
+    }
+    Release: {
+      /**
+       * @description RFC 3339 formatted release date
+       * @example 2023-03-27T00:00:00+00:00
+       */
+      date?: string | null
+      description?: components['schemas']['TranslatableString'] | null
+      /** @example 3.0.0 */
+      version: string
+    }
+    ResendVerificationRequest: {
+      /** @example developer@example.com */
+      email: string
+    }
+    Screenshot: {
+      caption?: components['schemas']['TranslatableString'] | null
+      images: components['schemas']['ScreenshotImage'][]
+      is_default: boolean
+    }
+    ScreenshotImage: {
+      /**
+       * Format: int32
+       * @example 720
+       */
+      height?: number | null
+      /** @description Whether this is a scaled down version of the source image */
+      thumbnail: boolean
+      /** @example https://example.com/screenshots/torrential.png */
+      url: string
+      /**
+       * Format: int32
+       * @example 1280
+       */
+      width?: number | null
+    }
     StripeAccount: {
       /** @example acct_1NCliJPBGjCwUDHc */
       account_id: string
       /** @example true */
       charges_enabled: boolean
     }
+    Submission: {
+      /** @example com.github.davidmhewitt.torrential */
+      app_id: string
+      /**
+       * @description Branch of the reviews repository the update was pushed to
+       * @example appcenter-website/com.github.davidmhewitt.torrential-3.0.1
+       */
+      branch_name?: string | null
+      /** Format: date-time */
+      created_at: string
+      /** @description Why the submission failed, if it did */
+      error?: string | null
+      /** Format: uuid */
+      id: string
+      /**
+       * Format: int32
+       * @example 1234
+       */
+      pr_number?: number | null
+      /** @example https://github.com/elementary/appcenter-reviews/pull/1234 */
+      pr_url?: string | null
+      state: components['schemas']['SubmissionState']
+      /** Format: date-time */
+      updated_at: string
+      /**
+       * @description The user who submitted the update, unset if their account was deleted
+       * Format: uuid
+       */
+      user_id?: string | null
+      /** @example 3.0.1 */
+      version_tag: string
+    }
     /**
+     * @description Progress of an app update submission through the background worker
+     * @enum {string}
+     */
+    SubmissionState:
+      | 'queued'
+      | 'pushing'
+      | 'pr_open'
+      | 'changes_requested'
+      | 'merged'
+      | 'closed'
+      | 'failed'
+    /**
+     * @description What a personal access token can be used for
+     * @enum {string}
+     */
+    TokenScope: 'submit' | 'read-apps'
+    /**
+     * @description A string with a translation for each locale, keyed by locale name with `C` being the
+     * untranslated original. Once localized, only the best matching translation is kept.
      * @example {
      *   "C": "Welcome",
      *   "ja": "いらっしゃいませ"
      * }
      */
-    TranslatableString: {
-      [key: string]: string | undefined
+    TranslatableString: OneOf<[{ [key: string]: string | undefined }, string]>
+    /** @enum {string} */
+    UrlKind:
+      | 'homepage'
+      | 'bug_tracker'
+      | 'donation'
+      | 'help'
+      | 'translate'
+      | 'faq'
+      | 'contact'
+    UserPage: {
+      items: components['schemas']['UserVisible'][]
+      /** @description Cursor to pass as `after` to get the next page, or `null` if this is the last page */
+      next?: string | null
+    }
+    UserVisible: {
+      email: string
+      /** Format: uuid */
+      id: string
+      is_active: boolean
+      is_admin: boolean
+      /** @description Whether an administrator has deactivated the account */
+      is_deactivated: boolean
+    }
+    Verification: {
+      /** @example true */
+      verified: boolean
     }
   }
   responses: never
@@ -122,86 +825,995 @@ export interface components {
 export type external = Record<string, never>
 
 export interface operations {
-  all_ids: {
-    responses: {
-      /** @description List of all applications ids */
-      200: {
-        content: {
-          'application/json': string[]
-        }
+  set_app_fee: {
+    parameters: {
+      path: {
+        app_id: string
+      }
+    }
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['AppFeeOverride']
       }
     }
-  }
-  recently_added: {
     responses: {
-      /** @description List of recently added applications */
-      200: {
+      /** @description Fee policy overrides for the app were replaced */
+      200: never
+      400: {
         content: {
-          'application/json': components['schemas']['ComponentSummary'][]
+          'application/json': components['schemas']['ErrorResponse']
         }
       }
-    }
-  }
-  recently_updated: {
-    responses: {
-      /** @description List of recently updated applications */
-      200: {
+      403: {
         content: {
-          'application/json': components['schemas']['ComponentSummary'][]
+          'application/json': components['schemas']['ErrorResponse']
         }
       }
-    }
-  }
-  get_apps: {
-    responses: {
-      /** @description A list of apps owned by the current user */
-      200: {
+      404: {
         content: {
-          'application/json': components['schemas']['App'][]
+          'application/json': components['schemas']['ErrorResponse']
         }
       }
     }
   }
-  add_app: {
+  set_owner_verification: {
+    parameters: {
+      path: {
+        app_id: string
+        user_id: string
+      }
+    }
     requestBody: {
       content: {
-        'application/json': components['schemas']['CreateApp']
+        'application/json': components['schemas']['Verification']
       }
     }
     responses: {
-      /** @description App successfully added */
+      /** @description Whether the user is a verified owner of the app was changed */
       200: never
-      400: {
+      403: {
         content: {
           'application/json': components['schemas']['ErrorResponse']
         }
       }
-      /** @description Server error while adding app */
-      500: never
-    }
-  }
-  get_stripe_account: {
-    responses: {
-      /** @description Stripe account details */
-      200: {
+      404: {
         content: {
-          'application/json': components['schemas']['StripeAccount']
+          'application/json': components['schemas']['ErrorResponse']
         }
       }
     }
   }
-  submit: {
-    requestBody: {
-      content: {
-        'application/json': components['schemas']['AppUpdateSubmission']
+  publish_app: {
+    parameters: {
+      path: {
+        app_id: string
       }
     }
     responses: {
-      /** @description App update submitted for processing */
+      /** @description The app appears in listings and search again */
       200: never
-      /** @description Error occurred while submitting app for processing */
-      500: never
-    }
+      403: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+      404: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
+  }
+  unpublish_app: {
+    parameters: {
+      path: {
+        app_id: string
+      }
+    }
+    responses: {
+      /** @description The app no longer appears in listings or search */
+      200: never
+      403: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+      404: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
+  }
+  set_app_verification: {
+    parameters: {
+      path: {
+        app_id: string
+      }
+    }
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['Verification']
+      }
+    }
+    responses: {
+      /** @description Whether the app is verified was changed */
+      200: never
+      403: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+      404: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
+  }
+  list_audit_events: {
+    parameters: {
+      query?: {
+        /** @description Only return events caused by this user */
+        actor_id?: string | null
+        /** @description Only return events about this app */
+        app_id?: string | null
+        /** @description Only return events of this kind */
+        action?: components['schemas']['AuditAction'] | null
+        /** @description The `next` cursor returned with the previous page */
+        after?: string | null
+        /** @description The number of events to return, between 1 and 100. Defaults to 20 */
+        limit?: number | null
+      }
+    }
+    responses: {
+      /** @description Page of audit events, newest first */
+      200: {
+        content: {
+          'application/json': components['schemas']['AuditEventPage']
+        }
+      }
+      400: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+      403: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
+  }
+  list_users: {
+    parameters: {
+      query?: {
+        /** @description Only return users whose email address contains this text, ignoring case */
+        q?: string | null
+        /** @description The `next` cursor returned with the previous page */
+        after?: string | null
+        /** @description The number of users to return, between 1 and 100. Defaults to 20 */
+        limit?: number | null
+      }
+    }
+    responses: {
+      /** @description Page of users, ordered by email address */
+      200: {
+        content: {
+          'application/json': components['schemas']['UserPage']
+        }
+      }
+      400: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+      403: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
+  }
+  deactivate_user: {
+    parameters: {
+      path: {
+        user_id: string
+      }
+    }
+    responses: {
+      /** @description The user can no longer sign in, and their sessions stopped working */
+      200: never
+      400: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+      403: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+      404: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
+  }
+  reactivate_user: {
+    parameters: {
+      path: {
+        user_id: string
+      }
+    }
+    responses: {
+      /** @description The user can sign in again */
+      200: never
+      403: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+      404: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
+  }
+  all_ids: {
+    parameters: {
+      query?: {
+        /** @description The `next` cursor returned with the previous page */
+        after?: string | null
+        /** @description The number of items to return, between 1 and 100. Defaults to 20 */
+        limit?: number | null
+      }
+    }
+    responses: {
+      /** @description Page of published application ids, in alphabetical order */
+      200: {
+        content: {
+          'application/json': components['schemas']['AppIdPage']
+        }
+      }
+      400: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
+  }
+  categories: {
+    responses: {
+      /** @description List of AppStream categories with the number of published apps in each */
+      200: {
+        content: {
+          'application/json': components['schemas']['CategorySummary'][]
+        }
+      }
+    }
+  }
+  category: {
+    parameters: {
+      query?: {
+        /**
+         * @description Collapse translatable strings to the best match for this locale, e.g. `pt_BR`. Takes
+         * precedence over the `Accept-Language` header
+         */
+        locale?: string | null
+      }
+      path: {
+        name: string
+      }
+    }
+    responses: {
+      /** @description The published apps in the given AppStream category */
+      200: {
+        content: {
+          'application/json': components['schemas']['CategoryApps']
+        }
+      }
+      404: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
+  }
+  recently_added: {
+    parameters: {
+      query?: {
+        /** @description The `next` cursor returned with the previous page */
+        after?: string | null
+        /** @description The number of items to return, between 1 and 100. Defaults to 20 */
+        limit?: number | null
+        /**
+         * @description Collapse translatable strings to the best match for this locale, e.g. `pt_BR`. Takes
+         * precedence over the `Accept-Language` header
+         */
+        locale?: string | null
+      }
+    }
+    responses: {
+      /** @description Page of recently added applications, newest first */
+      200: {
+        content: {
+          'application/json': components['schemas']['ComponentSummaryPage']
+        }
+      }
+      400: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
+  }
+  recently_updated: {
+    parameters: {
+      query?: {
+        /** @description The `next` cursor returned with the previous page */
+        after?: string | null
+        /** @description The number of items to return, between 1 and 100. Defaults to 20 */
+        limit?: number | null
+        /**
+         * @description Collapse translatable strings to the best match for this locale, e.g. `pt_BR`. Takes
+         * precedence over the `Accept-Language` header
+         */
+        locale?: string | null
+      }
+    }
+    responses: {
+      /** @description Page of recently updated applications, most recent first */
+      200: {
+        content: {
+          'application/json': components['schemas']['ComponentSummaryPage']
+        }
+      }
+      400: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
+  }
+  search: {
+    parameters: {
+      query: {
+        /** @description The search query, matched against app names, summaries, keywords and descriptions */
+        q: string
+        /**
+         * @description Collapse translatable strings to the best match for this locale, e.g. `pt_BR`. Takes
+         * precedence over the `Accept-Language` header
+         */
+        locale?: string | null
+      }
+    }
+    responses: {
+      /** @description Published applications matching the search query, best matches first */
+      200: {
+        content: {
+          'application/json': components['schemas']['ComponentSummary'][]
+        }
+      }
+    }
+  }
+  get: {
+    parameters: {
+      path: {
+        id: string
+      }
+    }
+    responses: {
+      200: {
+        content: {
+          'application/json': components['schemas']['App']
+        }
+      }
+    }
+  }
+  details: {
+    parameters: {
+      query?: {
+        /**
+         * @description Collapse translatable strings to the best match for this locale, e.g. `pt_BR`. Takes
+         * precedence over the `Accept-Language` header
+         */
+        locale?: string | null
+      }
+      path: {
+        id: string
+      }
+    }
+    responses: {
+      /** @description AppStream metadata for the app, as published in the Flatpak remote */
+      200: {
+        content: {
+          'application/json': components['schemas']['AppDetails']
+        }
+      }
+      404: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
+  }
+  get_access_tokens: {
+    responses: {
+      /** @description The user's personal access tokens, newest first */
+      200: {
+        content: {
+          'application/json': components['schemas']['AccessToken'][]
+        }
+      }
+      401: never
+    }
+  }
+  create_access_token: {
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['CreateAccessToken']
+      }
+    }
+    responses: {
+      /** @description The new token, whose secret isn't shown again */
+      200: {
+        content: {
+          'application/json': components['schemas']['CreatedAccessToken']
+        }
+      }
+      400: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+      401: never
+    }
+  }
+  revoke_access_token: {
+    parameters: {
+      path: {
+        token_id: string
+      }
+    }
+    responses: {
+      /** @description The token can no longer be used */
+      200: never
+      401: never
+      404: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
+  }
+  delete_account: {
+    responses: {
+      /** @description The account was deleted and the user logged out */
+      200: never
+    }
+  }
+  export_account: {
+    responses: {
+      /** @description Everything stored about the current user */
+      200: {
+        content: {
+          'application/json': components['schemas']['AccountExport']
+        }
+      }
+    }
+  }
+  get_app_owners: {
+    parameters: {
+      path: {
+        app_id: string
+      }
+    }
+    responses: {
+      /** @description Owners of the app and their roles */
+      200: {
+        content: {
+          'application/json': components['schemas']['AppCoOwner'][]
+        }
+      }
+      403: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
+  }
+  invite_app_owner: {
+    parameters: {
+      path: {
+        app_id: string
+      }
+    }
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['AppOwnerInvitation']
+      }
+    }
+    responses: {
+      /** @description An invitation was emailed to the address */
+      200: never
+      400: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+      403: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
+  }
+  transfer_app_ownership: {
+    parameters: {
+      path: {
+        app_id: string
+      }
+    }
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['AppOwnershipTransfer']
+      }
+    }
+    responses: {
+      /** @description Primary ownership was transferred, the current user is now a maintainer */
+      200: never
+      403: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+      404: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
+  }
+  remove_app_owner: {
+    parameters: {
+      path: {
+        app_id: string
+        user_id: string
+      }
+    }
+    responses: {
+      /** @description The owner was removed from the app */
+      200: never
+      400: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+      403: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+      404: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
+  }
+  get_apps: {
+    responses: {
+      /** @description A list of apps owned by the current user */
+      200: {
+        content: {
+          'application/json': components['schemas']['App'][]
+        }
+      }
+    }
+  }
+  add_app: {
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['CreateApp']
+      }
+    }
+    responses: {
+      /** @description App successfully added */
+      200: never
+      400: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+      /** @description Server error while adding app */
+      500: never
+    }
+  }
+  set_app_pricing: {
+    parameters: {
+      path: {
+        app_id: string
+      }
+    }
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['AppPricing']
+      }
+    }
+    responses: {
+      /** @description Pricing of the app was updated */
+      200: never
+      400: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+      403: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
+  }
+  create: {
+    responses: {
+      /** @description Stripe account created */
+      200: never
+      /** @description Error occurred while creating stripe account */
+      500: never
+    }
+  }
+  issue_domain_challenge: {
+    parameters: {
+      path: {
+        app_id: string
+      }
+    }
+    responses: {
+      /** @description The challenge to prove control of the app's domain with */
+      200: {
+        content: {
+          'application/json': components['schemas']['DomainChallenge']
+        }
+      }
+      400: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+      403: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
+  }
+  check_domain_challenge: {
+    parameters: {
+      path: {
+        app_id: string
+      }
+    }
+    responses: {
+      /** @description Control of the domain was proven and the user is now a verified owner of the app */
+      200: {
+        content: {
+          'application/json': components['schemas']['DomainChallenge']
+        }
+      }
+      400: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+      404: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
+  }
+  get_earnings: {
+    responses: {
+      /** @description Earnings of the current user's apps per month and currency, newest month first */
+      200: {
+        content: {
+          'application/json': components['schemas']['AppEarnings'][]
+        }
+      }
+    }
+  }
+  enable_app_payments: {
+    parameters: {
+      path: {
+        app_id: string
+      }
+    }
+    responses: {
+      /** @description Payments enabled for app */
+      200: never
+      /** @description Error occurred while enabling payments for app */
+      500: never
+    }
+  }
+  accept_app_invitation: {
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['AcceptAppInvitation']
+      }
+    }
+    responses: {
+      /** @description The current user is now an owner of the app */
+      200: never
+      400: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+      403: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
+  }
+  link: {
+    responses: {
+      /** @description Stripe account successfully linked, follow redirect for Stripe setup */
+      303: {
+        headers: {
+          /** @description Stripe URL the user's browser should be redirected to */
+          Location?: string
+        }
+        content: never
+      }
+      /** @description Error occurred while linking stripe account */
+      500: never
+    }
+  }
+  get_stripe_account: {
+    responses: {
+      /** @description Stripe account details */
+      200: {
+        content: {
+          'application/json': components['schemas']['StripeAccount']
+        }
+      }
+    }
+  }
+  get_submissions: {
+    responses: {
+      /** @description Update submissions for the current user's apps, newest first */
+      200: {
+        content: {
+          'application/json': components['schemas']['Submission'][]
+        }
+      }
+    }
+  }
+  submit: {
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['AppUpdateSubmission']
+      }
+    }
+    responses: {
+      /** @description App update submitted for processing */
+      200: {
+        content: {
+          'application/json': components['schemas']['Submission']
+        }
+      }
+      400: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+      403: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+      404: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+      /** @description Error occurred while submitting app for processing */
+      500: never
+      502: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
+  }
+  github_webhook: {
+    parameters: {
+      header: {
+        /** @description Type of the event */
+        'X-GitHub-Event': string
+        /** @description Signature of the payload using the webhook secret */
+        'X-Hub-Signature-256': string
+      }
+    }
+    /** @description GitHub event, as signed in the `X-Hub-Signature-256` header */
+    requestBody: {
+      content: {
+        'text/plain': string
+      }
+    }
+    responses: {
+      /** @description The event was processed or ignored */
+      200: never
+      400: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+      /** @description The event couldn't be processed */
+      500: never
+    }
+  }
+  session: {
+    parameters: {
+      path: {
+        /** @description ID of the Stripe checkout session, as passed to the success URL */
+        id: string
+      }
+    }
+    responses: {
+      /** @description Receipt for the checkout session */
+      200: {
+        content: {
+          'application/json': components['schemas']['PaymentReceipt']
+        }
+      }
+      404: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
+  }
+  start: {
+    parameters: {
+      query: {
+        app_name: string
+        app_id: string
+        /** @description The amount to pay for the app in the smallest unit of `currency`, e.g. cents or yen */
+        amount: number
+        /** @description Lowercase ISO 4217 code of the currency to pay in, defaults to `usd` */
+        currency?: string | null
+      }
+    }
+    responses: {
+      /** @description Checkout session successfully created, follow redirection */
+      303: {
+        headers: {
+          /** @description Stripe checkout URL the user's browser should be redirected to */
+          Location?: string
+        }
+        content: never
+      }
+      400: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
+  }
+  webhook: {
+    parameters: {
+      header: {
+        /** @description Signature of the payload using the webhook signing secret */
+        'Stripe-Signature': string
+      }
+    }
+    /** @description Stripe event, as signed in the `Stripe-Signature` header */
+    requestBody: {
+      content: {
+        'text/plain': string
+      }
+    }
+    responses: {
+      /** @description The event was processed or ignored */
+      200: never
+      400: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+      /** @description The event couldn't be processed yet, so Stripe should retry it */
+      500: never
+    }
+  }
+  github_callback: {
+    parameters: {
+      query: {
+        code: string
+        state: string
+      }
+    }
+    responses: {
+      303: never
+    }
+  }
+  github_login: {
+    responses: {
+      303: never
+    }
+  }
+  login_user: {
+    requestBody: {
+      content: {
+        'application/x-www-form-urlencoded': components['schemas']['LoginUser']
+      }
+    }
+    responses: {
+      303: never
+    }
+  }
+  reset_password: {
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['PasswordReset']
+      }
+    }
+    responses: {
+      /** @description The password was changed */
+      200: never
+      400: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
+  }
+  request_password_reset: {
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['PasswordResetRequest']
+      }
+    }
+    responses: {
+      /** @description A password reset link was sent, if an active account exists for the email address */
+      200: never
+      429: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
+  }
+  confirm: {
+    parameters: {
+      query: {
+        token: string
+      }
+    }
+    responses: {
+      303: never
+    }
+  }
+  resend_verification: {
+    requestBody: {
+      content: {
+        'application/json': components['schemas']['ResendVerificationRequest']
+      }
+    }
+    responses: {
+      /** @description A new verification link was sent, if an inactive account exists for the email address */
+      200: never
+      429: {
+        content: {
+          'application/json': components['schemas']['ErrorResponse']
+        }
+      }
+    }
   }
   test_auth: {
     responses: {
@@ -211,4 +1823,34 @@ export interface operations {
       403: never
     }
   }
+  forge_callback: {
+    parameters: {
+      query: {
+        code: string
+        state: string
+      }
+      path: {
+        /** @description Identifier of the forge being signed in with, e.g. `gitlab` */
+        forge: string
+      }
+    }
+    responses: {
+      303: never
+      /** @description The forge doesn't exist or isn't configured */
+      404: never
+    }
+  }
+  forge_login: {
+    parameters: {
+      path: {
+        /** @description Identifier of the forge to sign in with, e.g. `gitlab` */
+        forge: string
+      }
+    }
+    responses: {
+      303: never
+      /** @description The forge doesn't exist or isn't configured */
+      404: never
+    }
+  }
 }
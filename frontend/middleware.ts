@@ -24,7 +24,7 @@ export default function middleware(req: NextRequest) {
     !req.nextUrl.pathname.startsWith('/_next')
   ) {
     return NextResponse.redirect(
-      new URL(`/${lng}${req.nextUrl.pathname}`, req.url)
+      new URL(`/${lng}${req.nextUrl.pathname}${req.nextUrl.search}`, req.url)
     )
   }
 